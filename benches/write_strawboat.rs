@@ -34,8 +34,14 @@ fn write(array: &dyn Array) -> Result<()> {
     let options = write::WriteOptions {
         default_compression: CommonCompression::Lz4,
         max_page_size: Some(8192),
+        max_page_bytes: None,
         default_compress_ratio: None,
         forbidden_compressions: vec![],
+        forced_compression: None,
+        forced_compressions: vec![],
+        page_checksum: false,
+        column_dictionary: false,
+        column_options: vec![],
     };
 
     let file = vec![];
@@ -31,7 +31,13 @@ fn write_batches(path: &str, schema: Schema, chunks: &[Chunk<Box<dyn Array>>]) -
         default_compression: CommonCompression::Lz4,
         default_compress_ratio: None,
         max_page_size: Some(8192),
+        max_page_bytes: None,
         forbidden_compressions: vec![],
+        forced_compression: None,
+        forced_compressions: vec![],
+        page_checksum: false,
+        column_dictionary: false,
+        column_options: vec![],
     };
     let mut writer = write::NativeWriter::new(file, schema, options);
 
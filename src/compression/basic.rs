@@ -16,14 +16,20 @@
 // under the License.
 
 use arrow::error::{Error, Result};
+use lz4::block::CompressionMode;
 
 use super::Compression;
 
+/// The general purpose compressions, the levels are only used to compress,
+/// they are not written to the file.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum CommonCompression {
     None,
     Lz4,
-    Zstd,
+    /// lz4 high compression mode with the given level, it's decompressed as [`Self::Lz4`].
+    Lz4Hc(i32),
+    /// zstd with the given level, `0` means the default level of zstd.
+    Zstd(i32),
     Snappy,
 }
 
@@ -40,7 +46,7 @@ impl TryFrom<&Compression> for CommonCompression {
         match value {
             Compression::None => Ok(CommonCompression::None),
             Compression::Lz4 => Ok(CommonCompression::Lz4),
-            Compression::Zstd => Ok(CommonCompression::Zstd),
+            Compression::Zstd => Ok(CommonCompression::Zstd(0)),
             Compression::Snappy => Ok(CommonCompression::Snappy),
             other => Err(Error::OutOfSpec(format!(
                 "Unknown compression codec {other:?}",
@@ -50,19 +56,28 @@ impl TryFrom<&Compression> for CommonCompression {
 }
 
 impl CommonCompression {
+    /// Returns `other` if it's the same codec as `self`, to keep the level of `other`.
+    pub(crate) fn with_level_of(self, other: CommonCompression) -> Self {
+        if self.to_compression() == other.to_compression() {
+            other
+        } else {
+            self
+        }
+    }
+
     pub fn to_compression(&self) -> Compression {
         match self {
             Self::None => Compression::None,
-            Self::Lz4 => Compression::Lz4,
-            Self::Zstd => Compression::Zstd,
+            Self::Lz4 | Self::Lz4Hc(_) => Compression::Lz4,
+            Self::Zstd(_) => Compression::Zstd,
             Self::Snappy => Compression::Snappy,
         }
     }
 
     pub fn decompress(&self, input: &[u8], out_slice: &mut [u8]) -> Result<()> {
         match self {
-            Self::Lz4 => decompress_lz4(input, out_slice),
-            Self::Zstd => decompress_zstd(input, out_slice),
+            Self::Lz4 | Self::Lz4Hc(_) => decompress_lz4(input, out_slice),
+            Self::Zstd(_) => decompress_zstd(input, out_slice),
             Self::Snappy => decompress_snappy(input, out_slice),
             Self::None => {
                 out_slice.copy_from_slice(input);
@@ -73,8 +88,13 @@ impl CommonCompression {
 
     pub fn compress(&self, input_buf: &[u8], output_buf: &mut Vec<u8>) -> Result<usize> {
         match self {
-            Self::Lz4 => compress_lz4(input_buf, output_buf),
-            Self::Zstd => compress_zstd(input_buf, output_buf),
+            Self::Lz4 => compress_lz4(input_buf, output_buf, None),
+            Self::Lz4Hc(level) => compress_lz4(
+                input_buf,
+                output_buf,
+                Some(CompressionMode::HIGHCOMPRESSION(*level)),
+            ),
+            Self::Zstd(level) => compress_zstd(input_buf, output_buf, *level),
             Self::Snappy => compress_snappy(input_buf, output_buf),
             Self::None => {
                 output_buf.extend_from_slice(input_buf);
@@ -105,28 +125,32 @@ pub fn decompress_snappy(input_buf: &[u8], output_buf: &mut [u8]) -> Result<()>
         })
 }
 
-pub fn compress_lz4(input_buf: &[u8], output_buf: &mut Vec<u8>) -> Result<usize> {
+pub fn compress_lz4(
+    input_buf: &[u8],
+    output_buf: &mut Vec<u8>,
+    mode: Option<CompressionMode>,
+) -> Result<usize> {
     let bound = lz4::block::compress_bound(input_buf.len())?;
     let len = output_buf.len();
     output_buf.reserve(bound);
 
     let s = unsafe { core::slice::from_raw_parts_mut(output_buf.as_mut_ptr().add(len), bound) };
 
-    let size = lz4::block::compress_to_buffer(input_buf, None, false, s)
+    let size = lz4::block::compress_to_buffer(input_buf, mode, false, s)
         .map_err(|e| arrow::error::Error::External("Compress lz4 faild".to_owned(), Box::new(e)))?;
 
     unsafe { output_buf.set_len(size + len) };
     Ok(size)
 }
 
-pub fn compress_zstd(input_buf: &[u8], output_buf: &mut Vec<u8>) -> Result<usize> {
+pub fn compress_zstd(input_buf: &[u8], output_buf: &mut Vec<u8>, level: i32) -> Result<usize> {
     let bound = zstd::zstd_safe::compress_bound(input_buf.len());
     let len = output_buf.len();
     output_buf.reserve(bound);
 
     let s = unsafe { core::slice::from_raw_parts_mut(output_buf.as_mut_ptr().add(len), bound) };
 
-    let size = zstd::bulk::compress_to_buffer(input_buf, s, 0).map_err(|e| {
+    let size = zstd::bulk::compress_to_buffer(input_buf, s, level).map_err(|e| {
         arrow::error::Error::External("Compress zstd faild".to_owned(), Box::new(e))
     })?;
 
@@ -78,6 +78,7 @@ impl<O: Offset> BinaryCompression<O> for Dict {
         // dict data use custom encoding
         let mut write_options = write_options.clone();
         write_options.forbidden_compressions.push(Compression::Dict);
+        write_options.forced_compression = None;
         compress_integer(&indices, write_options, output_buf)?;
 
         // data page use plain encoding
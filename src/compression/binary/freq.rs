@@ -90,6 +90,7 @@ impl<O: Offset> BinaryCompression<O> for Freq {
         // Write exceptions
         let mut write_options = write_options.clone();
         write_options.forbidden_compressions.push(Compression::Freq);
+        write_options.forced_compression = None;
 
         // Plain encoding
         for exception in exceptions {
@@ -145,7 +146,8 @@ impl<O: Offset> BinaryCompression<O> for Freq {
     }
 
     fn compress_ratio(&self, stats: &super::BinaryStats<O>) -> f64 {
-        if stats.unique_count <= 1 {
+        // a single value is better written as one value, but can be written as a frequent one
+        if stats.unique_count == 0 {
             return 0.0f64;
         }
 
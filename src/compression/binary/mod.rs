@@ -13,14 +13,13 @@ use arrow::{
 
 use crate::{
     read::{read_basic::read_compress_header, NativeReadBuf},
-    util::env::{check_dict_env, check_freq_env},
     write::WriteOptions,
 };
 
 use super::{
     basic::CommonCompression,
     integer::{Dict, Freq, OneValue},
-    Compression,
+    unsupported_forced_compression, Compression,
 };
 
 pub fn compress_binary<O: Offset>(
@@ -30,7 +29,7 @@ pub fn compress_binary<O: Offset>(
 ) -> Result<()> {
     // choose compressor
     let stats = gen_stats(array);
-    let compressor = choose_compressor(array, &stats, &write_options);
+    let compressor = choose_compressor(array, &stats, &write_options)?;
 
     log::info!(
         "choose binary compression : {:?}",
@@ -182,6 +181,25 @@ pub fn decompress_binary<O: Offset, R: NativeReadBuf>(
     Ok(())
 }
 
+/// Skips the values written by [`compress_binary`] without decompressing them.
+pub fn skip_binary<O: Offset, R: NativeReadBuf>(reader: &mut R) -> Result<()> {
+    let (codec, compressed_size, _uncompressed_size) = read_compress_header(reader)?;
+    let compression = Compression::from_codec(codec)?;
+    skip_bytes(reader, compressed_size)?;
+    // the values of the common compressions follow the offsets
+    if let BinaryCompressor::<O>::Basic(_) = BinaryCompressor::<O>::from_compression(compression)? {
+        let (_, compressed_size, _uncompressed_size) = read_compress_header(reader)?;
+        skip_bytes(reader, compressed_size)?;
+    }
+    Ok(())
+}
+
+fn skip_bytes<R: NativeReadBuf>(reader: &mut R, length: usize) -> Result<()> {
+    let mut bytes = std::io::Read::take(reader, length as u64);
+    std::io::copy(&mut bytes, &mut std::io::sink())?;
+    Ok(())
+}
+
 pub trait BinaryCompression<O: Offset> {
     fn compress(
         &self,
@@ -294,37 +312,28 @@ fn choose_compressor<O: Offset>(
     _value: &BinaryArray<O>,
     stats: &BinaryStats<O>,
     write_options: &WriteOptions,
-) -> BinaryCompressor<O> {
-    #[cfg(debug_assertions)]
-    {
-        if check_freq_env()
-            && !write_options
-                .forbidden_compressions
-                .contains(&Compression::Freq)
-        {
-            return BinaryCompressor::Extend(Box::new(Freq {}));
+) -> Result<BinaryCompressor<O>> {
+    if let Some(compression) = write_options.forced_compression {
+        if let Ok(c) = CommonCompression::try_from(&compression) {
+            return Ok(BinaryCompressor::Basic(
+                c.with_level_of(write_options.default_compression),
+            ));
         }
-        if check_dict_env()
-            && !write_options
-                .forbidden_compressions
-                .contains(&Compression::Dict)
-        {
-            return BinaryCompressor::Extend(Box::new(Dict {}));
+        let c = compressors()
+            .into_iter()
+            .find(|c| c.to_compression() == compression)
+            .ok_or_else(|| unsupported_forced_compression(compression, "binary"))?;
+        if c.compress_ratio(stats) == 0.0 {
+            return Err(unsupported_forced_compression(compression, "binary"));
         }
+        return Ok(BinaryCompressor::Extend(c));
     }
-    // todo
     let basic = BinaryCompressor::Basic(write_options.default_compression);
     if let Some(ratio) = write_options.default_compress_ratio {
         let mut max_ratio = ratio;
         let mut result = basic;
 
-        let compressors: Vec<Box<dyn BinaryCompression<O>>> = vec![
-            Box::new(OneValue {}) as _,
-            Box::new(Freq {}) as _,
-            Box::new(Dict {}) as _,
-        ];
-
-        for encoder in compressors {
+        for encoder in compressors() {
             if write_options
                 .forbidden_compressions
                 .contains(&encoder.to_compression())
@@ -341,8 +350,16 @@ fn choose_compressor<O: Offset>(
                 }
             }
         }
-        result
+        Ok(result)
     } else {
-        basic
+        Ok(basic)
     }
 }
+
+fn compressors<O: Offset>() -> Vec<Box<dyn BinaryCompression<O>>> {
+    vec![
+        Box::new(OneValue {}) as _,
+        Box::new(Freq {}) as _,
+        Box::new(Dict {}) as _,
+    ]
+}
@@ -10,14 +10,13 @@ use rand::{thread_rng, Rng};
 
 use crate::{
     read::{read_basic::read_compress_header, NativeReadBuf},
-    util::env::check_rle_env,
     write::WriteOptions,
 };
 
 use super::{
     basic::CommonCompression,
     integer::{OneValue, RLE},
-    Compression,
+    unsupported_forced_compression, Compression,
 };
 
 pub fn compress_boolean(
@@ -27,7 +26,7 @@ pub fn compress_boolean(
 ) -> Result<()> {
     // choose compressor
     let stats = gen_stats(array);
-    let compressor = choose_compressor(array, &stats, &write_options);
+    let compressor = choose_compressor(array, &stats, &write_options)?;
 
     log::info!(
         "choose boolean compression : {:?}",
@@ -195,26 +194,28 @@ fn choose_compressor(
     _array: &BooleanArray,
     stats: &BooleanStats,
     write_options: &WriteOptions,
-) -> BooleanCompressor {
-    #[cfg(debug_assertions)]
-    {
-        if check_rle_env()
-            && !write_options
-                .forbidden_compressions
-                .contains(&Compression::Rle)
-        {
-            return BooleanCompressor::Extend(Box::new(RLE {}));
+) -> Result<BooleanCompressor> {
+    if let Some(compression) = write_options.forced_compression {
+        if let Ok(c) = CommonCompression::try_from(&compression) {
+            return Ok(BooleanCompressor::Basic(
+                c.with_level_of(write_options.default_compression),
+            ));
+        }
+        let c = compressors()
+            .into_iter()
+            .find(|c| c.to_compression() == compression)
+            .ok_or_else(|| unsupported_forced_compression(compression, "boolean"))?;
+        if c.compress_ratio(stats) == 0.0 {
+            return Err(unsupported_forced_compression(compression, "boolean"));
         }
+        return Ok(BooleanCompressor::Extend(c));
     }
     let basic = BooleanCompressor::Basic(write_options.default_compression);
     if let Some(ratio) = write_options.default_compress_ratio {
         let mut max_ratio = ratio;
         let mut result = basic;
 
-        let compressors: Vec<Box<dyn BooleanCompression>> =
-            vec![Box::new(OneValue {}) as _, Box::new(RLE {}) as _];
-
-        for c in compressors {
+        for c in compressors() {
             if write_options
                 .forbidden_compressions
                 .contains(&c.to_compression())
@@ -232,12 +233,16 @@ fn choose_compressor(
                 }
             }
         }
-        result
+        Ok(result)
     } else {
-        basic
+        Ok(basic)
     }
 }
 
+fn compressors() -> Vec<Box<dyn BooleanCompression>> {
+    vec![Box::new(OneValue {}) as _, Box::new(RLE {}) as _]
+}
+
 fn compress_sample_ratio<C: BooleanCompression>(
     c: &C,
     stats: &BooleanStats,
@@ -80,6 +80,7 @@ impl<T: DoubleType> DoubleCompression<T> for Freq {
         // Write exceptions
         let mut write_options = write_options.clone();
         write_options.forbidden_compressions.push(Compression::Freq);
+        write_options.forced_compression = None;
 
         let exceptions = PrimitiveArray::<T>::from_vec(exceptions);
         compress_double(&exceptions, write_options, output)?;
@@ -127,7 +128,8 @@ impl<T: DoubleType> DoubleCompression<T> for Freq {
     }
 
     fn compress_ratio(&self, stats: &DoubleStats<T>) -> f64 {
-        if stats.unique_count <= 1 {
+        // a single value is better written as one value, but can be written as a frequent one
+        if stats.unique_count == 0 {
             return 0.0f64;
         }
 
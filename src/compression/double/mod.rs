@@ -15,7 +15,6 @@ use rand::{thread_rng, Rng};
 
 use crate::{
     read::{read_basic::read_compress_header, NativeReadBuf},
-    util::env::{check_dict_env, check_freq_env, check_patas_env, check_rle_env},
     write::WriteOptions,
 };
 
@@ -26,7 +25,7 @@ pub use self::traits::DoubleType;
 use super::{
     basic::CommonCompression,
     integer::{Dict, Freq, RLE},
-    is_valid, Compression,
+    is_valid, unsupported_forced_compression, Compression,
 };
 
 pub fn compress_double<T: DoubleType>(
@@ -36,7 +35,7 @@ pub fn compress_double<T: DoubleType>(
 ) -> Result<()> {
     // choose compressor
     let stats = gen_stats(array);
-    let compressor = choose_compressor(array, &stats, &write_options);
+    let compressor = choose_compressor(array, &stats, &write_options)?;
 
     log::info!(
         "choose double compression : {:?}",
@@ -232,50 +231,27 @@ fn choose_compressor<T: DoubleType>(
     _value: &PrimitiveArray<T>,
     stats: &DoubleStats<T>,
     write_options: &WriteOptions,
-) -> DoubleCompressor<T> {
-    #[cfg(debug_assertions)]
-    {
-        if check_freq_env()
-            && !write_options
-                .forbidden_compressions
-                .contains(&Compression::Freq)
-        {
-            return DoubleCompressor::Extend(Box::new(Freq {}));
-        }
-        if check_dict_env()
-            && !write_options
-                .forbidden_compressions
-                .contains(&Compression::Dict)
-        {
-            return DoubleCompressor::Extend(Box::new(Dict {}));
-        }
-        if check_rle_env()
-            && !write_options
-                .forbidden_compressions
-                .contains(&Compression::Rle)
-        {
-            return DoubleCompressor::Extend(Box::new(RLE {}));
+) -> Result<DoubleCompressor<T>> {
+    if let Some(compression) = write_options.forced_compression {
+        if let Ok(c) = CommonCompression::try_from(&compression) {
+            return Ok(DoubleCompressor::Basic(
+                c.with_level_of(write_options.default_compression),
+            ));
         }
-        if check_patas_env()
-            && !write_options
-                .forbidden_compressions
-                .contains(&Compression::Patas)
-        {
-            return DoubleCompressor::Extend(Box::new(Patas {}));
+        let c = compressors()
+            .into_iter()
+            .find(|c| c.to_compression() == compression)
+            .ok_or_else(|| unsupported_forced_compression(compression, "double"))?;
+        if c.compress_ratio(stats) == 0.0 {
+            return Err(unsupported_forced_compression(compression, "double"));
         }
+        return Ok(DoubleCompressor::Extend(c));
     }
     let basic = DoubleCompressor::Basic(write_options.default_compression);
     if let Some(ratio) = write_options.default_compress_ratio {
         let mut max_ratio = ratio;
         let mut result = basic;
-        let compressors: Vec<Box<dyn DoubleCompression<T>>> = vec![
-            Box::new(OneValue {}) as _,
-            Box::new(Freq {}) as _,
-            Box::new(Dict {}) as _,
-            Box::new(Patas {}) as _,
-            Box::new(RLE {}) as _,
-        ];
-        for c in compressors {
+        for c in compressors() {
             if write_options
                 .forbidden_compressions
                 .contains(&c.to_compression())
@@ -300,12 +276,22 @@ fn choose_compressor<T: DoubleType>(
                 }
             }
         }
-        result
+        Ok(result)
     } else {
-        basic
+        Ok(basic)
     }
 }
 
+fn compressors<T: DoubleType>() -> Vec<Box<dyn DoubleCompression<T>>> {
+    vec![
+        Box::new(OneValue {}) as _,
+        Box::new(Freq {}) as _,
+        Box::new(Dict {}) as _,
+        Box::new(Patas {}) as _,
+        Box::new(RLE {}) as _,
+    ]
+}
+
 fn compress_sample_ratio<T: DoubleType, C: DoubleCompression<T>>(
     c: &C,
     stats: &DoubleStats<T>,
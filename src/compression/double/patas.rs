@@ -118,7 +118,11 @@ impl<T: DoubleType> DoubleCompression<T> for Patas {
 
         for _ in 0..length - 1 {
             let packed_data = input.read_u16::<LittleEndian>()?;
-            let (reference_diff, significant_bytes, trailing_zeros) = unpack(packed_data);
+            let (reference_diff, mut significant_bytes, trailing_zeros) = unpack(packed_data);
+            // an equal value of a type narrower than 8 bytes is unpacked as 8 significant bytes
+            if significant_bytes as usize > std::mem::size_of::<T>() {
+                significant_bytes = 0;
+            }
 
             let val: T::BitType = read_value_custom(input, significant_bytes, trailing_zeros);
             input.consume(significant_bytes as usize);
@@ -1,5 +1,4 @@
-use arrow::types::NativeType;
-use num::Float;
+use arrow::types::{f16, NativeType};
 use ordered_float::OrderedFloat;
 
 use std::{
@@ -9,7 +8,7 @@ use std::{
 
 use crate::util::AsBytes;
 
-pub trait DoubleType: AsBytes + Copy + Clone + NativeType + Float {
+pub trait DoubleType: AsBytes + Copy + Clone + NativeType {
     type OrderType: std::fmt::Debug
         + std::fmt::Display
         + Eq
@@ -35,6 +34,8 @@ pub trait DoubleType: AsBytes + Copy + Clone + NativeType + Float {
 
     fn from_order(order: Self::OrderType) -> Self;
 
+    fn is_nan(&self) -> bool;
+
     fn as_bits(&self) -> Self::BitType;
     fn from_bits_val(bits: Self::BitType) -> Self;
 
@@ -56,6 +57,10 @@ macro_rules! double_type {
                 order.0
             }
 
+            fn is_nan(&self) -> bool {
+                <$type>::is_nan(*self)
+            }
+
             fn as_bits(&self) -> Self::BitType {
                 self.to_bits()
             }
@@ -80,3 +85,37 @@ type F64 = OrderedFloat<f64>;
 
 double_type!(f32, F32, u32);
 double_type!(f64, F64, u64);
+
+// f16 has no arithmetic, its values are ordered as f32 which represents them exactly
+impl DoubleType for f16 {
+    type OrderType = F32;
+    type BitType = u16;
+
+    fn as_order(&self) -> Self::OrderType {
+        OrderedFloat(self.to_f32())
+    }
+
+    fn from_order(order: Self::OrderType) -> Self {
+        f16::from_f32(order.0)
+    }
+
+    fn is_nan(&self) -> bool {
+        self.to_f32().is_nan()
+    }
+
+    fn as_bits(&self) -> Self::BitType {
+        self.to_bits()
+    }
+
+    fn from_bits_val(bits: Self::BitType) -> Self {
+        f16::from_bits(bits)
+    }
+
+    fn leading_zeros(bit_value: &Self::BitType) -> u32 {
+        bit_value.leading_zeros()
+    }
+
+    fn trailing_zeros(bit_value: &Self::BitType) -> u32 {
+        bit_value.trailing_zeros()
+    }
+}
@@ -0,0 +1,104 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use arrow::array::FixedSizeBinaryArray;
+use arrow::error::Error;
+use arrow::error::Result;
+
+use crate::compression::{get_bits_needed, CommonCompression, Compression};
+use crate::general_err;
+use crate::write::WriteOptions;
+
+use super::FixedBinaryCompression;
+use super::FixedBinaryStats;
+
+/// Splits the values into byte planes, the `i`-th plane has the `i`-th byte of each value,
+/// the planes are then compressed by the default compression.
+///
+/// The bytes at the same position of values such as decimals or hashes with a common prefix
+/// are often similar, the planes compress better than the interleaved values.
+pub(crate) struct ByteStreamSplit {}
+
+impl FixedBinaryCompression for ByteStreamSplit {
+    fn to_compression(&self) -> Compression {
+        Compression::ByteStreamSplit
+    }
+
+    fn compress_ratio(&self, stats: &FixedBinaryStats) -> f64 {
+        if stats.tuple_count == 0 {
+            return 0.0f64;
+        }
+        // estimates a plane by the bits needed for its distinct bytes
+        let after_size = stats
+            .distinct_bytes
+            .iter()
+            .map(|distinct| {
+                let bits = get_bits_needed(distinct.saturating_sub(1) as u64) as usize;
+                (stats.tuple_count * bits / 8).max(1)
+            })
+            .sum::<usize>();
+        stats.total_bytes as f64 / after_size.max(1) as f64
+    }
+
+    fn compress(
+        &self,
+        array: &FixedSizeBinaryArray,
+        _stats: &FixedBinaryStats,
+        write_options: &WriteOptions,
+        output_buf: &mut Vec<u8>,
+    ) -> Result<usize> {
+        let start = output_buf.len();
+        let size = array.size();
+        let values = array.values().as_slice();
+
+        let mut planes = vec![0u8; values.len()];
+        for (i, value) in values.chunks_exact(size).enumerate() {
+            for (j, byte) in value.iter().enumerate() {
+                planes[j * array.len() + i] = *byte;
+            }
+        }
+
+        let c = write_options.default_compression;
+        output_buf.push(u8::from(c.to_compression()));
+        c.compress(&planes, output_buf)?;
+        Ok(output_buf.len() - start)
+    }
+
+    fn decompress(
+        &self,
+        input: &[u8],
+        length: usize,
+        size: usize,
+        values: &mut Vec<u8>,
+    ) -> Result<()> {
+        let (codec, input) = input
+            .split_first()
+            .ok_or_else(|| general_err!("missing the codec of the byte planes"))?;
+        let c = CommonCompression::try_from(&Compression::from_codec(*codec)?)?;
+        let mut planes = vec![0u8; length * size];
+        c.decompress(input, &mut planes)?;
+
+        let start = values.len();
+        values.resize(start + length * size, 0);
+        for (i, value) in values[start..].chunks_exact_mut(size).enumerate() {
+            for (j, byte) in value.iter_mut().enumerate() {
+                *byte = planes[j * length + i];
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use arrow::array::{Array, FixedSizeBinaryArray};
+use arrow::error::Error;
+use arrow::error::Result;
+use byteorder::{LittleEndian, ReadBytesExt};
+
+use crate::compression::integer::compress_integer;
+use crate::compression::integer::{decompress_integer, Dict, DictEncoder};
+use crate::compression::{get_bits_needed, Compression};
+use crate::general_err;
+use crate::util::AsBytes;
+use crate::write::WriteOptions;
+
+use super::FixedBinaryCompression;
+use super::FixedBinaryStats;
+
+impl FixedBinaryCompression for Dict {
+    fn to_compression(&self) -> Compression {
+        Compression::Dict
+    }
+
+    fn compress_ratio(&self, stats: &FixedBinaryStats) -> f64 {
+        const MIN_DICT_RATIO: usize = 3;
+        if stats.unique_count * MIN_DICT_RATIO >= stats.tuple_count {
+            return 0.0f64;
+        }
+
+        let mut after_size = stats.unique_count * stats.size
+            + stats.tuple_count * (get_bits_needed(stats.unique_count as u64) / 8) as usize;
+        after_size += (stats.tuple_count) * 2 / 128;
+        stats.total_bytes as f64 / after_size as f64
+    }
+
+    fn compress(
+        &self,
+        array: &FixedSizeBinaryArray,
+        _stats: &FixedBinaryStats,
+        write_options: &WriteOptions,
+        output_buf: &mut Vec<u8>,
+    ) -> Result<usize> {
+        let start = output_buf.len();
+        let size = array.size();
+        let mut encoder = DictEncoder::with_capacity(array.len());
+
+        for i in 0..array.len() {
+            if array.is_null(i) && !encoder.is_empty() {
+                encoder.push_last_index();
+            } else {
+                let data = array.values().clone().sliced(i * size, size);
+                encoder.push(&data);
+            }
+        }
+
+        let indices = encoder.take_indices();
+        // dict data use custom encoding
+        let mut write_options = write_options.clone();
+        write_options.forbidden_compressions.push(Compression::Dict);
+        write_options.forced_compression = None;
+        compress_integer(&indices, write_options, output_buf)?;
+
+        // the values have the same size, they are written without lengths
+        let sets = encoder.get_sets();
+        output_buf.extend_from_slice(&(sets.len() as u32).to_le_bytes());
+        for val in sets.iter() {
+            output_buf.extend_from_slice(val.as_bytes());
+        }
+
+        Ok(output_buf.len() - start)
+    }
+
+    fn decompress(
+        &self,
+        mut input: &[u8],
+        length: usize,
+        size: usize,
+        values: &mut Vec<u8>,
+    ) -> Result<()> {
+        let mut indices: Vec<u32> = Vec::new();
+        decompress_integer(&mut input, length, &mut indices, &mut vec![])?;
+
+        let data_size = input.read_u32::<LittleEndian>()? as usize;
+        if input.len() < data_size * size {
+            return Err(general_err!("data size is less than {}", data_size * size));
+        }
+        let data = &input[..data_size * size];
+
+        values.reserve(indices.len() * size);
+        for i in indices.iter() {
+            let off = *i as usize * size;
+            let val = data
+                .get(off..off + size)
+                .ok_or_else(|| general_err!("dict index {} is out of bounds", i))?;
+            values.extend_from_slice(val);
+        }
+        Ok(())
+    }
+}
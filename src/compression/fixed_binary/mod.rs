@@ -0,0 +1,254 @@
+mod byte_stream_split;
+mod dict;
+mod one_value;
+
+use std::collections::HashMap;
+
+use arrow::{
+    array::{Array, FixedSizeBinaryArray},
+    error::{Error, Result},
+};
+
+use crate::{
+    read::{read_basic::read_compress_header, NativeReadBuf},
+    write::WriteOptions,
+};
+
+use self::byte_stream_split::ByteStreamSplit;
+
+use super::{
+    basic::CommonCompression,
+    binary::U8Buffer,
+    integer::{Dict, OneValue},
+    unsupported_forced_compression, Compression,
+};
+
+pub fn compress_fixed_binary(
+    array: &FixedSizeBinaryArray,
+    buf: &mut Vec<u8>,
+    write_options: WriteOptions,
+) -> Result<()> {
+    // choose compressor
+    let stats = gen_stats(array);
+    let compressor = choose_compressor(array, &stats, &write_options)?;
+
+    log::info!(
+        "choose fixed binary compression : {:?}",
+        compressor.to_compression()
+    );
+
+    let codec = u8::from(compressor.to_compression());
+    buf.extend_from_slice(&codec.to_le_bytes());
+    let pos = buf.len();
+    buf.extend_from_slice(&[0u8; 8]);
+
+    let input_buf = array.values().as_slice();
+    let compressed_size = match compressor {
+        FixedBinaryCompressor::Basic(c) => c.compress(input_buf, buf)?,
+        FixedBinaryCompressor::Extend(c) => c.compress(array, &stats, &write_options, buf)?,
+    };
+    buf[pos..pos + 4].copy_from_slice(&(compressed_size as u32).to_le_bytes());
+    buf[pos + 4..pos + 8].copy_from_slice(&(input_buf.len() as u32).to_le_bytes());
+    Ok(())
+}
+
+pub fn decompress_fixed_binary<R: NativeReadBuf>(
+    reader: &mut R,
+    length: usize,
+    size: usize,
+    values: &mut Vec<u8>,
+    scratch: &mut Vec<u8>,
+) -> Result<()> {
+    let (codec, compressed_size, _uncompressed_size) = read_compress_header(reader)?;
+    let compression = Compression::from_codec(codec)?;
+
+    // already fit in buffer
+    let mut use_inner = false;
+    reader.fill_buf()?;
+    let input = if reader.buffer_bytes().len() >= compressed_size {
+        use_inner = true;
+        reader.buffer_bytes()
+    } else {
+        scratch.resize(compressed_size, 0);
+        reader.read_exact(scratch.as_mut_slice())?;
+        scratch.as_slice()
+    };
+
+    let compressor = FixedBinaryCompressor::from_compression(compression)?;
+    match compressor {
+        FixedBinaryCompressor::Basic(c) => {
+            let out_len = length * size;
+            values.reserve(out_len);
+            let out_slice = unsafe {
+                core::slice::from_raw_parts_mut(values.as_mut_ptr().add(values.len()), out_len)
+            };
+            c.decompress(&input[..compressed_size], out_slice)?;
+            unsafe { values.set_len(values.len() + out_len) };
+        }
+        FixedBinaryCompressor::Extend(c) => {
+            c.decompress(&input[..compressed_size], length, size, values)?;
+        }
+    }
+
+    if use_inner {
+        reader.consume(compressed_size);
+    }
+    Ok(())
+}
+
+pub trait FixedBinaryCompression {
+    fn compress(
+        &self,
+        array: &FixedSizeBinaryArray,
+        stats: &FixedBinaryStats,
+        write_options: &WriteOptions,
+        output: &mut Vec<u8>,
+    ) -> Result<usize>;
+
+    fn decompress(
+        &self,
+        input: &[u8],
+        length: usize,
+        size: usize,
+        values: &mut Vec<u8>,
+    ) -> Result<()>;
+
+    fn compress_ratio(&self, stats: &FixedBinaryStats) -> f64;
+    fn to_compression(&self) -> Compression;
+}
+
+enum FixedBinaryCompressor {
+    Basic(CommonCompression),
+    Extend(Box<dyn FixedBinaryCompression>),
+}
+
+impl FixedBinaryCompressor {
+    fn to_compression(&self) -> Compression {
+        match self {
+            Self::Basic(c) => c.to_compression(),
+            Self::Extend(c) => c.to_compression(),
+        }
+    }
+
+    fn from_compression(compression: Compression) -> Result<Self> {
+        if let Ok(c) = CommonCompression::try_from(&compression) {
+            return Ok(Self::Basic(c));
+        }
+        match compression {
+            Compression::OneValue => Ok(Self::Extend(Box::new(OneValue {}))),
+            Compression::Dict => Ok(Self::Extend(Box::new(Dict {}))),
+            Compression::ByteStreamSplit => Ok(Self::Extend(Box::new(ByteStreamSplit {}))),
+            other => Err(Error::OutOfSpec(format!(
+                "Unknown compression codec {other:?}",
+            ))),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Debug)]
+pub struct FixedBinaryStats {
+    tuple_count: usize,
+    size: usize,
+    total_bytes: usize,
+    unique_count: usize,
+    null_count: usize,
+    distinct_values: HashMap<U8Buffer, usize>,
+    /// The number of distinct bytes at each position of the values.
+    distinct_bytes: Vec<usize>,
+}
+
+fn gen_stats(array: &FixedSizeBinaryArray) -> FixedBinaryStats {
+    let size = array.size();
+    let mut stats = FixedBinaryStats {
+        tuple_count: array.len(),
+        size,
+        total_bytes: array.values().len(),
+        unique_count: 0,
+        null_count: array.null_count(),
+        distinct_values: HashMap::new(),
+        distinct_bytes: vec![0; size],
+    };
+
+    let mut seen_bytes = vec![[false; 256]; size];
+    for i in 0..array.len() {
+        if array.is_null(i) {
+            continue;
+        }
+        let value = array.values().clone().sliced(i * size, size);
+        for (seen, byte) in seen_bytes.iter_mut().zip(value.iter()) {
+            seen[*byte as usize] = true;
+        }
+        *stats.distinct_values.entry(U8Buffer(value)).or_insert(0) += 1;
+    }
+
+    stats.unique_count = stats.distinct_values.len();
+    stats.distinct_bytes = seen_bytes
+        .iter()
+        .map(|seen| seen.iter().filter(|s| **s).count())
+        .collect();
+    stats
+}
+
+fn choose_compressor(
+    _value: &FixedSizeBinaryArray,
+    stats: &FixedBinaryStats,
+    write_options: &WriteOptions,
+) -> Result<FixedBinaryCompressor> {
+    if let Some(compression) = write_options.forced_compression {
+        if let Ok(c) = CommonCompression::try_from(&compression) {
+            return Ok(FixedBinaryCompressor::Basic(
+                c.with_level_of(write_options.default_compression),
+            ));
+        }
+        let c = compressors()
+            .into_iter()
+            .find(|c| c.to_compression() == compression)
+            .ok_or_else(|| unsupported_forced_compression(compression, "fixed size binary"))?;
+        if c.compress_ratio(stats) == 0.0 {
+            return Err(unsupported_forced_compression(
+                compression,
+                "fixed size binary",
+            ));
+        }
+        return Ok(FixedBinaryCompressor::Extend(c));
+    }
+    let basic = FixedBinaryCompressor::Basic(write_options.default_compression);
+    if let Some(ratio) = write_options.default_compress_ratio {
+        let mut max_ratio = ratio;
+        let mut result = basic;
+
+        for encoder in compressors() {
+            let compression = encoder.to_compression();
+            if write_options.forbidden_compressions.contains(&compression) {
+                continue;
+            }
+            // the byte planes are only useful with a general purpose compression
+            if compression == Compression::ByteStreamSplit
+                && write_options.default_compression == CommonCompression::None
+            {
+                continue;
+            }
+            let r = encoder.compress_ratio(stats);
+            if r > max_ratio {
+                max_ratio = r;
+                result = FixedBinaryCompressor::Extend(encoder);
+
+                if r == stats.tuple_count as f64 {
+                    break;
+                }
+            }
+        }
+        Ok(result)
+    } else {
+        Ok(basic)
+    }
+}
+
+fn compressors() -> Vec<Box<dyn FixedBinaryCompression>> {
+    vec![
+        Box::new(OneValue {}) as _,
+        Box::new(Dict {}) as _,
+        Box::new(ByteStreamSplit {}) as _,
+    ]
+}
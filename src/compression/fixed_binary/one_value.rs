@@ -0,0 +1,76 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use arrow::array::FixedSizeBinaryArray;
+use arrow::error::Error;
+use arrow::error::Result;
+
+use crate::compression::integer::OneValue;
+use crate::compression::Compression;
+use crate::general_err;
+use crate::write::WriteOptions;
+
+use super::FixedBinaryCompression;
+use super::FixedBinaryStats;
+
+impl FixedBinaryCompression for OneValue {
+    fn to_compression(&self) -> Compression {
+        Compression::OneValue
+    }
+
+    fn compress_ratio(&self, stats: &FixedBinaryStats) -> f64 {
+        if stats.unique_count <= 1 {
+            stats.tuple_count as f64
+        } else {
+            0.0f64
+        }
+    }
+
+    fn compress(
+        &self,
+        array: &FixedSizeBinaryArray,
+        _stats: &FixedBinaryStats,
+        _write_options: &WriteOptions,
+        output_buf: &mut Vec<u8>,
+    ) -> Result<usize> {
+        let start = output_buf.len();
+        match array.iter().flatten().next() {
+            Some(val) => output_buf.extend_from_slice(val),
+            None => output_buf.resize(start + array.size(), 0),
+        }
+        Ok(output_buf.len() - start)
+    }
+
+    fn decompress(
+        &self,
+        input: &[u8],
+        length: usize,
+        size: usize,
+        values: &mut Vec<u8>,
+    ) -> Result<()> {
+        if input.len() < size {
+            return Err(general_err!("data size is less than {}", size));
+        }
+        let val = &input[..size];
+
+        values.reserve(length * size);
+        for _ in 0..length {
+            values.extend_from_slice(val);
+        }
+        Ok(())
+    }
+}
@@ -16,7 +16,6 @@ use rand::{thread_rng, Rng};
 
 use crate::{
     read::{read_basic::read_compress_header, NativeReadBuf},
-    util::env::{check_bitpack_env, check_dict_env, check_freq_env, check_rle_env},
     write::WriteOptions,
 };
 
@@ -30,7 +29,7 @@ pub use self::one_value::OneValue;
 pub use self::rle::RLE;
 pub use self::traits::IntegerType;
 
-use super::{basic::CommonCompression, is_valid, Compression};
+use super::{basic::CommonCompression, is_valid, unsupported_forced_compression, Compression};
 
 pub fn compress_integer<T: IntegerType>(
     array: &PrimitiveArray<T>,
@@ -39,7 +38,7 @@ pub fn compress_integer<T: IntegerType>(
 ) -> Result<()> {
     // choose compressor
     let stats = gen_stats(array);
-    let compressor = choose_compressor(array, &stats, &write_options);
+    let compressor = choose_compressor(array, &stats, &write_options)?;
 
     log::info!(
         "choose integer compression : {:?}",
@@ -232,51 +231,27 @@ fn choose_compressor<T: IntegerType>(
     _value: &PrimitiveArray<T>,
     stats: &IntegerStats<T>,
     write_options: &WriteOptions,
-) -> IntCompressor<T> {
-    #[cfg(debug_assertions)]
-    {
-        if check_freq_env()
-            && !write_options
-                .forbidden_compressions
-                .contains(&Compression::Freq)
-        {
-            return IntCompressor::Extend(Box::new(Freq {}));
-        }
-        if check_dict_env()
-            && !write_options
-                .forbidden_compressions
-                .contains(&Compression::Dict)
-        {
-            return IntCompressor::Extend(Box::new(Dict {}));
-        }
-        if check_rle_env()
-            && !write_options
-                .forbidden_compressions
-                .contains(&Compression::Rle)
-        {
-            return IntCompressor::Extend(Box::new(RLE {}));
+) -> Result<IntCompressor<T>> {
+    if let Some(compression) = write_options.forced_compression {
+        if let Ok(c) = CommonCompression::try_from(&compression) {
+            return Ok(IntCompressor::Basic(
+                c.with_level_of(write_options.default_compression),
+            ));
         }
-        if check_bitpack_env()
-            && !write_options
-                .forbidden_compressions
-                .contains(&Compression::Bitpacking)
-        {
-            return IntCompressor::Extend(Box::new(Bitpacking {}));
+        let c = compressors()
+            .into_iter()
+            .find(|c| c.to_compression() == compression)
+            .ok_or_else(|| unsupported_forced_compression(compression, "integer"))?;
+        if c.compress_ratio(stats) == 0.0 {
+            return Err(unsupported_forced_compression(compression, "integer"));
         }
+        return Ok(IntCompressor::Extend(c));
     }
     let basic = IntCompressor::Basic(write_options.default_compression);
     if let Some(ratio) = write_options.default_compress_ratio {
         let mut max_ratio = ratio;
         let mut result = basic;
-        let compressors: Vec<Box<dyn IntegerCompression<T>>> = vec![
-            Box::new(OneValue {}) as _,
-            Box::new(Freq {}) as _,
-            Box::new(Dict {}) as _,
-            Box::new(RLE {}) as _,
-            Box::new(Bitpacking {}) as _,
-            Box::new(DeltaBitpacking {}) as _,
-        ];
-        for c in compressors {
+        for c in compressors() {
             if write_options
                 .forbidden_compressions
                 .contains(&c.to_compression())
@@ -301,12 +276,23 @@ fn choose_compressor<T: IntegerType>(
                 }
             }
         }
-        result
+        Ok(result)
     } else {
-        basic
+        Ok(basic)
     }
 }
 
+fn compressors<T: IntegerType>() -> Vec<Box<dyn IntegerCompression<T>>> {
+    vec![
+        Box::new(OneValue {}) as _,
+        Box::new(Freq {}) as _,
+        Box::new(Dict {}) as _,
+        Box::new(RLE {}) as _,
+        Box::new(Bitpacking {}) as _,
+        Box::new(DeltaBitpacking {}) as _,
+    ]
+}
+
 fn compress_sample_ratio<T: IntegerType, C: IntegerCompression<T>>(
     c: &C,
     stats: &IntegerStats<T>,
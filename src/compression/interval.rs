@@ -0,0 +1,123 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use arrow::array::PrimitiveArray;
+use arrow::datatypes::DataType;
+use arrow::error::Result;
+use arrow::types::{days_ms, months_days_ns, NativeType};
+
+use crate::read::NativeReadBuf;
+use crate::write::WriteOptions;
+
+use super::integer::{compress_integer, decompress_integer};
+
+/// The interval types, their values are split into the integer components,
+/// which are compressed one after another by the integer compressions.
+pub trait IntervalType: NativeType {
+    fn compress(
+        array: &PrimitiveArray<Self>,
+        write_options: WriteOptions,
+        buf: &mut Vec<u8>,
+    ) -> Result<()>;
+
+    fn decompress<R: NativeReadBuf>(
+        reader: &mut R,
+        length: usize,
+        output: &mut Vec<Self>,
+        scratch: &mut Vec<u8>,
+    ) -> Result<()>;
+}
+
+impl IntervalType for days_ms {
+    fn compress(
+        array: &PrimitiveArray<Self>,
+        write_options: WriteOptions,
+        buf: &mut Vec<u8>,
+    ) -> Result<()> {
+        let days = component(array, DataType::Int32, |v| v.days());
+        compress_integer(&days, write_options.clone(), buf)?;
+        let milliseconds = component(array, DataType::Int32, |v| v.milliseconds());
+        compress_integer(&milliseconds, write_options, buf)
+    }
+
+    fn decompress<R: NativeReadBuf>(
+        reader: &mut R,
+        length: usize,
+        output: &mut Vec<Self>,
+        scratch: &mut Vec<u8>,
+    ) -> Result<()> {
+        let mut days: Vec<i32> = Vec::with_capacity(length);
+        decompress_integer(reader, length, &mut days, scratch)?;
+        let mut milliseconds: Vec<i32> = Vec::with_capacity(length);
+        decompress_integer(reader, length, &mut milliseconds, scratch)?;
+
+        output.extend(
+            days.into_iter()
+                .zip(milliseconds)
+                .map(|(days, milliseconds)| days_ms::new(days, milliseconds)),
+        );
+        Ok(())
+    }
+}
+
+impl IntervalType for months_days_ns {
+    fn compress(
+        array: &PrimitiveArray<Self>,
+        write_options: WriteOptions,
+        buf: &mut Vec<u8>,
+    ) -> Result<()> {
+        let months = component(array, DataType::Int32, |v| v.months());
+        compress_integer(&months, write_options.clone(), buf)?;
+        let days = component(array, DataType::Int32, |v| v.days());
+        compress_integer(&days, write_options.clone(), buf)?;
+        let nanoseconds = component(array, DataType::Int64, |v| v.ns());
+        compress_integer(&nanoseconds, write_options, buf)
+    }
+
+    fn decompress<R: NativeReadBuf>(
+        reader: &mut R,
+        length: usize,
+        output: &mut Vec<Self>,
+        scratch: &mut Vec<u8>,
+    ) -> Result<()> {
+        let mut months: Vec<i32> = Vec::with_capacity(length);
+        decompress_integer(reader, length, &mut months, scratch)?;
+        let mut days: Vec<i32> = Vec::with_capacity(length);
+        decompress_integer(reader, length, &mut days, scratch)?;
+        let mut nanoseconds: Vec<i64> = Vec::with_capacity(length);
+        decompress_integer(reader, length, &mut nanoseconds, scratch)?;
+
+        output.extend(
+            months
+                .into_iter()
+                .zip(days)
+                .zip(nanoseconds)
+                .map(|((months, days), ns)| months_days_ns::new(months, days, ns)),
+        );
+        Ok(())
+    }
+}
+
+// the component of the values, with the validity of the array
+fn component<T: NativeType, C: NativeType>(
+    array: &PrimitiveArray<T>,
+    data_type: DataType,
+    f: impl Fn(&T) -> C,
+) -> PrimitiveArray<C> {
+    let values = array.values().iter().map(f).collect::<Vec<_>>();
+    PrimitiveArray::new(data_type, values.into(), array.validity().cloned())
+}
@@ -20,9 +20,14 @@ mod basic;
 pub mod binary;
 pub mod boolean;
 pub mod double;
+pub mod fixed_binary;
 pub mod integer;
+pub mod interval;
 
-use arrow::{bitmap::Bitmap, error::Result};
+use arrow::{
+    bitmap::Bitmap,
+    error::{Error, Result},
+};
 
 pub use basic::CommonCompression;
 
@@ -48,6 +53,7 @@ pub enum Compression {
     Bitpacking,
     DeltaBitpacking,
     Patas,
+    ByteStreamSplit,
 }
 
 impl Default for Compression {
@@ -74,6 +80,7 @@ impl Compression {
             14 => Ok(Compression::Bitpacking),
             15 => Ok(Compression::DeltaBitpacking),
             16 => Ok(Compression::Patas),
+            17 => Ok(Compression::ByteStreamSplit),
 
             other => Err(arrow::error::Error::OutOfSpec(format!(
                 "Unknown compression codec {other}",
@@ -103,6 +110,7 @@ impl From<Compression> for u8 {
             Compression::Bitpacking => 14,
             Compression::DeltaBitpacking => 15,
             Compression::Patas => 16,
+            Compression::ByteStreamSplit => 17,
         }
     }
 }
@@ -115,6 +123,12 @@ pub(crate) fn is_valid(validity: &Option<&Bitmap>, i: usize) -> bool {
     }
 }
 
+pub(crate) fn unsupported_forced_compression(compression: Compression, type_name: &str) -> Error {
+    Error::InvalidArgumentError(format!(
+        "The forced compression {compression:?} can't be used for the {type_name} page"
+    ))
+}
+
 #[inline]
 pub(crate) fn get_bits_needed(input: u64) -> u32 {
     u64::BITS - input.leading_zeros()
@@ -22,23 +22,43 @@ mod compression;
 #[macro_use]
 mod errors;
 
-pub use compression::CommonCompression;
+pub use compression::{CommonCompression, Compression};
 
 pub mod read;
 pub mod stat;
+pub mod statistics;
+pub mod variant;
 pub mod write;
 
+pub use statistics::Statistics;
+
+use arrow::datatypes::{Metadata, Schema};
+
 #[macro_use]
 mod util;
 
-const ARROW_MAGIC: [u8; 6] = [b'A', b'R', b'R', b'O', b'W', b'2'];
-pub(crate) const CONTINUATION_MARKER: [u8; 4] = [0xff; 4];
+/// The magic bytes at the start and at the end of a strawboat file.
+pub const STRAWBOAT_MAGIC: [u8; 6] = *b"STRAWB";
+/// The version of the file format, it's increased when the layout of the file changes.
+pub const FORMAT_VERSION: u16 = 1;
+
+/// The footer of a strawboat file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FileMeta {
+    pub schema: Schema,
+    // metas of the leaf columns
+    pub metas: Vec<ColumnMeta>,
+    // application metadata, set by `NativeWriter::set_key_value_metadata`
+    pub key_value_metadata: Metadata,
+}
 
 #[derive(
     Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize,
 )]
 pub struct ColumnMeta {
     pub offset: u64,
+    // statistics of all the pages
+    pub statistics: Statistics,
     pub pages: Vec<PageMeta>,
 }
 
@@ -48,16 +68,14 @@ impl ColumnMeta {
         assert!(start_page_index < self.pages.len());
         assert!(end_page_index <= self.pages.len());
 
-        let offset = self
-            .pages
-            .iter()
-            .take(start_page_index)
-            .map(|meta| meta.length)
-            .sum::<u64>()
-            + self.offset;
+        let offset = self.pages[start_page_index].start_offset();
         let pages = self.pages[start_page_index..end_page_index].to_vec();
 
-        Self { offset, pages }
+        Self {
+            offset,
+            statistics: self.statistics.clone(),
+            pages,
+        }
     }
 
     pub fn skip_one_page(&self) -> Self {
@@ -73,8 +91,39 @@ impl ColumnMeta {
     Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize,
 )]
 pub struct PageMeta {
+    // offset of this page in the file, pages of a column are not
+    // contiguous if the file is written by multiple chunks
+    pub offset: u64,
     // compressed size of this page
     pub length: u64,
     // num values(rows) of this page
     pub num_values: u64,
+    pub statistics: Statistics,
+    // CRC32C of the page, if it's enabled by `WriteOptions::page_checksum`
+    pub checksum: Option<u32>,
+    // the dictionary shared by the pages of the column chunk,
+    // if it's enabled by `WriteOptions::column_dictionary`
+    pub dictionary: Option<DictionaryMeta>,
+}
+
+impl PageMeta {
+    /// The offset to start reading the page from, it's the offset of the dictionary
+    /// of the page if it has one, as the dictionary is written before the pages.
+    pub fn start_offset(&self) -> u64 {
+        match &self.dictionary {
+            Some(dictionary) => dictionary.offset,
+            None => self.offset,
+        }
+    }
+}
+
+/// The location of the dictionary of a column chunk, it's written once before
+/// the pages of the column chunk which only hold the indices of the values.
+#[derive(
+    Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize,
+)]
+pub struct DictionaryMeta {
+    pub offset: u64,
+    pub length: u64,
+    pub checksum: Option<u32>,
 }
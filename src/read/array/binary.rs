@@ -18,13 +18,14 @@
 use std::io::Cursor;
 use std::marker::PhantomData;
 
+use super::dictionary::*;
 use crate::compression::binary::decompress_binary;
 use crate::read::{read_basic::*, BufReader, NativeReadBuf, PageIterator};
 use crate::PageMeta;
 use arrow::array::{Array, BinaryArray, Utf8Array};
 use arrow::bitmap::{Bitmap, MutableBitmap};
 use arrow::buffer::Buffer;
-use arrow::datatypes::DataType;
+use arrow::datatypes::{DataType, PhysicalType};
 use arrow::error::Result;
 use arrow::io::parquet::read::{InitNested, NestedState};
 use arrow::offset::OffsetsBuffer;
@@ -41,6 +42,7 @@ where
     is_nullable: bool,
     data_type: DataType,
     scratch: Vec<u8>,
+    dictionary: DictionaryCache,
     _phantom: PhantomData<O>,
 }
 
@@ -55,6 +57,7 @@ where
             is_nullable,
             data_type,
             scratch: vec![],
+            dictionary: DictionaryCache::default(),
             _phantom: PhantomData,
         }
     }
@@ -79,13 +82,28 @@ where
         let mut offsets: Vec<O> = Vec::with_capacity(length + 1);
         let mut values = Vec::with_capacity(0);
 
-        decompress_binary(
-            &mut reader,
-            length,
-            &mut offsets,
-            &mut values,
-            &mut self.scratch,
-        )?;
+        match self.iter.dictionary() {
+            Some((offset, bytes)) => {
+                let dictionary =
+                    self.dictionary
+                        .values(offset, bytes, &binary_dictionary_type::<O>())?;
+                decompress_binary_keys(
+                    &mut reader,
+                    length,
+                    dictionary,
+                    &mut offsets,
+                    &mut values,
+                    &mut self.scratch,
+                )?
+            }
+            None => decompress_binary(
+                &mut reader,
+                length,
+                &mut offsets,
+                &mut values,
+                &mut self.scratch,
+            )?,
+        }
 
         try_new_binary_array(
             self.data_type.clone(),
@@ -131,6 +149,7 @@ where
     leaf: ColumnDescriptor,
     init: Vec<InitNested>,
     scratch: Vec<u8>,
+    dictionary: DictionaryCache,
     _phantom: PhantomData<O>,
 }
 
@@ -151,6 +170,7 @@ where
             leaf,
             init,
             scratch: vec![],
+            dictionary: DictionaryCache::default(),
             _phantom: PhantomData,
         }
     }
@@ -178,13 +198,28 @@ where
         let mut offsets: Vec<O> = Vec::with_capacity(length + 1);
         let mut values = Vec::with_capacity(0);
 
-        decompress_binary(
-            &mut reader,
-            length,
-            &mut offsets,
-            &mut values,
-            &mut self.scratch,
-        )?;
+        match self.iter.dictionary() {
+            Some((offset, bytes)) => {
+                let dictionary =
+                    self.dictionary
+                        .values(offset, bytes, &binary_dictionary_type::<O>())?;
+                decompress_binary_keys(
+                    &mut reader,
+                    length,
+                    dictionary,
+                    &mut offsets,
+                    &mut values,
+                    &mut self.scratch,
+                )?
+            }
+            None => decompress_binary(
+                &mut reader,
+                length,
+                &mut offsets,
+                &mut values,
+                &mut self.scratch,
+            )?,
+        }
 
         let array = try_new_binary_array(
             self.data_type.clone(),
@@ -243,13 +278,35 @@ pub fn read_binary<O: Offset, R: NativeReadBuf>(
     let mut offsets: Vec<O> = Vec::with_capacity(out_off_len);
     let mut values: Vec<u8> = Vec::with_capacity(out_buf_len);
 
+    let mut dictionaries = DictionaryCache::default();
+    let mut position = page_metas
+        .first()
+        .map(|p| p.start_offset())
+        .unwrap_or_default();
     for page_meta in page_metas {
+        let dictionary = dictionaries.read(
+            reader,
+            &mut position,
+            &page_meta,
+            &binary_dictionary_type::<O>(),
+        )?;
+        skip_to_page(reader, &mut position, &page_meta)?;
         let length = page_meta.num_values as usize;
         if let Some(ref mut validity_builder) = validity_builder {
             read_validity(reader, length, validity_builder)?;
         }
 
-        decompress_binary(reader, length, &mut offsets, &mut values, &mut scratch)?;
+        match dictionary {
+            Some(dictionary) => decompress_binary_keys(
+                reader,
+                length,
+                dictionary,
+                &mut offsets,
+                &mut values,
+                &mut scratch,
+            )?,
+            None => decompress_binary(reader, length, &mut offsets, &mut values, &mut scratch)?,
+        }
     }
     let validity =
         validity_builder.map(|mut validity_builder| std::mem::take(&mut validity_builder).into());
@@ -275,7 +332,19 @@ pub fn read_nested_binary<O: Offset, R: NativeReadBuf>(
 
     let mut results = Vec::with_capacity(page_metas.len());
 
+    let mut dictionaries = DictionaryCache::default();
+    let mut position = page_metas
+        .first()
+        .map(|p| p.start_offset())
+        .unwrap_or_default();
     for page_meta in page_metas {
+        let dictionary = dictionaries.read(
+            reader,
+            &mut position,
+            &page_meta,
+            &binary_dictionary_type::<O>(),
+        )?;
+        skip_to_page(reader, &mut position, &page_meta)?;
         let num_values = page_meta.num_values as usize;
         let (mut nested, validity) = read_validity_nested(reader, num_values, &leaf, init.clone())?;
         let length = nested.nested.pop().unwrap().len();
@@ -283,7 +352,17 @@ pub fn read_nested_binary<O: Offset, R: NativeReadBuf>(
         let mut offsets: Vec<O> = Vec::with_capacity(length + 1);
         let mut values = Vec::with_capacity(0);
 
-        decompress_binary(reader, length, &mut offsets, &mut values, &mut scratch)?;
+        match dictionary {
+            Some(dictionary) => decompress_binary_keys(
+                reader,
+                length,
+                dictionary,
+                &mut offsets,
+                &mut values,
+                &mut scratch,
+            )?,
+            None => decompress_binary(reader, length, &mut offsets, &mut values, &mut scratch)?,
+        }
 
         let array = try_new_binary_array(
             data_type.clone(),
@@ -296,13 +375,16 @@ pub fn read_nested_binary<O: Offset, R: NativeReadBuf>(
     Ok(results)
 }
 
-fn try_new_binary_array<O: Offset>(
+pub(super) fn try_new_binary_array<O: Offset>(
     data_type: DataType,
     offsets: OffsetsBuffer<O>,
     values: Buffer<u8>,
     validity: Option<Bitmap>,
 ) -> Result<Box<dyn Array>> {
-    if matches!(data_type, DataType::Utf8 | DataType::LargeUtf8) {
+    if matches!(
+        data_type.to_physical_type(),
+        PhysicalType::Utf8 | PhysicalType::LargeUtf8
+    ) {
         let array = Utf8Array::<O>::try_new(data_type, offsets, values, validity)?;
         Ok(Box::new(array) as Box<dyn Array>)
     } else {
@@ -202,7 +202,9 @@ pub fn read_boolean<R: NativeReadBuf>(
         None
     };
     let mut bitmap_builder = MutableBitmap::with_capacity(num_values);
+    let mut position = page_metas.first().map(|p| p.offset).unwrap_or_default();
     for page_meta in page_metas {
+        skip_to_page(reader, &mut position, &page_meta)?;
         let length = page_meta.num_values as usize;
         if let Some(ref mut validity_builder) = validity_builder {
             read_validity(reader, length, validity_builder)?;
@@ -228,7 +230,9 @@ pub fn read_nested_boolean<R: NativeReadBuf>(
     let mut scratch = vec![];
 
     let mut results = Vec::with_capacity(page_metas.len());
+    let mut position = page_metas.first().map(|p| p.offset).unwrap_or_default();
     for page_meta in page_metas {
+        skip_to_page(reader, &mut position, &page_meta)?;
         let num_values = page_meta.num_values as usize;
         let (mut nested, validity) = read_validity_nested(reader, num_values, &leaf, init.clone())?;
         let length = nested.nested.pop().unwrap().len();
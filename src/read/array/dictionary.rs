@@ -0,0 +1,798 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::io::Cursor;
+
+use arrow::array::{
+    new_empty_array, Array, BinaryArray, DictionaryArray, DictionaryKey, FixedSizeBinaryArray,
+    GenericBinaryArray, PrimitiveArray, Utf8Array,
+};
+use arrow::bitmap::{Bitmap, MutableBitmap};
+use arrow::compute::concatenate::concatenate;
+use arrow::compute::take::take;
+use arrow::datatypes::{DataType, PhysicalType, PrimitiveType};
+use arrow::error::{Error, Result};
+use arrow::io::parquet::read::{InitNested, NestedState};
+use arrow::offset::OffsetsBuffer;
+use arrow::types::{NativeType, Offset};
+use byteorder::{LittleEndian, ReadBytesExt};
+use parquet2::metadata::ColumnDescriptor;
+
+use super::{concatenate_unions, fixed_size, try_new_binary_array};
+use crate::compression::binary::decompress_binary;
+use crate::compression::double::decompress_double;
+use crate::compression::fixed_binary::decompress_fixed_binary;
+use crate::compression::integer::decompress_integer;
+use crate::compression::Compression;
+use crate::read::{read_basic::*, BufReader, NativeReadBuf, PageIterator};
+use crate::variant::is_variant;
+use crate::write::dictionary::leaf_values;
+use crate::PageMeta;
+use crate::{
+    with_match_dictionary_key_type, with_match_integer_double_type, with_match_primitive_type,
+};
+
+/// Reads the pages of a primitive or binary column as [`DictionaryArray`]s, the pages of
+/// dictionary columns are read the same way.
+///
+/// The keys and values of `Dict` pages are used as is, the values of the pages of other
+/// compressions are decoded and dictionary encoded.
+pub struct DictionaryIter<I>
+where
+    I: Iterator<Item = Result<(u64, Vec<u8>)>> + PageIterator + Send + Sync,
+{
+    iter: I,
+    is_nullable: bool,
+    data_type: DataType,
+    scratch: Vec<u8>,
+    dictionary: DictionaryCache,
+}
+
+impl<I> DictionaryIter<I>
+where
+    I: Iterator<Item = Result<(u64, Vec<u8>)>> + PageIterator + Send + Sync,
+{
+    pub fn new(iter: I, is_nullable: bool, data_type: DataType) -> Self {
+        Self {
+            iter,
+            is_nullable,
+            data_type,
+            scratch: vec![],
+            dictionary: DictionaryCache::default(),
+        }
+    }
+
+    fn deserialize(&mut self, num_values: u64, buffer: Vec<u8>) -> Result<Box<dyn Array>> {
+        let length = num_values as usize;
+        let mut reader = BufReader::with_capacity(buffer.len(), Cursor::new(buffer));
+        let validity = if self.is_nullable {
+            let mut validity_builder = MutableBitmap::with_capacity(length);
+            read_validity(&mut reader, length, &mut validity_builder)?;
+            Some(std::mem::take(&mut validity_builder).into())
+        } else {
+            None
+        };
+
+        let value_type = dictionary_value_type(&self.data_type)?;
+        let dictionary = match self.iter.dictionary() {
+            Some((offset, bytes)) => Some(self.dictionary.values(offset, bytes, value_type)?),
+            None => None,
+        };
+        let (keys, values) = read_dictionary_page(
+            &mut reader,
+            length,
+            value_type,
+            dictionary,
+            validity.clone(),
+            &mut self.scratch,
+        )?;
+
+        let mut buffer = reader.into_inner().into_inner();
+        self.iter.swap_buffer(&mut buffer);
+
+        new_dictionary_array(&self.data_type, keys, validity, values)
+    }
+}
+
+impl<I> Iterator for DictionaryIter<I>
+where
+    I: Iterator<Item = Result<(u64, Vec<u8>)>> + PageIterator + Send + Sync,
+{
+    type Item = Result<Box<dyn Array>>;
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        match self.iter.nth(n) {
+            Some(Ok((num_values, buffer))) => Some(self.deserialize(num_values, buffer)),
+            Some(Err(err)) => Some(Result::Err(err)),
+            None => None,
+        }
+    }
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.iter.next() {
+            Some(Ok((num_values, buffer))) => Some(self.deserialize(num_values, buffer)),
+            Some(Err(err)) => Some(Result::Err(err)),
+            None => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct DictionaryNestedIter<I>
+where
+    I: Iterator<Item = Result<(u64, Vec<u8>)>> + PageIterator + Send + Sync,
+{
+    iter: I,
+    data_type: DataType,
+    leaf: ColumnDescriptor,
+    init: Vec<InitNested>,
+    scratch: Vec<u8>,
+    dictionary: DictionaryCache,
+}
+
+impl<I> DictionaryNestedIter<I>
+where
+    I: Iterator<Item = Result<(u64, Vec<u8>)>> + PageIterator + Send + Sync,
+{
+    pub fn new(
+        iter: I,
+        data_type: DataType,
+        leaf: ColumnDescriptor,
+        init: Vec<InitNested>,
+    ) -> Self {
+        Self {
+            iter,
+            data_type,
+            leaf,
+            init,
+            scratch: vec![],
+            dictionary: DictionaryCache::default(),
+        }
+    }
+
+    fn deserialize(
+        &mut self,
+        num_values: u64,
+        buffer: Vec<u8>,
+    ) -> Result<(NestedState, Box<dyn Array>)> {
+        let mut reader = BufReader::with_capacity(buffer.len(), Cursor::new(buffer));
+        let (mut nested, validity) = read_validity_nested(
+            &mut reader,
+            num_values as usize,
+            &self.leaf,
+            self.init.clone(),
+        )?;
+        let length = nested.nested.pop().unwrap().len();
+
+        let value_type = dictionary_value_type(&self.data_type)?;
+        let dictionary = match self.iter.dictionary() {
+            Some((offset, bytes)) => Some(self.dictionary.values(offset, bytes, value_type)?),
+            None => None,
+        };
+        let (keys, values) = read_dictionary_page(
+            &mut reader,
+            length,
+            value_type,
+            dictionary,
+            validity.clone(),
+            &mut self.scratch,
+        )?;
+
+        let mut buffer = reader.into_inner().into_inner();
+        self.iter.swap_buffer(&mut buffer);
+
+        let array = new_dictionary_array(&self.data_type, keys, validity, values)?;
+        Ok((nested, array))
+    }
+}
+
+impl<I> Iterator for DictionaryNestedIter<I>
+where
+    I: Iterator<Item = Result<(u64, Vec<u8>)>> + PageIterator + Send + Sync,
+{
+    type Item = Result<(NestedState, Box<dyn Array>)>;
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        match self.iter.nth(n) {
+            Some(Ok((num_values, buffer))) => Some(self.deserialize(num_values, buffer)),
+            Some(Err(err)) => Some(Result::Err(err)),
+            None => None,
+        }
+    }
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.iter.next() {
+            Some(Ok((num_values, buffer))) => Some(self.deserialize(num_values, buffer)),
+            Some(Err(err)) => Some(Result::Err(err)),
+            None => None,
+        }
+    }
+}
+
+pub fn read_dictionary<R: NativeReadBuf>(
+    reader: &mut R,
+    is_nullable: bool,
+    data_type: DataType,
+    page_metas: Vec<PageMeta>,
+) -> Result<Box<dyn Array>> {
+    let value_type = dictionary_value_type(&data_type)?;
+    if page_metas.is_empty() {
+        return Ok(new_empty_array(data_type));
+    }
+
+    let mut scratch = vec![];
+    let mut arrays = Vec::with_capacity(page_metas.len());
+    let mut dictionaries = DictionaryCache::default();
+    let mut position = page_metas
+        .first()
+        .map(|p| p.start_offset())
+        .unwrap_or_default();
+    for page_meta in page_metas {
+        let dictionary = dictionaries.read(reader, &mut position, &page_meta, value_type)?;
+        skip_to_page(reader, &mut position, &page_meta)?;
+        let length = page_meta.num_values as usize;
+        let validity: Option<Bitmap> = if is_nullable {
+            let mut validity_builder = MutableBitmap::with_capacity(length);
+            read_validity(reader, length, &mut validity_builder)?;
+            Some(validity_builder.into())
+        } else {
+            None
+        };
+        let (keys, values) = read_dictionary_page(
+            reader,
+            length,
+            value_type,
+            dictionary,
+            validity.clone(),
+            &mut scratch,
+        )?;
+        arrays.push(new_dictionary_array(&data_type, keys, validity, values)?);
+    }
+    let arrays = arrays.iter().map(|v| v.as_ref()).collect::<Vec<_>>();
+    concatenate_arrays(&arrays)
+}
+
+pub fn read_nested_dictionary<R: NativeReadBuf>(
+    reader: &mut R,
+    data_type: DataType,
+    leaf: ColumnDescriptor,
+    init: Vec<InitNested>,
+    page_metas: Vec<PageMeta>,
+) -> Result<Vec<(NestedState, Box<dyn Array>)>> {
+    let value_type = dictionary_value_type(&data_type)?;
+    let mut scratch = vec![];
+    let mut results = Vec::with_capacity(page_metas.len());
+    let mut dictionaries = DictionaryCache::default();
+    let mut position = page_metas
+        .first()
+        .map(|p| p.start_offset())
+        .unwrap_or_default();
+    for page_meta in page_metas {
+        let dictionary = dictionaries.read(reader, &mut position, &page_meta, value_type)?;
+        skip_to_page(reader, &mut position, &page_meta)?;
+        let num_values = page_meta.num_values as usize;
+        let (mut nested, validity) = read_validity_nested(reader, num_values, &leaf, init.clone())?;
+        let length = nested.nested.pop().unwrap().len();
+
+        let (keys, values) = read_dictionary_page(
+            reader,
+            length,
+            value_type,
+            dictionary,
+            validity.clone(),
+            &mut scratch,
+        )?;
+        let array = new_dictionary_array(&data_type, keys, validity, values)?;
+        results.push((nested, array));
+    }
+    Ok(results)
+}
+
+/// The decoded values of the column dictionary of a column chunk, the dictionary is
+/// decoded once and shared by the pages of the column chunk.
+#[derive(Debug, Default)]
+pub(crate) struct DictionaryCache {
+    // the offset of the dictionary and its values
+    dictionary: Option<(u64, Box<dyn Array>)>,
+}
+
+impl DictionaryCache {
+    /// Returns the values of the dictionary at `offset`, `bytes` are only decoded
+    /// as values of `data_type` if it isn't the cached dictionary.
+    pub(crate) fn values(
+        &mut self,
+        offset: u64,
+        bytes: &[u8],
+        data_type: &DataType,
+    ) -> Result<&dyn Array> {
+        if !matches!(&self.dictionary, Some((cached, _)) if *cached == offset) {
+            let mut input = bytes;
+            let values = read_dictionary_values(&mut input, data_type)?;
+            self.dictionary = Some((offset, values));
+        }
+        Ok(self.dictionary.as_ref().unwrap().1.as_ref())
+    }
+
+    /// Reads the dictionary of the page from the reader at `position` if it isn't the
+    /// cached one, returns `None` if the page has no dictionary.
+    pub(crate) fn read<R: NativeReadBuf>(
+        &mut self,
+        reader: &mut R,
+        position: &mut u64,
+        page_meta: &PageMeta,
+        data_type: &DataType,
+    ) -> Result<Option<&dyn Array>> {
+        let meta = match &page_meta.dictionary {
+            Some(meta) => meta,
+            None => return Ok(None),
+        };
+        if !matches!(&self.dictionary, Some((cached, _)) if *cached == meta.offset) {
+            skip_to(reader, position, meta.offset)?;
+            let mut bytes = vec![0u8; meta.length as usize];
+            reader.read_exact(&mut bytes)?;
+            *position += meta.length;
+            self.values(meta.offset, &bytes, data_type)?;
+        }
+        Ok(self.dictionary.as_ref().map(|(_, values)| values.as_ref()))
+    }
+}
+
+/// Reads the indices of a page of a column dictionary.
+fn read_dictionary_keys<R: NativeReadBuf>(
+    reader: &mut R,
+    length: usize,
+    num_values: usize,
+    scratch: &mut Vec<u8>,
+) -> Result<Vec<u32>> {
+    let (codec, _, _) = read_compress_header(reader)?;
+    if codec != u8::from(Compression::Dict) {
+        return Err(Error::OutOfSpec(format!(
+            "The page of a column dictionary has the codec {codec} rather than Dict"
+        )));
+    }
+    let mut keys = Vec::with_capacity(length);
+    decompress_integer(reader, length, &mut keys, scratch)?;
+    // the nulls of a dictionary without values are written as the first index
+    if let Some(key) = keys.iter().find(|key| **key as usize >= num_values.max(1)) {
+        return Err(Error::OutOfSpec(format!(
+            "The index {key} is out of the dictionary of {num_values} values"
+        )));
+    }
+    Ok(keys)
+}
+
+/// Reads the indices of a page of a column dictionary and extends `output`
+/// with the values of the dictionary they point to.
+pub(crate) fn decompress_primitive_keys<T: NativeType, R: NativeReadBuf>(
+    reader: &mut R,
+    length: usize,
+    dictionary: &dyn Array,
+    output: &mut Vec<T>,
+    scratch: &mut Vec<u8>,
+) -> Result<()> {
+    let keys = read_dictionary_keys(reader, length, dictionary.len(), scratch)?;
+    let dictionary: &PrimitiveArray<T> = dictionary.as_any().downcast_ref().unwrap();
+    let values = dictionary.values();
+    output.extend(
+        keys.into_iter()
+            .map(|key| values.get(key as usize).copied().unwrap_or_default()),
+    );
+    Ok(())
+}
+
+/// Same as [`decompress_primitive_keys`], for binary values.
+pub(crate) fn decompress_binary_keys<O: Offset, R: NativeReadBuf>(
+    reader: &mut R,
+    length: usize,
+    dictionary: &dyn Array,
+    offsets: &mut Vec<O>,
+    values: &mut Vec<u8>,
+    scratch: &mut Vec<u8>,
+) -> Result<()> {
+    let keys = read_dictionary_keys(reader, length, dictionary.len(), scratch)?;
+    let dictionary: &BinaryArray<O> = dictionary.as_any().downcast_ref().unwrap();
+    if offsets.is_empty() {
+        offsets.push(O::default());
+    }
+    for key in keys {
+        if (key as usize) < dictionary.len() {
+            values.extend_from_slice(dictionary.value(key as usize));
+        }
+        offsets.push(O::from_usize(values.len()).ok_or(Error::Overflow)?);
+    }
+    Ok(())
+}
+
+/// Same as [`decompress_primitive_keys`], for fixed size binary values.
+pub(crate) fn decompress_fixed_binary_keys<R: NativeReadBuf>(
+    reader: &mut R,
+    length: usize,
+    dictionary: &dyn Array,
+    values: &mut Vec<u8>,
+    scratch: &mut Vec<u8>,
+) -> Result<()> {
+    let keys = read_dictionary_keys(reader, length, dictionary.len(), scratch)?;
+    let dictionary: &FixedSizeBinaryArray = dictionary.as_any().downcast_ref().unwrap();
+    for key in keys {
+        match (key as usize) < dictionary.len() {
+            true => values.extend_from_slice(dictionary.value(key as usize)),
+            false => values.resize(values.len() + dictionary.size(), 0),
+        }
+    }
+    Ok(())
+}
+
+/// The type the values of a column dictionary are decoded as by the binary readers.
+pub(crate) fn binary_dictionary_type<O: Offset>() -> DataType {
+    if O::IS_LARGE {
+        DataType::LargeBinary
+    } else {
+        DataType::Binary
+    }
+}
+
+/// Returns whether the arrays of the type can be read as [`DictionaryArray`]s.
+pub(crate) fn is_dictionary_value_type(data_type: &DataType) -> bool {
+    if is_variant(data_type) {
+        return false;
+    }
+    match data_type.to_physical_type() {
+        PhysicalType::Primitive(primitive) => !matches!(
+            primitive,
+            PrimitiveType::DaysMs | PrimitiveType::MonthDayNano
+        ),
+        PhysicalType::Binary
+        | PhysicalType::LargeBinary
+        | PhysicalType::Utf8
+        | PhysicalType::LargeUtf8
+        | PhysicalType::FixedSizeBinary => true,
+        _ => false,
+    }
+}
+
+/// Returns the type of the values of a dictionary read from the pages of the values.
+pub(crate) fn dictionary_value_type(data_type: &DataType) -> Result<&DataType> {
+    match data_type.to_logical_type() {
+        DataType::Dictionary(_, values, _) if is_dictionary_value_type(values) => {
+            Ok(values.as_ref())
+        }
+        _ => Err(Error::NotYetImplemented(format!(
+            "Reading the pages as arrays of type {data_type:?}"
+        ))),
+    }
+}
+
+fn new_dictionary_array(
+    data_type: &DataType,
+    keys: Vec<u32>,
+    validity: Option<Bitmap>,
+    values: Box<dyn Array>,
+) -> Result<Box<dyn Array>> {
+    let key_type = match data_type.to_logical_type() {
+        DataType::Dictionary(key_type, _, _) => *key_type,
+        _ => unreachable!(),
+    };
+    with_match_dictionary_key_type!(key_type, |$K| {
+        let keys = keys
+            .into_iter()
+            .map(|key| $K::try_from(key as usize).map_err(|_| Error::Overflow))
+            .collect::<Result<Vec<_>>>()?;
+        let keys = PrimitiveArray::<$K>::try_new($K::PRIMITIVE.into(), keys.into(), validity)?;
+        Ok(DictionaryArray::<$K>::try_new(data_type.clone(), keys, values)?.boxed())
+    })
+}
+
+/// Concatenates the arrays, the values of [`DictionaryArray`]s are unified rather than
+/// appended, the arrays sharing the same values, e.g. of a column dictionary, keep them.
+pub(crate) fn concatenate_arrays(arrays: &[&dyn Array]) -> Result<Box<dyn Array>> {
+    match arrays
+        .first()
+        .map(|array| array.data_type().to_logical_type())
+    {
+        Some(DataType::Dictionary(key_type, _, _)) => {
+            with_match_dictionary_key_type!(key_type, |$K| {
+                let arrays = arrays
+                    .iter()
+                    .map(|array| array.as_any().downcast_ref::<DictionaryArray<$K>>().unwrap())
+                    .collect::<Vec<_>>();
+                concatenate_dictionaries(&arrays)
+            })
+        }
+        Some(DataType::Union(..)) => concatenate_unions(arrays),
+        _ => concatenate(arrays),
+    }
+}
+
+fn concatenate_dictionaries<K: DictionaryKey>(
+    arrays: &[&DictionaryArray<K>],
+) -> Result<Box<dyn Array>> {
+    let data_type = arrays[0].data_type().clone();
+    let keys = arrays
+        .iter()
+        .map(|array| array.keys() as &dyn Array)
+        .collect::<Vec<_>>();
+    let keys = concatenate(&keys)?;
+    let keys = keys.as_any().downcast_ref::<PrimitiveArray<K>>().unwrap();
+
+    let first = arrays[0].values();
+    if arrays
+        .iter()
+        .all(|array| same_values(array.values().as_ref(), first.as_ref()))
+    {
+        let array = DictionaryArray::<K>::try_new(data_type, keys.clone(), first.clone())?;
+        return Ok(array.boxed());
+    }
+
+    // encodes all the values again and maps the keys of each array to the new keys
+    let values = arrays
+        .iter()
+        .map(|array| array.values().as_ref())
+        .collect::<Vec<_>>();
+    let (new_keys, values) = dictionary_encode(concatenate(&values)?.as_ref())?;
+    let mut base = 0;
+    let mut mapped = Vec::with_capacity(keys.len());
+    for array in arrays {
+        for key in array.keys_values_iter() {
+            let key = new_keys.get(base + key).copied().unwrap_or_default();
+            mapped.push(K::try_from(key as usize).map_err(|_| Error::Overflow)?);
+        }
+        base += array.values().len();
+    }
+    let keys =
+        PrimitiveArray::<K>::try_new(K::PRIMITIVE.into(), mapped.into(), keys.validity().cloned())?;
+    Ok(DictionaryArray::<K>::try_new(data_type, keys, values)?.boxed())
+}
+
+/// Returns whether the arrays hold the same buffers, e.g. the values of the pages of the same
+/// column dictionary, which are decoded once. The values aren't compared.
+fn same_values(a: &dyn Array, b: &dyn Array) -> bool {
+    let validity = |array: &dyn Array| {
+        array.validity().map(|validity| {
+            let (bytes, offset, length) = validity.as_slice();
+            (bytes.as_ptr(), offset, length)
+        })
+    };
+    if a.data_type() != b.data_type() || a.len() != b.len() || validity(a) != validity(b) {
+        return false;
+    }
+    match a.data_type().to_physical_type() {
+        PhysicalType::Primitive(primitive) => with_match_primitive_type!(primitive, |$T| {
+            let a: &PrimitiveArray<$T> = a.as_any().downcast_ref().unwrap();
+            let b: &PrimitiveArray<$T> = b.as_any().downcast_ref().unwrap();
+            a.values().as_ptr() == b.values().as_ptr()
+        }),
+        PhysicalType::Binary => same_binary_buffers::<BinaryArray<i32>, i32>(a, b),
+        PhysicalType::LargeBinary => same_binary_buffers::<BinaryArray<i64>, i64>(a, b),
+        PhysicalType::Utf8 => same_binary_buffers::<Utf8Array<i32>, i32>(a, b),
+        PhysicalType::LargeUtf8 => same_binary_buffers::<Utf8Array<i64>, i64>(a, b),
+        PhysicalType::FixedSizeBinary => {
+            let a: &FixedSizeBinaryArray = a.as_any().downcast_ref().unwrap();
+            let b: &FixedSizeBinaryArray = b.as_any().downcast_ref().unwrap();
+            a.values().as_ptr() == b.values().as_ptr()
+        }
+        _ => false,
+    }
+}
+
+fn same_binary_buffers<A: GenericBinaryArray<O> + 'static, O: Offset>(
+    a: &dyn Array,
+    b: &dyn Array,
+) -> bool {
+    let a: &A = a.as_any().downcast_ref().unwrap();
+    let b: &A = b.as_any().downcast_ref().unwrap();
+    a.values().as_ptr() == b.values().as_ptr() && a.offsets().as_ptr() == b.offsets().as_ptr()
+}
+
+/// Reads the values of a page as the keys and the values of a dictionary, the pages of
+/// a column dictionary only hold the keys into the decoded `dictionary`.
+/// The `validity` of the page is only used to skip the nulls of non `Dict` pages.
+fn read_dictionary_page<R: NativeReadBuf>(
+    reader: &mut R,
+    length: usize,
+    data_type: &DataType,
+    dictionary: Option<&dyn Array>,
+    validity: Option<Bitmap>,
+    scratch: &mut Vec<u8>,
+) -> Result<(Vec<u32>, Box<dyn Array>)> {
+    if let Some(dictionary) = dictionary {
+        let keys = read_dictionary_keys(reader, length, dictionary.len(), scratch)?;
+        return Ok((keys, dictionary.to_boxed()));
+    }
+    reader.fill_buf()?;
+    if reader.buffer_bytes().first() != Some(&u8::from(Compression::Dict)) {
+        let array = read_plain_page(reader, length, data_type, validity, scratch)?;
+        return dictionary_encode(array.as_ref());
+    }
+
+    let (_, compressed_size, _) = read_compress_header(reader)?;
+    let mut body = vec![0u8; compressed_size];
+    reader.read_exact(&mut body)?;
+    let mut input = body.as_slice();
+
+    let mut keys = Vec::with_capacity(length);
+    decompress_integer(&mut input, length, &mut keys, scratch)?;
+    let values = read_dictionary_values(&mut input, data_type)?;
+    Ok((keys, values))
+}
+
+fn read_plain_page<R: NativeReadBuf>(
+    reader: &mut R,
+    length: usize,
+    data_type: &DataType,
+    validity: Option<Bitmap>,
+    scratch: &mut Vec<u8>,
+) -> Result<Box<dyn Array>> {
+    let data_type = data_type.clone();
+    match data_type.to_physical_type() {
+        PhysicalType::Primitive(primitive) => with_match_integer_double_type!(primitive,
+        |$I| {
+            let mut values: Vec<$I> = Vec::with_capacity(length);
+            decompress_integer(reader, length, &mut values, scratch)?;
+            Ok(PrimitiveArray::<$I>::try_new(data_type, values.into(), validity)?.boxed())
+        },
+        |$T| {
+            let mut values: Vec<$T> = Vec::with_capacity(length);
+            decompress_double(reader, length, &mut values, scratch)?;
+            Ok(PrimitiveArray::<$T>::try_new(data_type, values.into(), validity)?.boxed())
+        }),
+        PhysicalType::Binary | PhysicalType::Utf8 => {
+            read_plain_binary::<i32, _>(reader, length, data_type, validity, scratch)
+        }
+        PhysicalType::LargeBinary | PhysicalType::LargeUtf8 => {
+            read_plain_binary::<i64, _>(reader, length, data_type, validity, scratch)
+        }
+        PhysicalType::FixedSizeBinary => {
+            let size = fixed_size(&data_type)?;
+            let mut values = Vec::with_capacity(length * size);
+            decompress_fixed_binary(reader, length, size, &mut values, scratch)?;
+            Ok(FixedSizeBinaryArray::try_new(data_type, values.into(), validity)?.boxed())
+        }
+        _ => unreachable!(),
+    }
+}
+
+fn read_plain_binary<O: Offset, R: NativeReadBuf>(
+    reader: &mut R,
+    length: usize,
+    data_type: DataType,
+    validity: Option<Bitmap>,
+    scratch: &mut Vec<u8>,
+) -> Result<Box<dyn Array>> {
+    let mut offsets: Vec<O> = Vec::with_capacity(length + 1);
+    let mut values = vec![];
+    decompress_binary(reader, length, &mut offsets, &mut values, scratch)?;
+    try_new_binary_array(
+        data_type,
+        unsafe { OffsetsBuffer::new_unchecked(offsets.into()) },
+        values.into(),
+        validity,
+    )
+}
+
+/// Reads the values at the end of a `Dict` body, as written by the `Dict` compressions.
+fn read_dictionary_values(input: &mut &[u8], data_type: &DataType) -> Result<Box<dyn Array>> {
+    let count = input.read_u32::<LittleEndian>()? as usize;
+    match data_type.to_physical_type() {
+        PhysicalType::Primitive(primitive) => with_match_primitive_type!(primitive, |$T| {
+            read_primitive_values::<$T>(input, count, data_type.clone())
+        }),
+        PhysicalType::Binary | PhysicalType::Utf8 => {
+            read_binary_values::<i32>(input, count, data_type.clone())
+        }
+        PhysicalType::LargeBinary | PhysicalType::LargeUtf8 => {
+            read_binary_values::<i64>(input, count, data_type.clone())
+        }
+        PhysicalType::FixedSizeBinary => read_fixed_binary_values(input, count, data_type.clone()),
+        _ => unreachable!(),
+    }
+}
+
+fn read_primitive_values<T: NativeType>(
+    input: &mut &[u8],
+    count: usize,
+    data_type: DataType,
+) -> Result<Box<dyn Array>> {
+    let size = count * std::mem::size_of::<T>();
+    if input.len() < size {
+        return Err(Error::OutOfSpec(format!(
+            "The dictionary of {count} values needs {size} bytes, but only {} are left",
+            input.len()
+        )));
+    }
+    let values = input[..size]
+        .chunks_exact(std::mem::size_of::<T>())
+        .map(|chunk| match T::Bytes::try_from(chunk) {
+            Ok(bytes) => T::from_le_bytes(bytes),
+            Err(_) => unreachable!(),
+        })
+        .collect::<Vec<_>>();
+    *input = &input[size..];
+    Ok(PrimitiveArray::<T>::try_new(data_type, values.into(), None)?.boxed())
+}
+
+fn read_binary_values<O: Offset>(
+    input: &mut &[u8],
+    count: usize,
+    data_type: DataType,
+) -> Result<Box<dyn Array>> {
+    let mut offsets = Vec::with_capacity(count + 1);
+    offsets.push(O::default());
+    let mut values = vec![];
+    for _ in 0..count {
+        let len = input.read_u64::<LittleEndian>()? as usize;
+        if input.len() < len {
+            return Err(Error::OutOfSpec(format!(
+                "The dictionary value of {len} bytes exceeds the {} bytes left",
+                input.len()
+            )));
+        }
+        values.extend_from_slice(&input[..len]);
+        *input = &input[len..];
+        offsets.push(O::from_usize(values.len()).ok_or(Error::Overflow)?);
+    }
+    try_new_binary_array(
+        data_type,
+        unsafe { OffsetsBuffer::new_unchecked(offsets.into()) },
+        values.into(),
+        None,
+    )
+}
+
+fn read_fixed_binary_values(
+    input: &mut &[u8],
+    count: usize,
+    data_type: DataType,
+) -> Result<Box<dyn Array>> {
+    let size = count * fixed_size(&data_type)?;
+    if input.len() < size {
+        return Err(Error::OutOfSpec(format!(
+            "The dictionary of {count} values needs {size} bytes, but only {} are left",
+            input.len()
+        )));
+    }
+    let values = input[..size].to_vec();
+    *input = &input[size..];
+    Ok(FixedSizeBinaryArray::try_new(data_type, values.into(), None)?.boxed())
+}
+
+/// Encodes the valid values of the array as keys into the distinct values,
+/// the nulls are encoded as the first key.
+fn dictionary_encode(array: &dyn Array) -> Result<(Vec<u32>, Box<dyn Array>)> {
+    let (values, _) = leaf_values(array).ok_or_else(|| {
+        Error::NotYetImplemented(format!(
+            "Dictionary encoding arrays of type {:?}",
+            array.data_type()
+        ))
+    })?;
+
+    let mut index: HashMap<&[u8], u32> = HashMap::new();
+    let mut positions = vec![];
+    let keys = values
+        .iter()
+        .enumerate()
+        .map(|(i, value)| match value {
+            Some(value) => *index.entry(value).or_insert_with(|| {
+                positions.push(i as u32);
+                positions.len() as u32 - 1
+            }),
+            None => 0,
+        })
+        .collect();
+    let values = take(array, &PrimitiveArray::from_vec(positions))?;
+    Ok((keys, values))
+}
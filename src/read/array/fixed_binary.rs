@@ -0,0 +1,307 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::io::Cursor;
+
+use super::dictionary::*;
+use crate::compression::fixed_binary::decompress_fixed_binary;
+use crate::read::{read_basic::*, BufReader, NativeReadBuf, PageIterator};
+use crate::PageMeta;
+use arrow::array::{Array, FixedSizeBinaryArray};
+use arrow::bitmap::MutableBitmap;
+use arrow::datatypes::DataType;
+use arrow::error::{Error, Result};
+use arrow::io::parquet::read::{InitNested, NestedState};
+use parquet2::metadata::ColumnDescriptor;
+
+#[derive(Debug)]
+pub struct FixedBinaryIter<I>
+where
+    I: Iterator<Item = Result<(u64, Vec<u8>)>> + PageIterator + Send + Sync,
+{
+    iter: I,
+    is_nullable: bool,
+    data_type: DataType,
+    scratch: Vec<u8>,
+    dictionary: DictionaryCache,
+}
+
+impl<I> FixedBinaryIter<I>
+where
+    I: Iterator<Item = Result<(u64, Vec<u8>)>> + PageIterator + Send + Sync,
+{
+    pub fn new(iter: I, is_nullable: bool, data_type: DataType) -> Self {
+        Self {
+            iter,
+            is_nullable,
+            data_type,
+            scratch: vec![],
+            dictionary: DictionaryCache::default(),
+        }
+    }
+}
+
+impl<I> FixedBinaryIter<I>
+where
+    I: Iterator<Item = Result<(u64, Vec<u8>)>> + PageIterator + Send + Sync,
+{
+    fn deserialize(&mut self, num_values: u64, buffer: Vec<u8>) -> Result<Box<dyn Array>> {
+        let length = num_values as usize;
+        let mut reader = BufReader::with_capacity(buffer.len(), Cursor::new(buffer));
+        let validity = if self.is_nullable {
+            let mut validity_builder = MutableBitmap::with_capacity(length);
+            read_validity(&mut reader, length, &mut validity_builder)?;
+            Some(std::mem::take(&mut validity_builder).into())
+        } else {
+            None
+        };
+
+        let size = fixed_size(&self.data_type)?;
+        let mut values = Vec::with_capacity(length * size);
+        match self.iter.dictionary() {
+            Some((offset, bytes)) => {
+                let dictionary = self.dictionary.values(offset, bytes, &self.data_type)?;
+                decompress_fixed_binary_keys(
+                    &mut reader,
+                    length,
+                    dictionary,
+                    &mut values,
+                    &mut self.scratch,
+                )?
+            }
+            None => {
+                decompress_fixed_binary(&mut reader, length, size, &mut values, &mut self.scratch)?
+            }
+        }
+
+        let array = FixedSizeBinaryArray::try_new(self.data_type.clone(), values.into(), validity)?;
+        Ok(Box::new(array) as Box<dyn Array>)
+    }
+}
+
+impl<I> Iterator for FixedBinaryIter<I>
+where
+    I: Iterator<Item = Result<(u64, Vec<u8>)>> + PageIterator + Send + Sync,
+{
+    type Item = Result<Box<dyn Array>>;
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        match self.iter.nth(n) {
+            Some(Ok((num_values, buffer))) => Some(self.deserialize(num_values, buffer)),
+            Some(Err(err)) => Some(Result::Err(err)),
+            None => None,
+        }
+    }
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.iter.next() {
+            Some(Ok((num_values, buffer))) => Some(self.deserialize(num_values, buffer)),
+            Some(Err(err)) => Some(Result::Err(err)),
+            None => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct FixedBinaryNestedIter<I>
+where
+    I: Iterator<Item = Result<(u64, Vec<u8>)>> + PageIterator + Send + Sync,
+{
+    iter: I,
+    data_type: DataType,
+    leaf: ColumnDescriptor,
+    init: Vec<InitNested>,
+    scratch: Vec<u8>,
+    dictionary: DictionaryCache,
+}
+
+impl<I> FixedBinaryNestedIter<I>
+where
+    I: Iterator<Item = Result<(u64, Vec<u8>)>> + PageIterator + Send + Sync,
+{
+    pub fn new(
+        iter: I,
+        data_type: DataType,
+        leaf: ColumnDescriptor,
+        init: Vec<InitNested>,
+    ) -> Self {
+        Self {
+            iter,
+            data_type,
+            leaf,
+            init,
+            scratch: vec![],
+            dictionary: DictionaryCache::default(),
+        }
+    }
+}
+
+impl<I> FixedBinaryNestedIter<I>
+where
+    I: Iterator<Item = Result<(u64, Vec<u8>)>> + PageIterator + Send + Sync,
+{
+    fn deserialize(
+        &mut self,
+        num_values: u64,
+        buffer: Vec<u8>,
+    ) -> Result<(NestedState, Box<dyn Array>)> {
+        let mut reader = BufReader::with_capacity(buffer.len(), Cursor::new(buffer));
+        let (mut nested, validity) = read_validity_nested(
+            &mut reader,
+            num_values as usize,
+            &self.leaf,
+            self.init.clone(),
+        )?;
+        let length = nested.nested.pop().unwrap().len();
+
+        let size = fixed_size(&self.data_type)?;
+        let mut values = Vec::with_capacity(length * size);
+        match self.iter.dictionary() {
+            Some((offset, bytes)) => {
+                let dictionary = self.dictionary.values(offset, bytes, &self.data_type)?;
+                decompress_fixed_binary_keys(
+                    &mut reader,
+                    length,
+                    dictionary,
+                    &mut values,
+                    &mut self.scratch,
+                )?
+            }
+            None => {
+                decompress_fixed_binary(&mut reader, length, size, &mut values, &mut self.scratch)?
+            }
+        }
+
+        let array = FixedSizeBinaryArray::try_new(self.data_type.clone(), values.into(), validity)?;
+        Ok((nested, Box::new(array) as Box<dyn Array>))
+    }
+}
+
+impl<I> Iterator for FixedBinaryNestedIter<I>
+where
+    I: Iterator<Item = Result<(u64, Vec<u8>)>> + PageIterator + Send + Sync,
+{
+    type Item = Result<(NestedState, Box<dyn Array>)>;
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        match self.iter.nth(n) {
+            Some(Ok((num_values, buffer))) => Some(self.deserialize(num_values, buffer)),
+            Some(Err(err)) => Some(Result::Err(err)),
+            None => None,
+        }
+    }
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.iter.next() {
+            Some(Ok((num_values, buffer))) => Some(self.deserialize(num_values, buffer)),
+            Some(Err(err)) => Some(Result::Err(err)),
+            None => None,
+        }
+    }
+}
+
+pub fn read_fixed_binary<R: NativeReadBuf>(
+    reader: &mut R,
+    is_nullable: bool,
+    data_type: DataType,
+    page_metas: Vec<PageMeta>,
+) -> Result<Box<dyn Array>> {
+    let num_values = page_metas.iter().map(|p| p.num_values as usize).sum();
+    let size = fixed_size(&data_type)?;
+
+    let mut validity_builder = if is_nullable {
+        Some(MutableBitmap::with_capacity(num_values))
+    } else {
+        None
+    };
+    let mut scratch = vec![];
+    let mut values: Vec<u8> = Vec::with_capacity(num_values * size);
+
+    let mut dictionaries = DictionaryCache::default();
+    let mut position = page_metas
+        .first()
+        .map(|p| p.start_offset())
+        .unwrap_or_default();
+    for page_meta in page_metas {
+        let dictionary = dictionaries.read(reader, &mut position, &page_meta, &data_type)?;
+        skip_to_page(reader, &mut position, &page_meta)?;
+        let length = page_meta.num_values as usize;
+        if let Some(ref mut validity_builder) = validity_builder {
+            read_validity(reader, length, validity_builder)?;
+        }
+
+        match dictionary {
+            Some(dictionary) => {
+                decompress_fixed_binary_keys(reader, length, dictionary, &mut values, &mut scratch)?
+            }
+            None => decompress_fixed_binary(reader, length, size, &mut values, &mut scratch)?,
+        }
+    }
+    let validity =
+        validity_builder.map(|mut validity_builder| std::mem::take(&mut validity_builder).into());
+
+    let array = FixedSizeBinaryArray::try_new(data_type, values.into(), validity)?;
+    Ok(Box::new(array) as Box<dyn Array>)
+}
+
+pub fn read_nested_fixed_binary<R: NativeReadBuf>(
+    reader: &mut R,
+    data_type: DataType,
+    leaf: ColumnDescriptor,
+    init: Vec<InitNested>,
+    page_metas: Vec<PageMeta>,
+) -> Result<Vec<(NestedState, Box<dyn Array>)>> {
+    let size = fixed_size(&data_type)?;
+    let mut scratch = vec![];
+
+    let mut results = Vec::with_capacity(page_metas.len());
+
+    let mut dictionaries = DictionaryCache::default();
+    let mut position = page_metas
+        .first()
+        .map(|p| p.start_offset())
+        .unwrap_or_default();
+    for page_meta in page_metas {
+        let dictionary = dictionaries.read(reader, &mut position, &page_meta, &data_type)?;
+        skip_to_page(reader, &mut position, &page_meta)?;
+        let num_values = page_meta.num_values as usize;
+        let (mut nested, validity) = read_validity_nested(reader, num_values, &leaf, init.clone())?;
+        let length = nested.nested.pop().unwrap().len();
+
+        let mut values = Vec::with_capacity(length * size);
+        match dictionary {
+            Some(dictionary) => {
+                decompress_fixed_binary_keys(reader, length, dictionary, &mut values, &mut scratch)?
+            }
+            None => decompress_fixed_binary(reader, length, size, &mut values, &mut scratch)?,
+        }
+
+        let array = FixedSizeBinaryArray::try_new(data_type.clone(), values.into(), validity)?;
+        results.push((nested, Box::new(array) as Box<dyn Array>));
+    }
+    Ok(results)
+}
+
+/// The size of the values of a `FixedSizeBinary` data type.
+pub(crate) fn fixed_size(data_type: &DataType) -> Result<usize> {
+    match data_type.to_logical_type() {
+        DataType::FixedSizeBinary(size) if *size > 0 => Ok(*size),
+        other => Err(Error::OutOfSpec(format!(
+            "Expected a FixedSizeBinary with a positive size, got {other:?}"
+        ))),
+    }
+}
@@ -18,6 +18,7 @@
 use std::io::Cursor;
 use std::marker::PhantomData;
 
+use super::dictionary::*;
 use crate::compression::integer::{decompress_integer, IntegerType};
 use crate::read::{read_basic::*, BufReader, NativeReadBuf, PageIterator};
 use crate::PageMeta;
@@ -40,6 +41,7 @@ where
     is_nullable: bool,
     data_type: DataType,
     scratch: Vec<u8>,
+    dictionary: DictionaryCache,
     _phantom: PhantomData<T>,
 }
 
@@ -54,6 +56,7 @@ where
             is_nullable,
             data_type,
             scratch: vec![],
+            dictionary: DictionaryCache::default(),
             _phantom: PhantomData,
         }
     }
@@ -77,7 +80,19 @@ where
         };
         let mut values: Vec<T> = Vec::with_capacity(length);
 
-        decompress_integer(&mut reader, length, &mut values, &mut self.scratch)?;
+        match self.iter.dictionary() {
+            Some((offset, bytes)) => {
+                let dictionary = self.dictionary.values(offset, bytes, &self.data_type)?;
+                decompress_primitive_keys(
+                    &mut reader,
+                    length,
+                    dictionary,
+                    &mut values,
+                    &mut self.scratch,
+                )?
+            }
+            None => decompress_integer(&mut reader, length, &mut values, &mut self.scratch)?,
+        }
         assert_eq!(values.len(), length);
 
         let mut buffer = reader.into_inner().into_inner();
@@ -124,6 +139,7 @@ where
     leaf: ColumnDescriptor,
     init: Vec<InitNested>,
     scratch: Vec<u8>,
+    dictionary: DictionaryCache,
     _phantom: PhantomData<T>,
 }
 
@@ -144,6 +160,7 @@ where
             leaf,
             init,
             scratch: vec![],
+            dictionary: DictionaryCache::default(),
             _phantom: PhantomData,
         }
     }
@@ -170,7 +187,19 @@ where
         let length = nested.nested.pop().unwrap().len();
 
         let mut values = Vec::with_capacity(length);
-        decompress_integer(&mut reader, length, &mut values, &mut self.scratch)?;
+        match self.iter.dictionary() {
+            Some((offset, bytes)) => {
+                let dictionary = self.dictionary.values(offset, bytes, &self.data_type)?;
+                decompress_primitive_keys(
+                    &mut reader,
+                    length,
+                    dictionary,
+                    &mut values,
+                    &mut self.scratch,
+                )?
+            }
+            None => decompress_integer(&mut reader, length, &mut values, &mut self.scratch)?,
+        }
         assert_eq!(values.len(), length);
 
         let mut buffer = reader.into_inner().into_inner();
@@ -222,12 +251,28 @@ pub fn read_integer<T: IntegerType, R: NativeReadBuf>(
         None
     };
     let mut out_buffer: Vec<T> = Vec::with_capacity(num_values);
+    let mut dictionaries = DictionaryCache::default();
+    let mut position = page_metas
+        .first()
+        .map(|p| p.start_offset())
+        .unwrap_or_default();
     for page_meta in page_metas {
+        let dictionary = dictionaries.read(reader, &mut position, &page_meta, &data_type)?;
+        skip_to_page(reader, &mut position, &page_meta)?;
         let length = page_meta.num_values as usize;
         if let Some(ref mut validity_builder) = validity_builder {
             read_validity(reader, length, validity_builder)?;
         }
-        decompress_integer(reader, length, &mut out_buffer, &mut scratch)?;
+        match dictionary {
+            Some(dictionary) => decompress_primitive_keys(
+                reader,
+                length,
+                dictionary,
+                &mut out_buffer,
+                &mut scratch,
+            )?,
+            None => decompress_integer(reader, length, &mut out_buffer, &mut scratch)?,
+        }
     }
     let validity =
         validity_builder.map(|mut validity_builder| std::mem::take(&mut validity_builder).into());
@@ -246,13 +291,25 @@ pub fn read_nested_integer<T: IntegerType, R: NativeReadBuf>(
 ) -> Result<Vec<(NestedState, Box<dyn Array>)>> {
     let mut scratch = vec![];
     let mut results = Vec::with_capacity(page_metas.len());
+    let mut dictionaries = DictionaryCache::default();
+    let mut position = page_metas
+        .first()
+        .map(|p| p.start_offset())
+        .unwrap_or_default();
     for page_meta in page_metas {
+        let dictionary = dictionaries.read(reader, &mut position, &page_meta, &data_type)?;
+        skip_to_page(reader, &mut position, &page_meta)?;
         let num_values = page_meta.num_values as usize;
         let (mut nested, validity) = read_validity_nested(reader, num_values, &leaf, init.clone())?;
         let length = nested.nested.pop().unwrap().len();
 
         let mut values = Vec::with_capacity(length);
-        decompress_integer(reader, length, &mut values, &mut scratch)?;
+        match dictionary {
+            Some(dictionary) => {
+                decompress_primitive_keys(reader, length, dictionary, &mut values, &mut scratch)?
+            }
+            None => decompress_integer(reader, length, &mut values, &mut scratch)?,
+        }
 
         let array = PrimitiveArray::<T>::try_new(data_type.clone(), values.into(), validity)?;
         results.push((nested, Box::new(array) as Box<dyn Array>));
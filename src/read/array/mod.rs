@@ -20,10 +20,16 @@ pub use integer::*;
 
 mod double;
 pub use double::*;
+mod interval;
+pub use interval::*;
 mod boolean;
 pub use boolean::*;
 mod binary;
 pub use binary::*;
+mod fixed_binary;
+pub use fixed_binary::*;
+mod dictionary;
+pub use dictionary::*;
 mod null;
 pub use null::*;
 mod struct_;
@@ -32,3 +38,7 @@ mod list;
 pub use list::*;
 mod map;
 pub use map::*;
+mod union;
+pub use union::*;
+mod variant;
+pub use variant::*;
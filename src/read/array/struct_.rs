@@ -16,7 +16,7 @@
 // under the License.
 
 use arrow::array::{Array, StructArray};
-use arrow::datatypes::{DataType, Field};
+use arrow::datatypes::DataType;
 use arrow::error::Result;
 use arrow::io::parquet::read::NestedState;
 
@@ -27,14 +27,14 @@ type StructValues = Vec<Option<Result<(NestedState, Box<dyn Array>)>>>;
 /// An iterator adapter over [`DynIter`] assumed to be encoded as Struct arrays
 pub struct StructIterator<'a> {
     iters: Vec<NestedIters<'a>>,
-    fields: Vec<Field>,
+    data_type: DataType,
 }
 
 impl<'a> StructIterator<'a> {
-    /// Creates a new [`StructIterator`] with `iters` and `fields`.
-    pub fn new(iters: Vec<NestedIters<'a>>, fields: Vec<Field>) -> Self {
-        assert_eq!(iters.len(), fields.len());
-        Self { iters, fields }
+    /// Creates a new [`StructIterator`] with `iters` and the struct `data_type`.
+    pub fn new(iters: Vec<NestedIters<'a>>, data_type: DataType) -> Self {
+        assert_eq!(iters.len(), StructArray::get_fields(&data_type).len());
+        Self { iters, data_type }
     }
 }
 
@@ -62,7 +62,7 @@ impl<'a> StructIterator<'a> {
             }
         }
         Some(Ok(create_struct(
-            self.fields.clone(),
+            self.data_type.clone(),
             &mut nested,
             new_values,
         )))
@@ -94,7 +94,7 @@ impl<'a> Iterator for StructIterator<'a> {
 }
 
 pub fn create_struct(
-    fields: Vec<Field>,
+    data_type: DataType,
     nested: &mut Vec<NestedState>,
     values: Vec<Box<dyn Array>>,
 ) -> (NestedState, Box<dyn Array>) {
@@ -103,7 +103,7 @@ pub fn create_struct(
     (
         nested,
         Box::new(StructArray::new(
-            DataType::Struct(fields),
+            data_type,
             values,
             validity.and_then(|x| x.into()),
         )),
@@ -0,0 +1,255 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::io::Cursor;
+
+use crate::compression::integer::decompress_integer;
+use crate::read::deserialize::{ArrayIter, ColumnState};
+use crate::read::{read_basic::*, BufReader, NativeReadBuf, PageIterator};
+use crate::write::union::normalize_union;
+use crate::PageMeta;
+use arrow::array::{new_empty_array, Array, UnionArray};
+use arrow::datatypes::DataType;
+use arrow::error::{Error, Result};
+
+use super::concatenate_arrays;
+
+pub struct UnionIter<'a, I>
+where
+    I: Iterator<Item = Result<(u64, Vec<u8>)>> + PageIterator + Send + Sync,
+{
+    iter: I,
+    children: Vec<ColumnState<'a>>,
+    data_type: DataType,
+    scratch: Vec<u8>,
+}
+
+impl<'a, I> UnionIter<'a, I>
+where
+    I: Iterator<Item = Result<(u64, Vec<u8>)>> + PageIterator + Send + Sync,
+{
+    pub fn new(iter: I, children: Vec<ArrayIter<'a>>, data_type: DataType) -> Self {
+        Self {
+            iter,
+            children: children.into_iter().map(ColumnState::new).collect(),
+            data_type,
+            scratch: vec![],
+        }
+    }
+
+    fn deserialize(&mut self, num_values: u64, buffer: Vec<u8>) -> Result<Box<dyn Array>> {
+        let length = num_values as usize;
+        let mut reader = BufReader::with_capacity(buffer.len(), Cursor::new(buffer));
+        let mut types = Vec::with_capacity(length);
+        let mut offsets = (!UnionArray::is_sparse(&self.data_type)).then(Vec::new);
+        let mut child_lengths = vec![0; self.children.len()];
+        read_union_page(
+            &mut reader,
+            length,
+            &self.data_type,
+            &mut types,
+            offsets.as_mut(),
+            &mut child_lengths,
+            &mut self.scratch,
+        )?;
+
+        let mut buffer = reader.into_inner().into_inner();
+        self.iter.swap_buffer(&mut buffer);
+
+        // the rows of the children of the page are pulled from their own pages
+        let fields = UnionArray::get_fields(&self.data_type);
+        let mut arrays = Vec::with_capacity(fields.len());
+        for ((child, field), length) in self
+            .children
+            .iter_mut()
+            .zip(fields.iter())
+            .zip(child_lengths)
+        {
+            let array = match length {
+                0 => new_empty_array(field.data_type().clone()),
+                _ => child
+                    .next_batch(length)?
+                    .unwrap_or_else(|| new_empty_array(field.data_type().clone())),
+            };
+            if array.len() != length {
+                return Err(Error::OutOfSpec(format!(
+                    "The union child {} has {} rows, but the type ids need {length}",
+                    field.name,
+                    array.len()
+                )));
+            }
+            arrays.push(array);
+        }
+
+        let array = UnionArray::try_new(
+            self.data_type.clone(),
+            types.into(),
+            arrays,
+            offsets.map(|offsets| offsets.into()),
+        )?;
+        Ok(Box::new(array) as Box<dyn Array>)
+    }
+}
+
+impl<'a, I> Iterator for UnionIter<'a, I>
+where
+    I: Iterator<Item = Result<(u64, Vec<u8>)>> + PageIterator + Send + Sync,
+{
+    type Item = Result<Box<dyn Array>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.iter.next() {
+            Some(Ok((num_values, buffer))) => Some(self.deserialize(num_values, buffer)),
+            Some(Err(err)) => Some(Result::Err(err)),
+            None => None,
+        }
+    }
+}
+
+// the type ids, the offsets of a dense union and the number of rows of each child
+type UnionTypes = (Vec<i8>, Option<Vec<i32>>, Vec<usize>);
+
+/// Reads the type ids and offsets of the pages of a union, the offsets are rebased
+/// to the children of all the pages. Returns the number of rows of each child too.
+pub fn read_union_types<R: NativeReadBuf>(
+    reader: &mut R,
+    data_type: &DataType,
+    page_metas: Vec<PageMeta>,
+) -> Result<UnionTypes> {
+    let num_values = page_metas.iter().map(|p| p.num_values as usize).sum();
+
+    let mut scratch = vec![];
+    let mut types = Vec::with_capacity(num_values);
+    let mut offsets = (!UnionArray::is_sparse(data_type)).then(|| Vec::with_capacity(num_values));
+    let mut child_lengths = vec![0; UnionArray::get_fields(data_type).len()];
+    let mut position = page_metas.first().map(|p| p.offset).unwrap_or_default();
+    for page_meta in page_metas {
+        skip_to_page(reader, &mut position, &page_meta)?;
+        read_union_page(
+            reader,
+            page_meta.num_values as usize,
+            data_type,
+            &mut types,
+            offsets.as_mut(),
+            &mut child_lengths,
+            &mut scratch,
+        )?;
+    }
+    Ok((types, offsets, child_lengths))
+}
+
+/// Reads the type ids of a page followed by the offsets of a dense union, the offsets
+/// relative to the page are validated and rebased by the `child_lengths` of the previous
+/// pages, which are then increased by the rows of each child of the page.
+fn read_union_page<R: NativeReadBuf>(
+    reader: &mut R,
+    length: usize,
+    data_type: &DataType,
+    types: &mut Vec<i8>,
+    offsets: Option<&mut Vec<i32>>,
+    child_lengths: &mut [usize],
+    scratch: &mut Vec<u8>,
+) -> Result<()> {
+    let start = types.len();
+    decompress_integer(reader, length, types, scratch)?;
+    let fields = types[start..]
+        .iter()
+        .map(|type_| field_index(data_type, *type_))
+        .collect::<Result<Vec<_>>>()?;
+
+    let offsets = match offsets {
+        Some(offsets) => offsets,
+        None => {
+            child_lengths.iter_mut().for_each(|len| *len += length);
+            return Ok(());
+        }
+    };
+    let mut page_offsets: Vec<i32> = Vec::with_capacity(length);
+    decompress_integer(reader, length, &mut page_offsets, scratch)?;
+    let mut page_lengths = vec![0; child_lengths.len()];
+    fields.iter().for_each(|field| page_lengths[*field] += 1);
+    for (field, offset) in fields.iter().zip(page_offsets) {
+        if offset < 0 || offset as usize >= page_lengths[*field] {
+            return Err(Error::OutOfSpec(format!(
+                "The union offset {offset} is out of bounds of the {} rows of child {field}",
+                page_lengths[*field]
+            )));
+        }
+        offsets.push(offset + child_lengths[*field] as i32);
+    }
+    for (len, page_len) in child_lengths.iter_mut().zip(page_lengths) {
+        *len += page_len;
+    }
+    Ok(())
+}
+
+/// Returns the index of the child of a type id.
+fn field_index(data_type: &DataType, type_: i8) -> Result<usize> {
+    let index = match data_type.to_logical_type() {
+        DataType::Union(_, Some(ids), _) => ids.iter().position(|id| *id == type_ as i32),
+        DataType::Union(fields, None, _) => {
+            (type_ >= 0 && (type_ as usize) < fields.len()).then_some(type_ as usize)
+        }
+        _ => unreachable!(),
+    };
+    index.ok_or_else(|| {
+        Error::OutOfSpec(format!(
+            "The type id {type_} is not a type of the union {data_type:?}"
+        ))
+    })
+}
+
+/// Concatenates unions, the children are concatenated after normalizing the unions, as
+/// concatenating the sparse unions sliced from their children isn't supported by arrow.
+pub(crate) fn concatenate_unions(arrays: &[&dyn Array]) -> Result<Box<dyn Array>> {
+    let arrays = arrays
+        .iter()
+        .map(|array| normalize_union(array.as_any().downcast_ref().unwrap()))
+        .collect::<Vec<_>>();
+    let data_type = arrays[0].data_type().clone();
+    let num_fields = arrays[0].fields().len();
+
+    let types = arrays
+        .iter()
+        .flat_map(|array| array.types().iter().copied())
+        .collect::<Vec<_>>();
+    let fields = (0..num_fields)
+        .map(|i| {
+            let children = arrays
+                .iter()
+                .map(|array| array.fields()[i].as_ref())
+                .collect::<Vec<_>>();
+            concatenate_arrays(&children)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let offsets = arrays[0].offsets().is_some().then(|| {
+        let mut bases = vec![0; num_fields];
+        let mut offsets = Vec::with_capacity(types.len());
+        for array in arrays.iter() {
+            for (i, offset) in array.offsets().unwrap().iter().enumerate() {
+                offsets.push(bases[array.index(i).0] + offset);
+            }
+            for (base, field) in bases.iter_mut().zip(array.fields()) {
+                *base += field.len() as i32;
+            }
+        }
+        offsets.into()
+    });
+
+    let array = UnionArray::try_new(data_type, types.into(), fields, offsets)?;
+    Ok(Box::new(array) as Box<dyn Array>)
+}
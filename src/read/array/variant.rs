@@ -0,0 +1,534 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::io::Cursor;
+use std::marker::PhantomData;
+
+use crate::compression::binary::{decompress_binary, skip_binary};
+use crate::compression::boolean::decompress_boolean;
+use crate::compression::double::decompress_double;
+use crate::compression::integer::decompress_integer;
+use crate::read::{read_basic::*, BufReader, NativeReadBuf, PageIterator};
+use crate::variant::{get_path, insert_path, variant_path, ShreddedType};
+use crate::PageMeta;
+use arrow::array::{Array, Utf8Array};
+use arrow::bitmap::{Bitmap, MutableBitmap};
+use arrow::datatypes::DataType;
+use arrow::error::{Error, Result};
+use arrow::io::parquet::read::{InitNested, NestedState};
+use arrow::offset::Offsets;
+use arrow::types::Offset;
+use parquet2::metadata::ColumnDescriptor;
+use serde_json::Value;
+
+pub struct VariantIter<I, O>
+where
+    I: Iterator<Item = Result<(u64, Vec<u8>)>> + PageIterator + Send + Sync,
+    O: Offset,
+{
+    iter: I,
+    is_nullable: bool,
+    data_type: DataType,
+    path: Option<Vec<String>>,
+    scratch: Vec<u8>,
+    _phantom: PhantomData<O>,
+}
+
+impl<I, O> VariantIter<I, O>
+where
+    I: Iterator<Item = Result<(u64, Vec<u8>)>> + PageIterator + Send + Sync,
+    O: Offset,
+{
+    pub fn new(iter: I, is_nullable: bool, data_type: DataType) -> Self {
+        Self {
+            iter,
+            is_nullable,
+            path: variant_path(&data_type),
+            data_type,
+            scratch: vec![],
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<I, O> VariantIter<I, O>
+where
+    I: Iterator<Item = Result<(u64, Vec<u8>)>> + PageIterator + Send + Sync,
+    O: Offset,
+{
+    fn deserialize(&mut self, num_values: u64, buffer: Vec<u8>) -> Result<Box<dyn Array>> {
+        let length = num_values as usize;
+        let mut reader = BufReader::with_capacity(buffer.len(), Cursor::new(buffer));
+        let validity = if self.is_nullable {
+            let mut validity_builder = MutableBitmap::with_capacity(length);
+            read_validity(&mut reader, length, &mut validity_builder)?;
+            Some(std::mem::take(&mut validity_builder).into())
+        } else {
+            None
+        };
+
+        let mut builder = VariantBuilder::<O>::with_capacity(length);
+        builder.decode_page(
+            &mut reader,
+            length,
+            validity.as_ref(),
+            self.path.as_deref(),
+            &mut self.scratch,
+        )?;
+
+        let mut buffer = reader.into_inner().into_inner();
+        self.iter.swap_buffer(&mut buffer);
+
+        builder.finish(self.data_type.clone())
+    }
+}
+
+impl<I, O> Iterator for VariantIter<I, O>
+where
+    I: Iterator<Item = Result<(u64, Vec<u8>)>> + PageIterator + Send + Sync,
+    O: Offset,
+{
+    type Item = Result<Box<dyn Array>>;
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        match self.iter.nth(n) {
+            Some(Ok((num_values, buffer))) => Some(self.deserialize(num_values, buffer)),
+            Some(Err(err)) => Some(Result::Err(err)),
+            None => None,
+        }
+    }
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.iter.next() {
+            Some(Ok((num_values, buffer))) => Some(self.deserialize(num_values, buffer)),
+            Some(Err(err)) => Some(Result::Err(err)),
+            None => None,
+        }
+    }
+}
+
+pub struct VariantNestedIter<I, O>
+where
+    I: Iterator<Item = Result<(u64, Vec<u8>)>> + PageIterator + Send + Sync,
+    O: Offset,
+{
+    iter: I,
+    data_type: DataType,
+    path: Option<Vec<String>>,
+    leaf: ColumnDescriptor,
+    init: Vec<InitNested>,
+    scratch: Vec<u8>,
+    _phantom: PhantomData<O>,
+}
+
+impl<I, O> VariantNestedIter<I, O>
+where
+    I: Iterator<Item = Result<(u64, Vec<u8>)>> + PageIterator + Send + Sync,
+    O: Offset,
+{
+    pub fn new(
+        iter: I,
+        data_type: DataType,
+        leaf: ColumnDescriptor,
+        init: Vec<InitNested>,
+    ) -> Self {
+        Self {
+            iter,
+            path: variant_path(&data_type),
+            data_type,
+            leaf,
+            init,
+            scratch: vec![],
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<I, O> VariantNestedIter<I, O>
+where
+    I: Iterator<Item = Result<(u64, Vec<u8>)>> + PageIterator + Send + Sync,
+    O: Offset,
+{
+    fn deserialize(
+        &mut self,
+        num_values: u64,
+        buffer: Vec<u8>,
+    ) -> Result<(NestedState, Box<dyn Array>)> {
+        let mut reader = BufReader::with_capacity(buffer.len(), Cursor::new(buffer));
+        let (mut nested, validity) = read_validity_nested(
+            &mut reader,
+            num_values as usize,
+            &self.leaf,
+            self.init.clone(),
+        )?;
+        let length = nested.nested.pop().unwrap().len();
+
+        let mut builder = VariantBuilder::<O>::with_capacity(length);
+        builder.decode_page(
+            &mut reader,
+            length,
+            validity.as_ref(),
+            self.path.as_deref(),
+            &mut self.scratch,
+        )?;
+
+        let mut buffer = reader.into_inner().into_inner();
+        self.iter.swap_buffer(&mut buffer);
+
+        Ok((nested, builder.finish(self.data_type.clone())?))
+    }
+}
+
+impl<I, O> Iterator for VariantNestedIter<I, O>
+where
+    I: Iterator<Item = Result<(u64, Vec<u8>)>> + PageIterator + Send + Sync,
+    O: Offset,
+{
+    type Item = Result<(NestedState, Box<dyn Array>)>;
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        match self.iter.nth(n) {
+            Some(Ok((num_values, buffer))) => Some(self.deserialize(num_values, buffer)),
+            Some(Err(err)) => Some(Result::Err(err)),
+            None => None,
+        }
+    }
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.iter.next() {
+            Some(Ok((num_values, buffer))) => Some(self.deserialize(num_values, buffer)),
+            Some(Err(err)) => Some(Result::Err(err)),
+            None => None,
+        }
+    }
+}
+
+pub fn read_variant<O: Offset, R: NativeReadBuf>(
+    reader: &mut R,
+    is_nullable: bool,
+    data_type: DataType,
+    page_metas: Vec<PageMeta>,
+) -> Result<Box<dyn Array>> {
+    let num_values = page_metas.iter().map(|p| p.num_values as usize).sum();
+    let path = variant_path(&data_type);
+
+    let mut scratch = vec![];
+    let mut builder = VariantBuilder::<O>::with_capacity(num_values);
+    let mut position = page_metas.first().map(|p| p.offset).unwrap_or_default();
+    for page_meta in page_metas {
+        skip_to_page(reader, &mut position, &page_meta)?;
+        let length = page_meta.num_values as usize;
+        let validity = if is_nullable {
+            let mut validity_builder = MutableBitmap::with_capacity(length);
+            read_validity(reader, length, &mut validity_builder)?;
+            Some(validity_builder.into())
+        } else {
+            None
+        };
+        builder.decode_page(
+            reader,
+            length,
+            validity.as_ref(),
+            path.as_deref(),
+            &mut scratch,
+        )?;
+    }
+    builder.finish(data_type)
+}
+
+pub fn read_nested_variant<O: Offset, R: NativeReadBuf>(
+    reader: &mut R,
+    data_type: DataType,
+    leaf: ColumnDescriptor,
+    init: Vec<InitNested>,
+    page_metas: Vec<PageMeta>,
+) -> Result<Vec<(NestedState, Box<dyn Array>)>> {
+    let path = variant_path(&data_type);
+    let mut scratch = vec![];
+    let mut results = Vec::with_capacity(page_metas.len());
+    let mut position = page_metas.first().map(|p| p.offset).unwrap_or_default();
+    for page_meta in page_metas {
+        skip_to_page(reader, &mut position, &page_meta)?;
+        let num_values = page_meta.num_values as usize;
+        let (mut nested, validity) = read_validity_nested(reader, num_values, &leaf, init.clone())?;
+        let length = nested.nested.pop().unwrap().len();
+
+        let mut builder = VariantBuilder::<O>::with_capacity(length);
+        builder.decode_page(
+            reader,
+            length,
+            validity.as_ref(),
+            path.as_deref(),
+            &mut scratch,
+        )?;
+        results.push((nested, builder.finish(data_type.clone())?));
+    }
+    Ok(results)
+}
+
+/// The shredded scalars of a path.
+enum Scalars {
+    Int64(Vec<i64>),
+    Float64(Vec<f64>),
+    String(Vec<i64>, Vec<u8>),
+    Boolean(MutableBitmap),
+}
+
+impl Scalars {
+    fn value(&self, index: usize) -> Result<Value> {
+        Ok(match self {
+            Scalars::Int64(values) => Value::from(values[index]),
+            Scalars::Float64(values) => Value::from(values[index]),
+            Scalars::String(offsets, values) => {
+                let bytes = &values[offsets[index] as usize..offsets[index + 1] as usize];
+                let string = std::str::from_utf8(bytes)
+                    .map_err(|err| Error::OutOfSpec(format!("Invalid shredded string: {err}")))?;
+                Value::from(string)
+            }
+            Scalars::Boolean(values) => Value::from(values.get(index)),
+        })
+    }
+}
+
+/// A shredded path of a page, its sub-column is decoded when needed.
+struct ShreddedPath {
+    path: Vec<String>,
+    type_: ShreddedType,
+    bytes: Vec<u8>,
+    decoded: Option<SubColumn>,
+}
+
+struct SubColumn {
+    // the index of the scalar of each row, if it's shredded
+    indices: Vec<Option<usize>>,
+    other: MutableBitmap,
+    scalars: Scalars,
+}
+
+impl ShreddedPath {
+    fn read<R: NativeReadBuf>(reader: &mut R) -> Result<Self> {
+        let mut buf = [0u8; 4];
+        let num_keys = read_u32(reader, &mut buf)?;
+        let path = (0..num_keys)
+            .map(|_| {
+                let mut key = vec![0u8; read_u32(reader, &mut buf)? as usize];
+                reader.read_exact(&mut key)?;
+                String::from_utf8(key)
+                    .map_err(|err| Error::OutOfSpec(format!("Invalid shredded path: {err}")))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let mut tag = [0u8; 1];
+        reader.read_exact(&mut tag)?;
+        let type_ = ShreddedType::from_u8(tag[0])?;
+        let mut bytes = vec![0u8; read_u32(reader, &mut buf)? as usize];
+        reader.read_exact(&mut bytes)?;
+        Ok(Self {
+            path,
+            type_,
+            bytes,
+            decoded: None,
+        })
+    }
+
+    fn decode(&mut self, length: usize, scratch: &mut Vec<u8>) -> Result<()> {
+        if self.decoded.is_some() {
+            return Ok(());
+        }
+        let mut reader = self.bytes.as_slice();
+        let mut shredded = MutableBitmap::with_capacity(length);
+        decompress_boolean(&mut reader, length, &mut shredded, scratch)?;
+        let mut other = MutableBitmap::with_capacity(length);
+        decompress_boolean(&mut reader, length, &mut other, scratch)?;
+
+        let mut count = 0;
+        let indices = (0..length)
+            .map(|i| {
+                shredded.get(i).then(|| {
+                    count += 1;
+                    count - 1
+                })
+            })
+            .collect();
+        let scalars = match self.type_ {
+            ShreddedType::Int64 => {
+                let mut values = Vec::with_capacity(count);
+                decompress_integer(&mut reader, count, &mut values, scratch)?;
+                Scalars::Int64(values)
+            }
+            ShreddedType::Float64 => {
+                let mut values = Vec::with_capacity(count);
+                decompress_double(&mut reader, count, &mut values, scratch)?;
+                Scalars::Float64(values)
+            }
+            ShreddedType::String => {
+                let mut offsets = Vec::with_capacity(count + 1);
+                let mut values = vec![];
+                decompress_binary(&mut reader, count, &mut offsets, &mut values, scratch)?;
+                Scalars::String(offsets, values)
+            }
+            ShreddedType::Boolean => {
+                let mut values = MutableBitmap::with_capacity(count);
+                decompress_boolean(&mut reader, count, &mut values, scratch)?;
+                Scalars::Boolean(values)
+            }
+        };
+        self.decoded = Some(SubColumn {
+            indices,
+            other,
+            scalars,
+        });
+        Ok(())
+    }
+
+    fn sub_column(&self) -> &SubColumn {
+        self.decoded.as_ref().unwrap()
+    }
+}
+
+/// Builds the JSON text of the variant values of pages.
+struct VariantBuilder<O: Offset> {
+    offsets: Offsets<O>,
+    values: Vec<u8>,
+    validity: MutableBitmap,
+}
+
+impl<O: Offset> VariantBuilder<O> {
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            offsets: Offsets::with_capacity(capacity),
+            values: vec![],
+            validity: MutableBitmap::with_capacity(capacity),
+        }
+    }
+
+    /// Decodes the values of a page, or the values at `path` if it's some.
+    ///
+    /// The shredded scalars at `path` are written without parsing the remainders,
+    /// the values are rebuilt from the remainders only for the rows with another value
+    /// at `path`, or if the path isn't shredded. The remainders are skipped without being
+    /// decompressed if no row needs them.
+    fn decode_page<R: NativeReadBuf>(
+        &mut self,
+        reader: &mut R,
+        length: usize,
+        validity: Option<&Bitmap>,
+        path: Option<&[String]>,
+        scratch: &mut Vec<u8>,
+    ) -> Result<()> {
+        let mut buf = [0u8; 4];
+        let num_paths = read_u32(reader, &mut buf)?;
+        let mut shredded = (0..num_paths)
+            .map(|_| ShreddedPath::read(reader))
+            .collect::<Result<Vec<_>>>()?;
+
+        let exact = path.and_then(|path| shredded.iter().position(|s| s.path == path));
+        let is_valid = |i: usize| validity.map_or(true, |validity| validity.get_bit(i));
+        // the values at a shredded path only need the remainders of the rows
+        // with another value at the path
+        let needs_remainders = match exact {
+            Some(exact) => {
+                shredded[exact].decode(length, scratch)?;
+                let sub_column = shredded[exact].sub_column();
+                (0..length).any(|i| is_valid(i) && sub_column.other.get(i))
+            }
+            None => true,
+        };
+
+        let mut remainder_offsets: Vec<i64> = Vec::with_capacity(length + 1);
+        let mut remainders = vec![];
+        if needs_remainders {
+            for s in shredded.iter_mut() {
+                s.decode(length, scratch)?;
+            }
+            decompress_binary(
+                reader,
+                length,
+                &mut remainder_offsets,
+                &mut remainders,
+                scratch,
+            )?;
+        } else {
+            skip_binary::<i64, _>(reader)?;
+        }
+        let remainder = |i: usize| {
+            &remainders[remainder_offsets[i] as usize..remainder_offsets[i + 1] as usize]
+        };
+
+        for i in 0..length {
+            let start = self.values.len();
+            let is_valid = match (is_valid(i), path) {
+                (false, _) => false,
+                (true, None) if shredded.is_empty() => {
+                    self.values.extend_from_slice(remainder(i));
+                    true
+                }
+                (true, None) => {
+                    let value = rebuild(remainder(i), &shredded, i)?;
+                    serde_json::to_writer(&mut self.values, &value).unwrap();
+                    true
+                }
+                (true, Some(path)) => {
+                    let value = match exact.map(|exact| shredded[exact].sub_column()) {
+                        Some(sub_column) => match sub_column.indices[i] {
+                            Some(index) => Some(sub_column.scalars.value(index)?),
+                            None if sub_column.other.get(i) => {
+                                let value = rebuild(remainder(i), &shredded, i)?;
+                                get_path(&value, path).cloned()
+                            }
+                            None => None,
+                        },
+                        None => {
+                            let value = rebuild(remainder(i), &shredded, i)?;
+                            get_path(&value, path).cloned()
+                        }
+                    };
+                    match value {
+                        Some(value) => {
+                            serde_json::to_writer(&mut self.values, &value).unwrap();
+                            true
+                        }
+                        None => false,
+                    }
+                }
+            };
+            self.validity.push(is_valid);
+            self.offsets.try_push_usize(self.values.len() - start)?;
+        }
+        Ok(())
+    }
+
+    fn finish(self, data_type: DataType) -> Result<Box<dyn Array>> {
+        let validity: Bitmap = self.validity.into();
+        let validity = (validity.unset_bits() > 0).then_some(validity);
+        let array =
+            Utf8Array::<O>::try_new(data_type, self.offsets.into(), self.values.into(), validity)?;
+        Ok(Box::new(array) as Box<dyn Array>)
+    }
+}
+
+/// Rebuilds a value from its remainder and the shredded scalars of the row.
+fn rebuild(remainder: &[u8], shredded: &[ShreddedPath], row: usize) -> Result<Value> {
+    let mut value: Value = serde_json::from_slice(remainder)
+        .map_err(|err| Error::OutOfSpec(format!("Invalid remainder of a variant value: {err}")))?;
+    for s in shredded {
+        let sub_column = s.sub_column();
+        if let Some(index) = sub_column.indices[row] {
+            insert_path(&mut value, &s.path, sub_column.scalars.value(index)?)?;
+        }
+    }
+    Ok(value)
+}
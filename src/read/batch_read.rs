@@ -15,13 +15,21 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use super::{array::*, NativeReadBuf};
-use crate::{with_match_integer_double_type, PageMeta};
+use super::{
+    array::*,
+    read_basic::skip_to,
+    reader::{is_primitive, verify_checksum, verify_dictionary_checksum, AsyncNativeReader},
+    NativeReadBuf, PageIterator,
+};
+use crate::variant::is_variant;
+use crate::write::n_columns;
+use crate::{with_match_integer_double_type, with_match_interval_type, PageMeta};
 use arrow::array::*;
 use arrow::compute::concatenate::concatenate;
-use arrow::datatypes::{DataType, Field, PhysicalType};
-use arrow::error::Result;
-use arrow::io::parquet::read::{create_list, create_map, n_columns, InitNested, NestedState};
+use arrow::datatypes::{DataType, Field, PhysicalType, PrimitiveType};
+use arrow::error::{Error, Result};
+use arrow::io::parquet::read::{create_list, create_map, InitNested, NestedState};
+use futures::{AsyncRead, AsyncSeek};
 use parquet2::metadata::ColumnDescriptor;
 
 pub fn read_simple<R: NativeReadBuf>(
@@ -37,6 +45,11 @@ pub fn read_simple<R: NativeReadBuf>(
     match data_type.to_physical_type() {
         Null => read_null(data_type, page_metas),
         Boolean => read_boolean(reader, is_nullable, data_type, page_metas),
+        Primitive(interval @ (PrimitiveType::DaysMs | PrimitiveType::MonthDayNano)) => {
+            with_match_interval_type!(interval, |$T| {
+                read_interval::<$T, _>(reader, is_nullable, data_type, page_metas)
+            })
+        }
         Primitive(primitive) => with_match_integer_double_type!(primitive,
         |$T| {
             read_integer::<$T, _>(
@@ -54,11 +67,18 @@ pub fn read_simple<R: NativeReadBuf>(
                 page_metas,
             )
         }),
+        Utf8 if is_variant(&data_type) => {
+            read_variant::<i32, _>(reader, is_nullable, data_type, page_metas)
+        }
+        LargeUtf8 if is_variant(&data_type) => {
+            read_variant::<i64, _>(reader, is_nullable, data_type, page_metas)
+        }
         Binary | Utf8 => read_binary::<i32, _>(reader, is_nullable, data_type, page_metas),
         LargeBinary | LargeUtf8 => {
             read_binary::<i64, _>(reader, is_nullable, data_type, page_metas)
         }
-        FixedSizeBinary => unimplemented!(),
+        FixedSizeBinary => read_fixed_binary(reader, is_nullable, data_type, page_metas),
+        Dictionary(_) => read_dictionary(reader, is_nullable, data_type, page_metas),
         _ => unreachable!(),
     }
 }
@@ -84,6 +104,18 @@ pub fn read_nested<R: NativeReadBuf>(
                 page_metas.pop().unwrap(),
             )?
         }
+        Primitive(interval @ (PrimitiveType::DaysMs | PrimitiveType::MonthDayNano)) => {
+            init.push(InitNested::Primitive(field.is_nullable));
+            with_match_interval_type!(interval, |$T| {
+                read_nested_interval::<$T, _>(
+                    &mut readers.pop().unwrap(),
+                    field.data_type().clone(),
+                    leaves.pop().unwrap(),
+                    init,
+                    page_metas.pop().unwrap(),
+                )?
+            })
+        }
         Primitive(primitive) => with_match_integer_double_type!(primitive,
         |$T| {
             init.push(InitNested::Primitive(field.is_nullable));
@@ -106,6 +138,26 @@ pub fn read_nested<R: NativeReadBuf>(
             )?
         }
         ),
+        Utf8 if is_variant(field.data_type()) => {
+            init.push(InitNested::Primitive(field.is_nullable));
+            read_nested_variant::<i32, _>(
+                &mut readers.pop().unwrap(),
+                field.data_type().clone(),
+                leaves.pop().unwrap(),
+                init,
+                page_metas.pop().unwrap(),
+            )?
+        }
+        LargeUtf8 if is_variant(field.data_type()) => {
+            init.push(InitNested::Primitive(field.is_nullable));
+            read_nested_variant::<i64, _>(
+                &mut readers.pop().unwrap(),
+                field.data_type().clone(),
+                leaves.pop().unwrap(),
+                init,
+                page_metas.pop().unwrap(),
+            )?
+        }
         Binary | Utf8 => {
             init.push(InitNested::Primitive(field.is_nullable));
             read_nested_binary::<i32, _>(
@@ -126,8 +178,26 @@ pub fn read_nested<R: NativeReadBuf>(
                 page_metas.pop().unwrap(),
             )?
         }
-
-        FixedSizeBinary => unimplemented!(),
+        Dictionary(_) => {
+            init.push(InitNested::Primitive(field.is_nullable));
+            read_nested_dictionary(
+                &mut readers.pop().unwrap(),
+                field.data_type().clone(),
+                leaves.pop().unwrap(),
+                init,
+                page_metas.pop().unwrap(),
+            )?
+        }
+        FixedSizeBinary => {
+            init.push(InitNested::Primitive(field.is_nullable));
+            read_nested_fixed_binary(
+                &mut readers.pop().unwrap(),
+                field.data_type().clone(),
+                leaves.pop().unwrap(),
+                init,
+                page_metas.pop().unwrap(),
+            )?
+        }
         _ => match field.data_type().to_logical_type() {
             DataType::List(inner)
             | DataType::LargeList(inner)
@@ -175,7 +245,7 @@ pub fn read_nested<R: NativeReadBuf>(
                         nesteds.push(nested);
                         values.push(value);
                     }
-                    let array = create_struct(fields.clone(), &mut nesteds, values);
+                    let array = create_struct(field.data_type().clone(), &mut nesteds, values);
                     arrays.push(array);
                 }
                 arrays.reverse();
@@ -187,15 +257,83 @@ pub fn read_nested<R: NativeReadBuf>(
 }
 
 /// Read all pages of column at once.
+///
+/// The pages with checksums or dictionaries are read into buffers first,
+/// they are verified before being decoded.
 pub fn batch_read_array<R: NativeReadBuf>(
-    mut readers: Vec<R>,
+    readers: Vec<R>,
     leaves: Vec<ColumnDescriptor>,
     field: Field,
     is_nested: bool,
+    page_metas: Vec<Vec<PageMeta>>,
+) -> Result<Box<dyn Array>> {
+    if page_metas
+        .iter()
+        .flatten()
+        .any(|p| p.checksum.is_some() || p.dictionary.is_some())
+    {
+        let mut buffers = Vec::with_capacity(readers.len());
+        let mut buffer_page_metas = Vec::with_capacity(readers.len());
+        for ((mut reader, page_metas), leaf) in readers.into_iter().zip(page_metas).zip(&leaves) {
+            let column = leaf.path_in_schema.join(".");
+            let (buffer, page_metas) =
+                read_pages_with_dictionary(&mut reader, page_metas, &column)?;
+            buffers.push(std::io::Cursor::new(buffer));
+            buffer_page_metas.push(page_metas);
+        }
+        return read_array(buffers, leaves, field, is_nested, buffer_page_metas);
+    }
+    read_array(readers, leaves, field, is_nested, page_metas)
+}
+
+/// Reads all pages of a column at once, the pages are decoded from the readers directly.
+fn read_array<R: NativeReadBuf>(
+    mut readers: Vec<R>,
+    mut leaves: Vec<ColumnDescriptor>,
+    field: Field,
+    is_nested: bool,
     mut page_metas: Vec<Vec<PageMeta>>,
 ) -> Result<Box<dyn Array>> {
+    // the first leaf column is the type ids, followed by the leaf columns of each child
+    if let DataType::Union(fields, _, _) = field.data_type().to_logical_type() {
+        leaves.remove(0);
+        let (types, offsets, child_lengths) = read_union_types(
+            &mut readers.remove(0),
+            field.data_type(),
+            page_metas.remove(0),
+        )?;
+        let mut arrays = Vec::with_capacity(fields.len());
+        for (f, length) in fields.iter().zip(child_lengths) {
+            let n = n_columns(&f.data_type);
+            let array = read_array(
+                readers.drain(..n).collect(),
+                leaves.drain(..n).collect(),
+                f.clone(),
+                !is_primitive(&f.data_type),
+                page_metas.drain(..n).collect(),
+            )?;
+            if array.len() != length {
+                return Err(Error::OutOfSpec(format!(
+                    "The union child {} has {} rows, but the type ids need {length}",
+                    f.name,
+                    array.len()
+                )));
+            }
+            arrays.push(array);
+        }
+        let array = UnionArray::try_new(
+            field.data_type().clone(),
+            types.into(),
+            arrays,
+            offsets.map(|offsets| offsets.into()),
+        )?;
+        return Ok(Box::new(array));
+    }
     if is_nested {
-        let results = read_nested(readers, field, leaves, vec![], page_metas)?;
+        let results = read_nested(readers, field.clone(), leaves, vec![], page_metas)?;
+        if results.is_empty() {
+            return Ok(new_empty_array(field.data_type().clone()));
+        }
         let arrays: Vec<&dyn Array> = results.iter().map(|(_, v)| v.as_ref()).collect();
         let array = concatenate(&arrays).unwrap();
         Ok(array)
@@ -207,3 +345,99 @@ pub fn batch_read_array<R: NativeReadBuf>(
         )
     }
 }
+
+/// Reads the pages and their dictionaries into a contiguous buffer, as the pages are
+/// decoded from the reader directly. A dictionary shared by several pages is read once.
+/// The checksums of the pages and of the dictionaries are verified.
+/// Returns the buffer and the pages rebased to the buffer.
+fn read_pages_with_dictionary<R: NativeReadBuf>(
+    reader: &mut R,
+    page_metas: Vec<PageMeta>,
+    column: &str,
+) -> Result<(Vec<u8>, Vec<PageMeta>)> {
+    let mut buffer = Vec::with_capacity(page_metas.iter().map(|p| p.length as usize).sum());
+    let mut pages = Vec::with_capacity(page_metas.len());
+    let mut position = page_metas
+        .first()
+        .map(|p| p.start_offset())
+        .unwrap_or_default();
+    // the offsets of the last dictionary in the reader and in the buffer
+    let mut dictionary: Option<(u64, u64)> = None;
+    for (page_index, mut page_meta) in page_metas.into_iter().enumerate() {
+        if let Some(meta) = &mut page_meta.dictionary {
+            match dictionary {
+                Some((offset, rebased)) if offset == meta.offset => meta.offset = rebased,
+                _ => {
+                    skip_to(reader, &mut position, meta.offset)?;
+                    let start = buffer.len();
+                    buffer.resize(start + meta.length as usize, 0);
+                    reader.read_exact(&mut buffer[start..])?;
+                    position += meta.length;
+                    verify_dictionary_checksum(meta, &buffer[start..], column, page_index)?;
+                    dictionary = Some((meta.offset, start as u64));
+                    meta.offset = start as u64;
+                }
+            }
+        }
+        skip_to(reader, &mut position, page_meta.offset)?;
+        if position != page_meta.offset {
+            return Err(Error::OutOfSpec(format!(
+                "The page at offset {} is before the position {position} of the reader",
+                page_meta.offset
+            )));
+        }
+        let start = buffer.len();
+        buffer.resize(start + page_meta.length as usize, 0);
+        reader.read_exact(&mut buffer[start..])?;
+        position += page_meta.length;
+        verify_checksum(&page_meta, &buffer[start..], column, page_index)?;
+
+        page_meta.offset = start as u64;
+        pages.push(page_meta);
+    }
+    Ok((buffer, pages))
+}
+
+/// Read all pages of column at once from [`AsyncRead`] + [`AsyncSeek`] sources,
+/// the `readers` must be positioned at the offset of the first page.
+pub async fn batch_read_array_async<R: AsyncRead + AsyncSeek + Send + Unpin>(
+    readers: Vec<R>,
+    leaves: Vec<ColumnDescriptor>,
+    field: Field,
+    is_nested: bool,
+    page_metas: Vec<Vec<PageMeta>>,
+) -> Result<Box<dyn Array>> {
+    let mut buffers = Vec::with_capacity(readers.len());
+    let mut buffer_page_metas = Vec::with_capacity(readers.len());
+    for ((reader, page_metas), leaf) in readers.into_iter().zip(page_metas).zip(&leaves) {
+        let mut buffer = Vec::with_capacity(page_metas.iter().map(|p| p.length as usize).sum());
+        let mut pages = Vec::with_capacity(page_metas.len());
+        let mut reader = AsyncNativeReader::new(reader, page_metas.clone(), vec![])
+            .with_column(leaf.path_in_schema.join("."));
+        // the pages and their dictionaries are read into a contiguous buffer,
+        // with the offsets of the last dictionary in the reader and in the buffer
+        let mut dictionary: Option<(u64, u64)> = None;
+        for mut page_meta in page_metas {
+            let (_, page) = reader.next_page().await.unwrap()?;
+            // the page and its dictionary are verified
+            if let (Some(meta), Some((offset, bytes))) =
+                (&mut page_meta.dictionary, reader.dictionary())
+            {
+                match dictionary {
+                    Some((last, rebased)) if last == offset => meta.offset = rebased,
+                    _ => {
+                        dictionary = Some((offset, buffer.len() as u64));
+                        meta.offset = buffer.len() as u64;
+                        buffer.extend_from_slice(bytes);
+                    }
+                }
+            }
+            page_meta.offset = buffer.len() as u64;
+            buffer.extend_from_slice(&page);
+            pages.push(page_meta);
+        }
+        buffers.push(std::io::Cursor::new(buffer));
+        buffer_page_metas.push(pages);
+    }
+    read_array(buffers, leaves, field, is_nested, buffer_page_metas)
+}
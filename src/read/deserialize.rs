@@ -15,12 +15,16 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use super::{array::*, PageIterator};
-use crate::with_match_integer_double_type;
+use std::collections::VecDeque;
+
+use super::{array::*, reader::is_primitive, PageIterator};
+use crate::variant::is_variant;
+use crate::write::n_columns;
+use crate::{with_match_integer_double_type, with_match_interval_type};
 use arrow::array::*;
-use arrow::datatypes::{DataType, Field, PhysicalType};
+use arrow::datatypes::{DataType, Field, PhysicalType, PrimitiveType};
 use arrow::error::Result;
-use arrow::io::parquet::read::{n_columns, InitNested, NestedState};
+use arrow::io::parquet::read::{InitNested, NestedState};
 use parquet2::metadata::ColumnDescriptor;
 
 /// [`DynIter`] is an iterator adapter adds a custom `nth` method implementation.
@@ -112,6 +116,11 @@ where
     Ok(match data_type.to_physical_type() {
         Null => DynIter::new(NullIter::new(reader, data_type)),
         Boolean => DynIter::new(BooleanIter::new(reader, is_nullable, data_type)),
+        Primitive(interval @ (PrimitiveType::DaysMs | PrimitiveType::MonthDayNano)) => {
+            with_match_interval_type!(interval, |$T| {
+                DynIter::new(IntervalIter::<_, $T>::new(reader, is_nullable, data_type))
+            })
+        }
         Primitive(primitive) => with_match_integer_double_type!(primitive,
         |$I| {
             DynIter::new(IntegerIter::<_, $I>::new(
@@ -128,11 +137,21 @@ where
             ))
         }
         ),
+        Utf8 if is_variant(&data_type) => {
+            DynIter::new(VariantIter::<_, i32>::new(reader, is_nullable, data_type))
+        }
+        LargeUtf8 if is_variant(&data_type) => {
+            DynIter::new(VariantIter::<_, i64>::new(reader, is_nullable, data_type))
+        }
         Binary | Utf8 => DynIter::new(BinaryIter::<_, i32>::new(reader, is_nullable, data_type)),
         LargeBinary | LargeUtf8 => {
             DynIter::new(BinaryIter::<_, i64>::new(reader, is_nullable, data_type))
         }
-        FixedSizeBinary => unimplemented!(),
+        FixedSizeBinary => DynIter::new(FixedBinaryIter::new(reader, is_nullable, data_type)),
+        Dictionary(_) => {
+            dictionary_value_type(&data_type)?;
+            DynIter::new(DictionaryIter::new(reader, is_nullable, data_type))
+        }
         _ => unreachable!(),
     })
 }
@@ -159,6 +178,17 @@ where
                 init,
             ))
         }
+        Primitive(interval @ (PrimitiveType::DaysMs | PrimitiveType::MonthDayNano)) => {
+            init.push(InitNested::Primitive(field.is_nullable));
+            with_match_interval_type!(interval, |$T| {
+                DynIter::new(IntervalNestedIter::<_, $T>::new(
+                    readers.pop().unwrap(),
+                    field.data_type().clone(),
+                    leaves.pop().unwrap(),
+                    init,
+                ))
+            })
+        }
         Primitive(primitive) => with_match_integer_double_type!(primitive,
         |$I| {
             init.push(InitNested::Primitive(field.is_nullable));
@@ -179,6 +209,24 @@ where
             ))
         }
         ),
+        Utf8 if is_variant(field.data_type()) => {
+            init.push(InitNested::Primitive(field.is_nullable));
+            DynIter::new(VariantNestedIter::<_, i32>::new(
+                readers.pop().unwrap(),
+                field.data_type().clone(),
+                leaves.pop().unwrap(),
+                init,
+            ))
+        }
+        LargeUtf8 if is_variant(field.data_type()) => {
+            init.push(InitNested::Primitive(field.is_nullable));
+            DynIter::new(VariantNestedIter::<_, i64>::new(
+                readers.pop().unwrap(),
+                field.data_type().clone(),
+                leaves.pop().unwrap(),
+                init,
+            ))
+        }
         Binary | Utf8 => {
             init.push(InitNested::Primitive(field.is_nullable));
             DynIter::new(BinaryNestedIter::<_, i32>::new(
@@ -197,8 +245,25 @@ where
                 init,
             ))
         }
-
-        FixedSizeBinary => unimplemented!(),
+        Dictionary(_) => {
+            dictionary_value_type(field.data_type())?;
+            init.push(InitNested::Primitive(field.is_nullable));
+            DynIter::new(DictionaryNestedIter::new(
+                readers.pop().unwrap(),
+                field.data_type().clone(),
+                leaves.pop().unwrap(),
+                init,
+            ))
+        }
+        FixedSizeBinary => {
+            init.push(InitNested::Primitive(field.is_nullable));
+            DynIter::new(FixedBinaryNestedIter::new(
+                readers.pop().unwrap(),
+                field.data_type().clone(),
+                leaves.pop().unwrap(),
+                init,
+            ))
+        }
         _ => match field.data_type().to_logical_type() {
             DataType::List(inner)
             | DataType::LargeList(inner)
@@ -226,7 +291,7 @@ where
                     })
                     .collect::<Result<Vec<_>>>()?;
                 let columns = columns.into_iter().rev().collect();
-                DynIter::new(StructIterator::new(columns, fields.clone()))
+                DynIter::new(StructIterator::new(columns, field.data_type().clone()))
             }
             _ => unreachable!(),
         },
@@ -243,6 +308,22 @@ pub fn column_iter_to_arrays<'a, I: 'a>(
 where
     I: Iterator<Item = Result<(u64, Vec<u8>)>> + PageIterator + Send + Sync,
 {
+    // the first leaf column is the type ids, followed by the leaf columns of each child
+    if let DataType::Union(fields, _, _) = field.data_type().to_logical_type() {
+        let types = readers.remove(0);
+        let mut leaves = leaves[1..].to_vec();
+        let children = fields
+            .iter()
+            .map(|f| {
+                let n = n_columns(&f.data_type);
+                let readers = readers.drain(..n).collect();
+                let leaves = leaves.drain(..n).collect();
+                column_iter_to_arrays(readers, leaves, f.clone(), !is_primitive(&f.data_type))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let iter = UnionIter::new(types, children, field.data_type().clone());
+        return Ok(DynIter::new(iter));
+    }
     if is_nested {
         let iter = deserialize_nested(readers, leaves, field, vec![])?;
         let nested_iter = NestedIter::new(iter);
@@ -251,3 +332,63 @@ where
         deserialize_simple(readers.pop().unwrap(), field)
     }
 }
+
+/// The state of a column read in batches of rows, the arrays read from the pages
+/// are buffered until there are enough rows for a batch.
+pub(crate) struct ColumnState<'a> {
+    iter: ArrayIter<'a>,
+    buffered: VecDeque<Box<dyn Array>>,
+    buffered_len: usize,
+}
+
+impl<'a> ColumnState<'a> {
+    pub(crate) fn new(iter: ArrayIter<'a>) -> Self {
+        Self {
+            iter,
+            buffered: VecDeque::new(),
+            buffered_len: 0,
+        }
+    }
+
+    /// Returns the next `batch_size` rows, or less at the end of the column.
+    pub(crate) fn next_batch(&mut self, batch_size: usize) -> Result<Option<Box<dyn Array>>> {
+        while self.buffered_len < batch_size {
+            match self.iter.next() {
+                Some(array) => {
+                    let array = array?;
+                    self.buffered_len += array.len();
+                    self.buffered.push_back(array);
+                }
+                None => break,
+            }
+        }
+        if self.buffered_len == 0 {
+            return Ok(None);
+        }
+
+        let mut arrays = Vec::with_capacity(self.buffered.len());
+        let mut len = 0;
+        while len < batch_size {
+            let array = match self.buffered.pop_front() {
+                Some(array) => array,
+                None => break,
+            };
+            let remaining = batch_size - len;
+            if array.len() > remaining {
+                self.buffered
+                    .push_front(array.sliced(remaining, array.len() - remaining));
+                arrays.push(array.sliced(0, remaining));
+            } else {
+                arrays.push(array);
+            }
+            len += arrays.last().unwrap().len();
+        }
+        self.buffered_len -= len;
+
+        if arrays.len() == 1 {
+            return Ok(arrays.pop());
+        }
+        let arrays = arrays.iter().map(|v| v.as_ref()).collect::<Vec<_>>();
+        concatenate_arrays(&arrays).map(Some)
+    }
+}
@@ -0,0 +1,428 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::sync::{Arc, Mutex};
+
+use crate::write::to_parquet_schema;
+use arrow::array::{new_empty_array, Array};
+use arrow::chunk::Chunk;
+use arrow::datatypes::{DataType, Field, IntegerType, Metadata, Schema};
+use arrow::error::{Error, Result};
+use arrow::io::parquet::read::ColumnDescriptor;
+
+use super::array::{concatenate_arrays, is_dictionary_value_type};
+use super::batch_read::batch_read_array;
+use super::deserialize::{column_iter_to_arrays, ArrayIter, ColumnState};
+use super::projection::{project_indices, project_paths, ProjectedField};
+use super::reader::{is_primitive, read_file_meta, NativeReader};
+use crate::{ColumnMeta, FileMeta};
+
+/// The default number of rows of each [`Chunk`] returned by [`NativeFileReader`].
+pub const DEFAULT_BATCH_SIZE: usize = 8192;
+
+const DEFAULT_BUFFER_SIZE: usize = 8192;
+
+/// A reader of a whole strawboat file, which reads the schema and the metas on creation
+/// and returns the (projected) columns as [`Chunk`]s of `batch_size` rows.
+pub struct NativeFileReader<R: Read + Seek + Send + 'static> {
+    reader: Arc<Mutex<R>>,
+    schema: Schema,
+    metas: Vec<ColumnMeta>,
+    key_value_metadata: Metadata,
+    leaves: Vec<ColumnDescriptor>,
+    projection: Option<Vec<ProjectedField>>,
+    dictionary_columns: Vec<String>,
+    batch_size: usize,
+    columns: Option<Vec<ColumnState<'static>>>,
+}
+
+impl<R: Read + Seek + Send + 'static> NativeFileReader<R> {
+    /// Creates a new [`NativeFileReader`] by reading the footer of the file.
+    pub fn try_new(mut reader: R) -> Result<Self> {
+        let FileMeta {
+            schema,
+            metas,
+            key_value_metadata,
+        } = read_file_meta(&mut reader)?;
+        let leaves = to_parquet_schema(&schema)?.columns().to_vec();
+        if leaves.len() != metas.len() {
+            return Err(Error::OutOfSpec(format!(
+                "The schema has {} leaf columns, but the file has {} column metas",
+                leaves.len(),
+                metas.len()
+            )));
+        }
+
+        Ok(Self {
+            reader: Arc::new(Mutex::new(reader)),
+            schema,
+            metas,
+            key_value_metadata,
+            leaves,
+            projection: None,
+            dictionary_columns: vec![],
+            batch_size: DEFAULT_BATCH_SIZE,
+            columns: None,
+        })
+    }
+
+    /// Only reads the fields of the given indices, in the given order.
+    pub fn with_projection(mut self, projection: Vec<usize>) -> Result<Self> {
+        self.projection = Some(project_indices(&self.schema, &projection)?);
+        Ok(self)
+    }
+
+    /// Only reads the leaf columns under the given field paths, such as `a.b.c`,
+    /// see [`project_paths`] for details.
+    pub fn with_projection_paths<S: AsRef<str>>(mut self, paths: &[S]) -> Result<Self> {
+        self.projection = Some(project_paths(&self.schema, paths)?);
+        Ok(self)
+    }
+
+    /// Reads the given top level columns as [`DictionaryArray`]s with `u32` keys,
+    /// the columns must be of integer, floating point or binary types.
+    /// The columns of dictionary types are always read as dictionaries.
+    ///
+    /// The keys and values of `Dict` pages are used without decoding the values,
+    /// the pages of other compressions are decoded and dictionary encoded.
+    ///
+    /// [`DictionaryArray`]: arrow::array::DictionaryArray
+    pub fn with_dictionary_columns<S: AsRef<str>>(mut self, columns: &[S]) -> Result<Self> {
+        for column in columns {
+            let column = column.as_ref();
+            let field = self
+                .schema
+                .fields
+                .iter()
+                .find(|f| f.name == column)
+                .ok_or_else(|| Error::InvalidArgumentError(format!("Unknown field {column}")))?;
+            if !matches!(field.data_type(), DataType::Dictionary(..))
+                && !is_dictionary_value_type(field.data_type())
+            {
+                return Err(Error::InvalidArgumentError(format!(
+                    "Can't read field {column} of type {:?} as a dictionary",
+                    field.data_type()
+                )));
+            }
+        }
+        self.dictionary_columns = columns.iter().map(|c| c.as_ref().to_string()).collect();
+        Ok(self)
+    }
+
+    /// Sets the maximum number of rows of each returned [`Chunk`].
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Returns the schema of the file.
+    pub fn file_schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    /// Returns the metas of all the leaf columns of the file.
+    pub fn metas(&self) -> &[ColumnMeta] {
+        &self.metas
+    }
+
+    /// Returns the application metadata of the file.
+    pub fn key_value_metadata(&self) -> &Metadata {
+        &self.key_value_metadata
+    }
+
+    /// Returns the schema of the returned [`Chunk`]s, after the projection.
+    pub fn schema(&self) -> Schema {
+        let fields = self
+            .projected_fields()
+            .into_iter()
+            .map(|projected| projected.field)
+            .collect::<Vec<_>>();
+        Schema::from(fields).with_metadata(self.schema.metadata.clone())
+    }
+
+    /// Reads all the pages of the projected columns at once into a single [`Chunk`].
+    pub fn read_all(self) -> Result<Chunk<Box<dyn Array>>> {
+        let projected_fields = self.projected_fields();
+        let mut arrays = Vec::with_capacity(projected_fields.len());
+        for ProjectedField {
+            field,
+            leaf_indices,
+        } in projected_fields
+        {
+            let readers = leaf_indices
+                .iter()
+                .map(|i| self.column_reader(&self.metas[*i]))
+                .collect::<Vec<_>>();
+            let page_metas = leaf_indices
+                .iter()
+                .map(|i| self.metas[*i].pages.clone())
+                .collect();
+            let leaves = leaf_indices
+                .iter()
+                .map(|i| self.leaves[*i].clone())
+                .collect();
+            let is_nested = !is_primitive(field.data_type());
+            arrays.push(batch_read_array(
+                readers, leaves, field, is_nested, page_metas,
+            )?);
+        }
+        Chunk::try_new(arrays)
+    }
+
+    /// Reads the rows `[start, end)` of the projected columns.
+    ///
+    /// The pages of primitive columns are located by the cumulative number of values
+    /// in their [`PageMeta`]s, only the pages covering the range are read.
+    /// The number of rows of the pages of nested columns is unknown before decoding,
+    /// so they are decoded from the first page until the `end` row.
+    ///
+    /// [`PageMeta`]: crate::PageMeta
+    pub fn read_range(&self, start: usize, end: usize) -> Result<Chunk<Box<dyn Array>>> {
+        if start > end {
+            return Err(Error::InvalidArgumentError(format!(
+                "Invalid row range [{start}, {end})"
+            )));
+        }
+        let projected_fields = self.projected_fields();
+        let mut arrays = Vec::with_capacity(projected_fields.len());
+        for ProjectedField {
+            field,
+            leaf_indices,
+        } in projected_fields
+        {
+            if start == end {
+                arrays.push(new_empty_array(field.data_type().clone()));
+                continue;
+            }
+            let is_nested = !is_primitive(field.data_type());
+            let (metas, offset) = if is_nested {
+                let metas = leaf_indices.iter().map(|i| self.metas[*i].clone());
+                (metas.collect(), start)
+            } else {
+                let meta = &self.metas[leaf_indices[0]];
+                match page_range(meta, start as u64, end as u64) {
+                    Some((start_page, end_page, offset)) => {
+                        (vec![meta.slice(start_page, end_page)], offset as usize)
+                    }
+                    None => (vec![], 0),
+                }
+            };
+            let leaves = leaf_indices
+                .iter()
+                .map(|i| self.leaves[*i].clone())
+                .collect();
+
+            let length = end - start;
+            let mut read = vec![];
+            let mut read_len = 0;
+            if !metas.is_empty() {
+                let iter = self.array_iter(field.clone(), &metas, leaves)?;
+                for array in iter {
+                    let array = array?;
+                    read_len += array.len();
+                    read.push(array);
+                    if read_len >= offset + length {
+                        break;
+                    }
+                }
+            }
+            if read_len < offset + length {
+                return Err(Error::InvalidArgumentError(format!(
+                    "Row range [{start}, {end}) is out of bounds of column {}",
+                    field.name
+                )));
+            }
+
+            let array = match read.len() {
+                1 => read.pop().unwrap(),
+                _ => {
+                    let read = read.iter().map(|v| v.as_ref()).collect::<Vec<_>>();
+                    concatenate_arrays(&read)?
+                }
+            };
+            arrays.push(array.sliced(offset, length));
+        }
+        Chunk::try_new(arrays)
+    }
+
+    fn projected_fields(&self) -> Vec<ProjectedField> {
+        let mut projected_fields = match &self.projection {
+            Some(projection) => projection.clone(),
+            None => {
+                let indices = (0..self.schema.fields.len()).collect::<Vec<_>>();
+                project_indices(&self.schema, &indices).unwrap()
+            }
+        };
+        for projected in projected_fields.iter_mut() {
+            if self.dictionary_columns.contains(&projected.field.name)
+                && !matches!(projected.field.data_type, DataType::Dictionary(..))
+            {
+                projected.field.data_type = DataType::Dictionary(
+                    IntegerType::UInt32,
+                    Box::new(projected.field.data_type.clone()),
+                    false,
+                );
+            }
+        }
+        projected_fields
+    }
+
+    fn column_reader(&self, meta: &ColumnMeta) -> BufReader<ColumnReader<R>> {
+        let reader = ColumnReader {
+            reader: self.reader.clone(),
+            position: meta.offset,
+        };
+        let buffer_size = meta.total_len().min(DEFAULT_BUFFER_SIZE as u64) as usize;
+        BufReader::with_capacity(buffer_size.max(1), reader)
+    }
+
+    fn array_iter(
+        &self,
+        field: Field,
+        metas: &[ColumnMeta],
+        leaves: Vec<ColumnDescriptor>,
+    ) -> Result<ArrayIter<'static>> {
+        let native_readers = metas
+            .iter()
+            .zip(leaves.iter())
+            .map(|(meta, leaf)| {
+                NativeReader::new(self.column_reader(meta), meta.pages.clone(), vec![])
+                    .with_column(leaf.path_in_schema.join("."))
+            })
+            .collect();
+        let is_nested = !is_primitive(field.data_type());
+        column_iter_to_arrays(native_readers, leaves, field, is_nested)
+    }
+
+    fn init_columns(&self) -> Result<Vec<ColumnState<'static>>> {
+        let projected_fields = self.projected_fields();
+        let mut columns = Vec::with_capacity(projected_fields.len());
+        for ProjectedField {
+            field,
+            leaf_indices,
+        } in projected_fields
+        {
+            let metas = leaf_indices
+                .iter()
+                .map(|i| self.metas[*i].clone())
+                .collect::<Vec<_>>();
+            let leaves = leaf_indices
+                .iter()
+                .map(|i| self.leaves[*i].clone())
+                .collect();
+
+            let iter = self.array_iter(field, &metas, leaves)?;
+            columns.push(ColumnState::new(iter));
+        }
+        Ok(columns)
+    }
+}
+
+impl<R: Read + Seek + Send + 'static> Iterator for NativeFileReader<R> {
+    type Item = Result<Chunk<Box<dyn Array>>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.columns.is_none() {
+            match self.init_columns() {
+                Ok(columns) => self.columns = Some(columns),
+                Err(err) => return Some(Err(err)),
+            }
+        }
+        let batch_size = self.batch_size;
+        let columns = self.columns.as_mut().unwrap();
+
+        let mut arrays = Vec::with_capacity(columns.len());
+        for column in columns.iter_mut() {
+            match column.next_batch(batch_size) {
+                Ok(array) => arrays.push(array),
+                Err(err) => return Some(Err(err)),
+            }
+        }
+
+        let arrays = arrays.into_iter().collect::<Option<Vec<_>>>()?;
+        if arrays.is_empty() {
+            return None;
+        }
+        Some(Chunk::try_new(arrays))
+    }
+}
+
+// Returns the pages `[start_page, end_page)` covering the rows `[start, end)` of a primitive
+// column, and the offset of `start` in the first page.
+fn page_range(meta: &ColumnMeta, start: u64, end: u64) -> Option<(usize, usize, u64)> {
+    let mut page_start = 0;
+    let mut range: Option<(usize, usize, u64)> = None;
+    for (i, page) in meta.pages.iter().enumerate() {
+        let page_end = page_start + page.num_values;
+        if page_end > start && page_start < end {
+            match range.as_mut() {
+                Some(range) => range.1 = i + 1,
+                None => range = Some((i, i + 1, start - page_start)),
+            }
+        }
+        if page_end >= end {
+            break;
+        }
+        page_start = page_end;
+    }
+    range
+}
+
+/// Reads a column of the file from its own position,
+/// so that the columns can share the same underlying reader.
+struct ColumnReader<R> {
+    reader: Arc<Mutex<R>>,
+    position: u64,
+}
+
+impl<R: Read + Seek> Read for ColumnReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let mut reader = self
+            .reader
+            .lock()
+            .map_err(|_| std::io::Error::new(std::io::ErrorKind::Other, "poisoned lock"))?;
+        reader.seek(SeekFrom::Start(self.position))?;
+        let n = reader.read(buf)?;
+        self.position += n as u64;
+        Ok(n)
+    }
+}
+
+impl<R: Read + Seek> Seek for ColumnReader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        self.position = match pos {
+            SeekFrom::Start(offset) => offset,
+            SeekFrom::Current(offset) => {
+                self.position.checked_add_signed(offset).ok_or_else(|| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        "invalid seek to a negative or overflowing position",
+                    )
+                })?
+            }
+            SeekFrom::End(_) => {
+                let mut reader = self
+                    .reader
+                    .lock()
+                    .map_err(|_| std::io::Error::new(std::io::ErrorKind::Other, "poisoned lock"))?;
+                reader.seek(pos)?
+            }
+        };
+        Ok(self.position)
+    }
+}
@@ -18,7 +18,11 @@
 mod array;
 pub mod batch_read;
 pub mod deserialize;
+pub mod file_reader;
+pub mod projection;
+pub mod pruning;
 pub use deserialize::{column_iter_to_arrays, ArrayIter};
+pub use file_reader::NativeFileReader;
 pub(crate) mod read_basic;
 use std::io::BufReader;
 pub mod reader;
@@ -54,4 +58,10 @@ impl<B: NativeReadBuf + ?Sized> NativeReadBuf for Box<B> {
 
 pub trait PageIterator {
     fn swap_buffer(&mut self, buffer: &mut Vec<u8>);
+
+    /// Returns the offset and the bytes of the column dictionary of the last returned page,
+    /// `None` if the page has no dictionary.
+    fn dictionary(&self) -> Option<(u64, &[u8])> {
+        None
+    }
 }
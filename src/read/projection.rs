@@ -0,0 +1,255 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::error::{Error, Result};
+
+use crate::variant::{is_variant, variant_path_type};
+use crate::write::n_columns;
+
+/// A top level field to read, with the leaf columns it is read from.
+///
+/// The `field` is pruned to the projected children, the `leaf_indices` are the
+/// indices of its leaf columns in the file, i.e. the indices of the [`ColumnMeta`]s.
+///
+/// [`ColumnMeta`]: crate::ColumnMeta
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectedField {
+    pub field: Field,
+    pub leaf_indices: Vec<usize>,
+}
+
+/// Projects the top level fields of the given indices, in the given order.
+pub fn project_indices(schema: &Schema, indices: &[usize]) -> Result<Vec<ProjectedField>> {
+    let leaf_starts = leaf_starts(schema);
+    indices
+        .iter()
+        .map(|index| {
+            let field = schema.fields.get(*index).ok_or_else(|| {
+                Error::InvalidArgumentError(format!(
+                    "Projection index {index} is out of bounds, the schema has {} fields",
+                    schema.fields.len()
+                ))
+            })?;
+            let start = leaf_starts[*index];
+            Ok(ProjectedField {
+                field: field.clone(),
+                leaf_indices: (start..start + n_columns(&field.data_type)).collect(),
+            })
+        })
+        .collect()
+}
+
+/// Projects the fields by paths such as `a.b.c` or `list_col.item.x`, only the leaf
+/// columns under the paths are selected.
+///
+/// The path components are the names of the fields, including the names of the inner fields
+/// of lists. Structs are pruned to the selected children in the order of the schema,
+/// the top level fields are returned in the order of their first appearance in `paths`.
+/// Extension types are pruned by their logical types, the children of maps and unions
+/// can't be projected.
+/// A single path inside a variant field selects the values at the path, see
+/// [`variant_path_type`]. The leaf column of the variant is still read whole, see
+/// [`crate::variant`].
+pub fn project_paths<S: AsRef<str>>(schema: &Schema, paths: &[S]) -> Result<Vec<ProjectedField>> {
+    let leaf_starts = leaf_starts(schema);
+
+    // the paths under each top level field
+    let mut selected: Vec<(usize, Vec<Vec<&str>>)> = vec![];
+    for path in paths {
+        let mut components = path.as_ref().split('.');
+        let name = components.next().unwrap_or_default();
+        let index = schema
+            .fields
+            .iter()
+            .position(|f| f.name == name)
+            .ok_or_else(|| Error::InvalidArgumentError(format!("Unknown field {name}")))?;
+        let rest = components.collect::<Vec<_>>();
+        match selected.iter_mut().find(|(i, _)| *i == index) {
+            Some((_, paths)) => paths.push(rest),
+            None => selected.push((index, vec![rest])),
+        }
+    }
+
+    selected
+        .into_iter()
+        .map(|(index, paths)| {
+            let mut leaf_indices = vec![];
+            let field = prune_field(
+                &schema.fields[index],
+                leaf_starts[index],
+                &paths,
+                &mut leaf_indices,
+            )?;
+            Ok(ProjectedField {
+                field,
+                leaf_indices,
+            })
+        })
+        .collect()
+}
+
+fn leaf_starts(schema: &Schema) -> Vec<usize> {
+    schema
+        .fields
+        .iter()
+        .scan(0, |start, field| {
+            let leaf_start = *start;
+            *start += n_columns(&field.data_type);
+            Some(leaf_start)
+        })
+        .collect()
+}
+
+fn prune_field(
+    field: &Field,
+    leaf_start: usize,
+    paths: &[Vec<&str>],
+    leaf_indices: &mut Vec<usize>,
+) -> Result<Field> {
+    // an empty path selects the whole field
+    if paths.iter().any(|path| path.is_empty()) {
+        leaf_indices.extend(leaf_start..leaf_start + n_columns(&field.data_type));
+        return Ok(field.clone());
+    }
+
+    // the paths of a variant are read from its values, the field keeps its nullability
+    // as the validity of the pages is read with it
+    if is_variant(field.data_type()) {
+        if paths.len() > 1 {
+            return Err(Error::InvalidArgumentError(format!(
+                "Can't project more than one path of variant field {}",
+                field.name
+            )));
+        }
+        leaf_indices.push(leaf_start);
+        return Ok(Field::new(
+            field.name.clone(),
+            variant_path_type(field.data_type(), &paths[0]),
+            field.is_nullable,
+        )
+        .with_metadata(field.metadata.clone()));
+    }
+
+    // extension types are pruned by their logical types and keep their extensions
+    let data_type = match field.data_type().to_logical_type() {
+        DataType::Struct(children) => {
+            let mut child_start = leaf_start;
+            let mut pruned = vec![];
+            for child in children {
+                let child_paths = child_paths(paths, &child.name);
+                if !child_paths.is_empty() {
+                    pruned.push(prune_field(child, child_start, &child_paths, leaf_indices)?);
+                }
+                child_start += n_columns(&child.data_type);
+            }
+            if let Some(path) = paths
+                .iter()
+                .find(|path| children.iter().all(|child| child.name != path[0]))
+            {
+                return Err(unknown_child(field, path[0]));
+            }
+            DataType::Struct(pruned)
+        }
+        DataType::List(inner) => DataType::List(Box::new(prune_inner(
+            field,
+            inner,
+            leaf_start,
+            paths,
+            leaf_indices,
+        )?)),
+        DataType::LargeList(inner) => DataType::LargeList(Box::new(prune_inner(
+            field,
+            inner,
+            leaf_start,
+            paths,
+            leaf_indices,
+        )?)),
+        DataType::FixedSizeList(inner, size) => DataType::FixedSizeList(
+            Box::new(prune_inner(field, inner, leaf_start, paths, leaf_indices)?),
+            *size,
+        ),
+        DataType::Map(inner, _) => {
+            if paths.iter().all(|path| path == &[inner.name.as_str()]) {
+                leaf_indices.extend(leaf_start..leaf_start + n_columns(&field.data_type));
+                return Ok(field.clone());
+            }
+            return Err(Error::InvalidArgumentError(format!(
+                "Can't project the children of map field {}",
+                field.name
+            )));
+        }
+        DataType::Union(_, _, _) => {
+            return Err(Error::InvalidArgumentError(format!(
+                "Can't project the children of union field {}",
+                field.name
+            )));
+        }
+        _ => return Err(unknown_child(field, paths[0][0])),
+    };
+    Ok(Field::new(
+        field.name.clone(),
+        with_logical_type(field.data_type(), data_type),
+        field.is_nullable,
+    )
+    .with_metadata(field.metadata.clone()))
+}
+
+fn with_logical_type(data_type: &DataType, logical_type: DataType) -> DataType {
+    match data_type {
+        DataType::Extension(name, inner, metadata) => DataType::Extension(
+            name.clone(),
+            Box::new(with_logical_type(inner, logical_type)),
+            metadata.clone(),
+        ),
+        _ => logical_type,
+    }
+}
+
+fn prune_inner(
+    field: &Field,
+    inner: &Field,
+    leaf_start: usize,
+    paths: &[Vec<&str>],
+    leaf_indices: &mut Vec<usize>,
+) -> Result<Field> {
+    if let Some(path) = paths.iter().find(|path| path[0] != inner.name) {
+        return Err(unknown_child(field, path[0]));
+    }
+    prune_field(
+        inner,
+        leaf_start,
+        &child_paths(paths, &inner.name),
+        leaf_indices,
+    )
+}
+
+fn child_paths<'a>(paths: &[Vec<&'a str>], name: &str) -> Vec<Vec<&'a str>> {
+    paths
+        .iter()
+        .filter(|path| path[0] == name)
+        .map(|path| path[1..].to_vec())
+        .collect()
+}
+
+fn unknown_child(field: &Field, name: &str) -> Error {
+    Error::InvalidArgumentError(format!(
+        "Field {} of type {:?} has no child {name}",
+        field.name,
+        field.data_type()
+    ))
+}
@@ -0,0 +1,270 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::cmp::Ordering;
+
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::error::{Error, Result};
+use arrow::scalar::Scalar;
+
+use super::reader::is_primitive;
+use crate::statistics::{compare_value, encode_scalar, is_nan_value};
+use crate::write::n_columns;
+use crate::{ColumnMeta, PageMeta};
+
+/// Comparison operators of a [`Predicate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CompareOp {
+    Eq,
+    NotEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+}
+
+/// A simple predicate expression over the top level columns, such as `a > 10 AND b = 'x'`.
+#[derive(Debug, Clone)]
+pub enum Predicate {
+    Compare {
+        column: String,
+        op: CompareOp,
+        value: Box<dyn Scalar>,
+    },
+    IsNull(String),
+    IsNotNull(String),
+    And(Box<Predicate>, Box<Predicate>),
+    Or(Box<Predicate>, Box<Predicate>),
+}
+
+impl Predicate {
+    pub fn compare(column: impl Into<String>, op: CompareOp, value: Box<dyn Scalar>) -> Self {
+        Self::Compare {
+            column: column.into(),
+            op,
+            value,
+        }
+    }
+
+    pub fn and(self, other: Predicate) -> Self {
+        Self::And(Box::new(self), Box::new(other))
+    }
+
+    pub fn or(self, other: Predicate) -> Self {
+        Self::Or(Box::new(self), Box::new(other))
+    }
+}
+
+// sorted and non-overlapping row ranges [start, end), `None` means all the rows.
+type RowRanges = Option<Vec<(u64, u64)>>;
+
+/// Returns whether each page of each [`ColumnMeta`] can be skipped because none of its
+/// rows can satisfy the `predicate`, the pages can then be skipped by [`NativeReader`].
+///
+/// Pages are aligned by rows, a page is skipped only if all its rows are filtered out.
+/// Pages of nested columns are never skipped as their row ranges are unknown.
+///
+/// [`NativeReader`]: super::reader::NativeReader
+pub fn prune_pages(
+    schema: &Schema,
+    metas: &[ColumnMeta],
+    predicate: &Predicate,
+) -> Result<Vec<Vec<bool>>> {
+    let ranges = eval_predicate(schema, metas, predicate)?;
+
+    let mut skips = Vec::with_capacity(metas.len());
+    let mut leaf_index = 0;
+    for field in schema.fields.iter() {
+        let n = n_columns(&field.data_type);
+        for meta in &metas[leaf_index..leaf_index + n] {
+            let skip = match (&ranges, is_primitive(field.data_type())) {
+                (Some(ranges), true) => page_ranges(&meta.pages)
+                    .map(|(start, end)| !ranges.iter().any(|r| r.0 < end && start < r.1))
+                    .collect(),
+                _ => vec![false; meta.pages.len()],
+            };
+            skips.push(skip);
+        }
+        leaf_index += n;
+    }
+    Ok(skips)
+}
+
+fn eval_predicate(
+    schema: &Schema,
+    metas: &[ColumnMeta],
+    predicate: &Predicate,
+) -> Result<RowRanges> {
+    match predicate {
+        Predicate::And(left, right) => {
+            let left = eval_predicate(schema, metas, left)?;
+            let right = eval_predicate(schema, metas, right)?;
+            Ok(intersect(left, right))
+        }
+        Predicate::Or(left, right) => {
+            let left = eval_predicate(schema, metas, left)?;
+            let right = eval_predicate(schema, metas, right)?;
+            Ok(union(left, right))
+        }
+        Predicate::Compare { column, op, value } => {
+            let (_, field) = find_field(schema, column)?;
+            if field.data_type().to_physical_type() != value.data_type().to_physical_type() {
+                return Err(Error::InvalidArgumentError(format!(
+                    "Can't compare column {column} of type {:?} with value of type {:?}",
+                    field.data_type(),
+                    value.data_type()
+                )));
+            }
+            let value = encode_scalar(value.as_ref());
+            eval_column(schema, metas, column, |data_type, page| {
+                page_may_match(data_type, page, *op, value.as_deref())
+            })
+        }
+        Predicate::IsNull(column) => eval_column(schema, metas, column, |_, page| {
+            page.statistics.null_count > 0
+        }),
+        Predicate::IsNotNull(column) => eval_column(schema, metas, column, |_, page| {
+            page.statistics.null_count < page.num_values
+        }),
+    }
+}
+
+fn eval_column<F>(schema: &Schema, metas: &[ColumnMeta], column: &str, f: F) -> Result<RowRanges>
+where
+    F: Fn(&DataType, &PageMeta) -> bool,
+{
+    let (index, field) = find_field(schema, column)?;
+    if !is_primitive(field.data_type()) {
+        return Ok(None);
+    }
+
+    let leaf_index = schema.fields[..index]
+        .iter()
+        .map(|f| n_columns(&f.data_type))
+        .sum::<usize>();
+    let pages = &metas[leaf_index].pages;
+
+    let mut ranges: Vec<(u64, u64)> = vec![];
+    for ((start, end), page) in page_ranges(pages).zip(pages.iter()) {
+        if !f(field.data_type(), page) {
+            continue;
+        }
+        match ranges.last_mut() {
+            Some(last) if last.1 == start => last.1 = end,
+            _ => ranges.push((start, end)),
+        }
+    }
+    Ok(Some(ranges))
+}
+
+fn find_field<'a>(schema: &'a Schema, column: &str) -> Result<(usize, &'a Field)> {
+    schema
+        .fields
+        .iter()
+        .enumerate()
+        .find(|(_, field)| field.name == column)
+        .ok_or_else(|| Error::InvalidArgumentError(format!("Unknown column {column}")))
+}
+
+fn page_may_match(
+    data_type: &DataType,
+    page: &PageMeta,
+    op: CompareOp,
+    value: Option<&[u8]>,
+) -> bool {
+    let value = match value {
+        Some(value) => value,
+        // compare with null is never true
+        None => return false,
+    };
+    let statistics = &page.statistics;
+    let (min, max) = match (&statistics.min_value, &statistics.max_value) {
+        (Some(min), Some(max)) => (min, max),
+        // all the values are null
+        _ if statistics.null_count == page.num_values => return false,
+        _ => return true,
+    };
+    // NaN is unequal to any value and is the largest value of the statistics
+    if is_nan_value(data_type, max)
+        && matches!(op, CompareOp::NotEq | CompareOp::Gt | CompareOp::GtEq)
+    {
+        return true;
+    }
+    let (min_cmp, max_cmp) = match (
+        compare_value(data_type, min, value),
+        compare_value(data_type, max, value),
+    ) {
+        (Some(min_cmp), Some(max_cmp)) => (min_cmp, max_cmp),
+        _ => return true,
+    };
+
+    match op {
+        CompareOp::Eq => min_cmp != Ordering::Greater && max_cmp != Ordering::Less,
+        CompareOp::NotEq => !(min_cmp == Ordering::Equal && max_cmp == Ordering::Equal),
+        CompareOp::Lt => min_cmp == Ordering::Less,
+        CompareOp::LtEq => min_cmp != Ordering::Greater,
+        CompareOp::Gt => max_cmp == Ordering::Greater,
+        CompareOp::GtEq => max_cmp != Ordering::Less,
+    }
+}
+
+fn page_ranges(pages: &[PageMeta]) -> impl Iterator<Item = (u64, u64)> + '_ {
+    pages.iter().scan(0, |start, page| {
+        let range = (*start, *start + page.num_values);
+        *start += page.num_values;
+        Some(range)
+    })
+}
+
+fn intersect(left: RowRanges, right: RowRanges) -> RowRanges {
+    let (left, right) = match (left, right) {
+        (None, other) | (other, None) => return other,
+        (Some(left), Some(right)) => (left, right),
+    };
+    let mut result = vec![];
+    let (mut i, mut j) = (0, 0);
+    while i < left.len() && j < right.len() {
+        let start = left[i].0.max(right[j].0);
+        let end = left[i].1.min(right[j].1);
+        if start < end {
+            result.push((start, end));
+        }
+        if left[i].1 < right[j].1 {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    Some(result)
+}
+
+fn union(left: RowRanges, right: RowRanges) -> RowRanges {
+    let (mut left, right) = match (left, right) {
+        (None, _) | (_, None) => return None,
+        (Some(left), Some(right)) => (left, right),
+    };
+    left.extend(right);
+    left.sort_unstable();
+    let mut result: Vec<(u64, u64)> = Vec::with_capacity(left.len());
+    for (start, end) in left {
+        match result.last_mut() {
+            Some(last) if last.1 >= start => last.1 = last.1.max(end),
+            _ => result.push((start, end)),
+        }
+    }
+    Some(result)
+}
@@ -19,6 +19,7 @@ use std::convert::TryInto;
 use std::io::Read;
 
 use super::NativeReadBuf;
+use crate::PageMeta;
 
 use arrow::{
     bitmap::{Bitmap, MutableBitmap},
@@ -26,7 +27,6 @@ use arrow::{
     io::parquet::read::{init_nested, InitNested, NestedState},
 };
 
-use futures::{AsyncRead, AsyncReadExt};
 use parquet2::{
     encoding::hybrid_rle::{BitmapIter, Decoder, HybridEncoded, HybridRleDecoder},
     metadata::ColumnDescriptor,
@@ -172,6 +172,28 @@ pub fn read_validity_nested<R: NativeReadBuf>(
     Ok((nested, validity))
 }
 
+/// Skips the bytes between `position` and the start of the page, pages of a column
+/// are not contiguous if the file is written by multiple chunks.
+pub fn skip_to_page<R: NativeReadBuf>(
+    reader: &mut R,
+    position: &mut u64,
+    page_meta: &PageMeta,
+) -> Result<()> {
+    skip_to(reader, position, page_meta.offset)?;
+    *position = page_meta.offset + page_meta.length;
+    Ok(())
+}
+
+/// Skips the bytes of the reader at `position` up to `offset`.
+pub fn skip_to<R: NativeReadBuf>(reader: &mut R, position: &mut u64, offset: u64) -> Result<()> {
+    if offset > *position {
+        let gap = offset - *position;
+        std::io::copy(&mut reader.take(gap), &mut std::io::sink())?;
+        *position = offset;
+    }
+    Ok(())
+}
+
 #[inline(always)]
 pub fn read_u32<R: Read>(r: &mut R, buf: &mut [u8]) -> Result<u32> {
     r.read_exact(buf)?;
@@ -193,8 +215,3 @@ pub fn read_u64<R: Read>(r: &mut R, buf: &mut [u8]) -> Result<u64> {
     r.read_exact(buf)?;
     Ok(u64::from_le_bytes(buf.try_into().unwrap()))
 }
-
-pub async fn read_u32_async<R: AsyncRead + Unpin>(r: &mut R, buf: &mut [u8]) -> Result<u32> {
-    r.read_exact(buf).await?;
-    Ok(u32::from_le_bytes(buf.try_into().unwrap()))
-}
@@ -17,18 +17,23 @@
 
 use std::io::{Read, Seek, SeekFrom};
 
-use arrow::datatypes::{DataType, PhysicalType, Schema};
-use arrow::error::Result;
+use arrow::datatypes::{DataType, Field, Metadata, PhysicalType, Schema};
+use arrow::error::{Error, Result};
 use arrow::io::ipc::read::deserialize_schema;
 
-use crate::{ColumnMeta, PageMeta};
+use crate::{
+    ColumnMeta, DictionaryMeta, FileMeta, PageMeta, Statistics, FORMAT_VERSION, STRAWBOAT_MAGIC,
+};
 
 use super::{
-    read_basic::{read_u32, read_u32_async, read_u64},
+    read_basic::{read_u32, read_u64},
     NativeReadBuf, PageIterator,
 };
 
-use futures::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt};
+use futures::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt, Stream};
+
+const EXTENSION_NAME: &str = "ARROW:extension:name";
+const EXTENSION_METADATA: &str = "ARROW:extension:metadata";
 
 const DEFAULT_FOOTER_SIZE: u64 = 64 * 1024;
 
@@ -52,20 +57,41 @@ pub struct NativeReader<R: NativeReadBuf> {
     page_reader: R,
     page_metas: Vec<PageMeta>,
     current_page: usize,
+    // offset of the `page_reader` in the file
+    position: u64,
     scratch: Vec<u8>,
+    // name of the column, used in errors
+    column: String,
+    // the last read dictionary of a column chunk and its offset,
+    // it's shared by the pages of the column chunk
+    dictionary: Option<(u64, Vec<u8>)>,
 }
 
 impl<R: NativeReadBuf> NativeReader<R> {
-    /// Creates a new [`NativeReader`]
+    /// Creates a new [`NativeReader`], the `page_reader` must be positioned at
+    /// the offset of the first page, or of its dictionary if it has one.
     pub fn new(page_reader: R, page_metas: Vec<PageMeta>, scratch: Vec<u8>) -> Self {
+        let position = page_metas
+            .first()
+            .map(|p| p.start_offset())
+            .unwrap_or_default();
         Self {
             page_reader,
             page_metas,
             current_page: 0,
+            position,
             scratch,
+            column: String::new(),
+            dictionary: None,
         }
     }
 
+    /// Sets the name of the column, it's used in the errors of the pages.
+    pub fn with_column(mut self, column: impl Into<String>) -> Self {
+        self.column = column.into();
+        self
+    }
+
     /// Check whether there is more data to read,
     /// returns true, if current page is not the last one, false otherwise
     pub fn has_next(&self) -> bool {
@@ -82,6 +108,11 @@ impl<R: NativeReadBuf> PageIterator for NativeReader<R> {
     fn swap_buffer(&mut self, scratch: &mut Vec<u8>) {
         std::mem::swap(&mut self.scratch, scratch)
     }
+
+    fn dictionary(&self) -> Option<(u64, &[u8])> {
+        let page_meta = self.page_metas.get(self.current_page.checked_sub(1)?)?;
+        page_dictionary(page_meta, &self.dictionary)
+    }
 }
 
 impl<R: NativeReadBuf + std::io::Seek> Iterator for NativeReader<R> {
@@ -89,29 +120,11 @@ impl<R: NativeReadBuf + std::io::Seek> Iterator for NativeReader<R> {
 
     /// Reads the next nth page of data, skipping the intermediate pages
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
-        let mut i = 0;
-        let mut length = 0;
-        while i < n {
-            if self.current_page == self.page_metas.len() {
-                break;
-            }
-            let page_meta = &self.page_metas[self.current_page];
-            length += page_meta.length;
-            i += 1;
-            self.current_page += 1;
-        }
-        if i < n {
+        if self.current_page + n >= self.page_metas.len() {
+            self.current_page = self.page_metas.len();
             return None;
         }
-        if length > 0 {
-            if let Some(err) = self
-                .page_reader
-                .seek(SeekFrom::Current(length as i64))
-                .err()
-            {
-                return Some(Result::Err(err.into()));
-            }
-        }
+        self.current_page += n;
         self.next()
     }
 
@@ -120,12 +133,24 @@ impl<R: NativeReadBuf + std::io::Seek> Iterator for NativeReader<R> {
         if self.current_page == self.page_metas.len() {
             return None;
         }
+        if let Some(err) = self.read_dictionary().err() {
+            return Some(Result::Err(err));
+        }
+        if let Some(err) = self.seek_to_current_page().err() {
+            return Some(Result::Err(err));
+        }
         let mut buffer = std::mem::take(&mut self.scratch);
         let page_meta = &self.page_metas[self.current_page];
         buffer.resize(page_meta.length as usize, 0);
         if let Some(err) = self.page_reader.read_exact(&mut buffer).err() {
             return Some(Result::Err(err.into()));
         }
+        if let Some(err) =
+            verify_checksum(page_meta, &buffer, &self.column, self.current_page).err()
+        {
+            return Some(Result::Err(err));
+        }
+        self.position += page_meta.length;
         self.current_page += 1;
         Some(Ok((page_meta.num_values, buffer)))
     }
@@ -137,12 +162,238 @@ impl<R: NativeReadBuf + std::io::Seek> NativeReader<R> {
         if self.current_page == self.page_metas.len() {
             return Ok(());
         }
+        self.current_page += 1;
+        Ok(())
+    }
+
+    /// Seeks the `page_reader` to the offset of the current page,
+    /// pages may be skipped or not contiguous.
+    fn seek_to_current_page(&mut self) -> Result<()> {
+        let offset = self.page_metas[self.current_page].offset;
+        self.seek_to(offset)
+    }
+
+    fn seek_to(&mut self, offset: u64) -> Result<()> {
+        if offset != self.position {
+            self.page_reader
+                .seek(SeekFrom::Current(offset as i64 - self.position as i64))?;
+            self.position = offset;
+        }
+        Ok(())
+    }
+
+    /// Reads the dictionary of the current page if it isn't the cached one.
+    fn read_dictionary(&mut self) -> Result<()> {
+        let dictionary = match &self.page_metas[self.current_page].dictionary {
+            Some(dictionary) => dictionary.clone(),
+            None => return Ok(()),
+        };
+        if matches!(&self.dictionary, Some((offset, _)) if *offset == dictionary.offset) {
+            return Ok(());
+        }
+        self.seek_to(dictionary.offset)?;
+        let mut buffer = vec![0u8; dictionary.length as usize];
+        self.page_reader.read_exact(&mut buffer)?;
+        self.position += dictionary.length;
+        verify_dictionary_checksum(&dictionary, &buffer, &self.column, self.current_page)?;
+        self.dictionary = Some((dictionary.offset, buffer));
+        Ok(())
+    }
+}
+
+/// The async counterpart of [`NativeReader`], it reads pages of a column
+/// from an [`AsyncRead`] + [`AsyncSeek`] source.
+#[derive(Debug)]
+pub struct AsyncNativeReader<R: AsyncRead + AsyncSeek + Send + Unpin> {
+    page_reader: R,
+    page_metas: Vec<PageMeta>,
+    current_page: usize,
+    // offset of the `page_reader` in the file
+    position: u64,
+    scratch: Vec<u8>,
+    // name of the column, used in errors
+    column: String,
+    // the last read dictionary of a column chunk and its offset,
+    // it's shared by the pages of the column chunk
+    dictionary: Option<(u64, Vec<u8>)>,
+}
+
+impl<R: AsyncRead + AsyncSeek + Send + Unpin> AsyncNativeReader<R> {
+    /// Creates a new [`AsyncNativeReader`], the `page_reader` must be positioned at
+    /// the offset of the first page, or of its dictionary if it has one.
+    pub fn new(page_reader: R, page_metas: Vec<PageMeta>, scratch: Vec<u8>) -> Self {
+        let position = page_metas
+            .first()
+            .map(|p| p.start_offset())
+            .unwrap_or_default();
+        Self {
+            page_reader,
+            page_metas,
+            current_page: 0,
+            position,
+            scratch,
+            column: String::new(),
+            dictionary: None,
+        }
+    }
+
+    /// Sets the name of the column, it's used in the errors of the pages.
+    pub fn with_column(mut self, column: impl Into<String>) -> Self {
+        self.column = column.into();
+        self
+    }
+
+    /// Check whether there is more data to read,
+    /// returns true, if current page is not the last one, false otherwise
+    pub fn has_next(&self) -> bool {
+        self.current_page < self.page_metas.len()
+    }
+
+    /// Returns current page number
+    pub fn current_page(&self) -> usize {
+        self.current_page
+    }
+
+    /// Skips the next page
+    pub fn skip_page(&mut self) {
+        if self.current_page < self.page_metas.len() {
+            self.current_page += 1;
+        }
+    }
+
+    /// Reads the next page of data, returns the number of values and the page buffer
+    pub async fn next_page(&mut self) -> Option<Result<(u64, Vec<u8>)>> {
+        if self.current_page == self.page_metas.len() {
+            return None;
+        }
+        if let Some(err) = self.read_dictionary().await.err() {
+            return Some(Result::Err(err));
+        }
+        let offset = self.page_metas[self.current_page].offset;
+        if let Some(err) = self.seek_to(offset).await.err() {
+            return Some(Result::Err(err));
+        }
         let page_meta = &self.page_metas[self.current_page];
-        self.page_reader
-            .seek(SeekFrom::Current(page_meta.length as i64))?;
+        let mut buffer = std::mem::take(&mut self.scratch);
+        buffer.resize(page_meta.length as usize, 0);
+        if let Some(err) = self.page_reader.read_exact(&mut buffer).await.err() {
+            return Some(Result::Err(err.into()));
+        }
+        if let Some(err) =
+            verify_checksum(page_meta, &buffer, &self.column, self.current_page).err()
+        {
+            return Some(Result::Err(err));
+        }
+        self.position += page_meta.length;
         self.current_page += 1;
+        Some(Ok((page_meta.num_values, buffer)))
+    }
+
+    async fn seek_to(&mut self, offset: u64) -> Result<()> {
+        if offset != self.position {
+            let gap = offset as i64 - self.position as i64;
+            self.page_reader.seek(SeekFrom::Current(gap)).await?;
+            self.position = offset;
+        }
         Ok(())
     }
+
+    /// Reads the dictionary of the current page if it isn't the cached one.
+    async fn read_dictionary(&mut self) -> Result<()> {
+        let dictionary = match &self.page_metas[self.current_page].dictionary {
+            Some(dictionary) => dictionary.clone(),
+            None => return Ok(()),
+        };
+        if matches!(&self.dictionary, Some((offset, _)) if *offset == dictionary.offset) {
+            return Ok(());
+        }
+        self.seek_to(dictionary.offset).await?;
+        let mut buffer = vec![0u8; dictionary.length as usize];
+        self.page_reader.read_exact(&mut buffer).await?;
+        self.position += dictionary.length;
+        verify_dictionary_checksum(&dictionary, &buffer, &self.column, self.current_page)?;
+        self.dictionary = Some((dictionary.offset, buffer));
+        Ok(())
+    }
+
+    /// Converts the reader into a [`Stream`] of pages
+    pub fn into_stream(self) -> impl Stream<Item = Result<(u64, Vec<u8>)>> + Send {
+        futures::stream::unfold(self, |mut reader| async move {
+            reader.next_page().await.map(|page| (page, reader))
+        })
+    }
+}
+
+impl<R: AsyncRead + AsyncSeek + Send + Unpin> PageIterator for AsyncNativeReader<R> {
+    fn swap_buffer(&mut self, scratch: &mut Vec<u8>) {
+        std::mem::swap(&mut self.scratch, scratch)
+    }
+
+    fn dictionary(&self) -> Option<(u64, &[u8])> {
+        let page_meta = self.page_metas.get(self.current_page.checked_sub(1)?)?;
+        page_dictionary(page_meta, &self.dictionary)
+    }
+}
+
+/// Verifies the CRC32C of a page if it has one.
+pub(crate) fn verify_checksum(
+    page_meta: &PageMeta,
+    page: &[u8],
+    column: &str,
+    page_index: usize,
+) -> Result<()> {
+    check_crc32c(page_meta.checksum, page, || {
+        format!("page {page_index}{}", column_context(column))
+    })
+}
+
+// the dictionary of the page if it's the last read one
+fn page_dictionary<'a>(
+    page_meta: &PageMeta,
+    dictionary: &'a Option<(u64, Vec<u8>)>,
+) -> Option<(u64, &'a [u8])> {
+    match (&page_meta.dictionary, dictionary) {
+        (Some(meta), Some((offset, bytes))) if meta.offset == *offset => {
+            Some((*offset, bytes.as_slice()))
+        }
+        _ => None,
+    }
+}
+
+/// Verifies the CRC32C of the dictionary of a page if it has one.
+pub(crate) fn verify_dictionary_checksum(
+    dictionary: &DictionaryMeta,
+    bytes: &[u8],
+    column: &str,
+    page_index: usize,
+) -> Result<()> {
+    check_crc32c(dictionary.checksum, bytes, || {
+        format!(
+            "the dictionary of page {page_index}{}",
+            column_context(column)
+        )
+    })
+}
+
+fn check_crc32c<F: FnOnce() -> String>(checksum: Option<u32>, bytes: &[u8], name: F) -> Result<()> {
+    if let Some(checksum) = checksum {
+        let actual = crc32c::crc32c(bytes);
+        if actual != checksum {
+            return Err(Error::OutOfSpec(format!(
+                "Checksum mismatch of {}: expected {checksum:#010x}, but got {actual:#010x}",
+                name()
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn column_context(column: &str) -> String {
+    if column.is_empty() {
+        String::new()
+    } else {
+        format!(" of column {column}")
+    }
 }
 
 fn deserialize_meta(buf: Vec<u8>) -> Result<Vec<ColumnMeta>> {
@@ -152,27 +403,308 @@ fn deserialize_meta(buf: Vec<u8>) -> Result<Vec<ColumnMeta>> {
     let mut metas = Vec::with_capacity(meta_len as usize);
     for _i in 0..meta_len {
         let offset = read_u64(&mut buf_reader, buf.as_mut_slice())?;
+        let statistics = read_statistics(&mut buf_reader)?;
         let page_num = read_u64(&mut buf_reader, buf.as_mut_slice())?;
         let mut pages = Vec::with_capacity(page_num as usize);
         for _p in 0..page_num {
+            let offset = read_u64(&mut buf_reader, buf.as_mut_slice())?;
             let length = read_u64(&mut buf_reader, buf.as_mut_slice())?;
             let num_values = read_u64(&mut buf_reader, buf.as_mut_slice())?;
+            let statistics = read_statistics(&mut buf_reader)?;
+            let checksum = read_checksum(&mut buf_reader)?;
+            let mut has_dictionary = [0u8; 1];
+            buf_reader.read_exact(&mut has_dictionary)?;
+            let dictionary = match has_dictionary[0] {
+                1 => Some(DictionaryMeta {
+                    offset: read_u64(&mut buf_reader, buf.as_mut_slice())?,
+                    length: read_u64(&mut buf_reader, buf.as_mut_slice())?,
+                    checksum: read_checksum(&mut buf_reader)?,
+                }),
+                _ => None,
+            };
 
-            pages.push(PageMeta { length, num_values });
+            pages.push(PageMeta {
+                offset,
+                length,
+                num_values,
+                statistics,
+                checksum,
+                dictionary,
+            });
         }
-        metas.push(ColumnMeta { offset, pages })
+        metas.push(ColumnMeta {
+            offset,
+            statistics,
+            pages,
+        })
     }
     Ok(metas)
 }
 
+fn read_checksum<R: Read>(reader: &mut R) -> Result<Option<u32>> {
+    let mut has_checksum = [0u8; 1];
+    reader.read_exact(&mut has_checksum)?;
+    match has_checksum[0] {
+        1 => Ok(Some(read_u32(reader, &mut [0u8; 4])?)),
+        _ => Ok(None),
+    }
+}
+
+fn read_statistics<R: Read>(reader: &mut R) -> Result<Statistics> {
+    let mut buf = vec![0u8; 8];
+    let null_count = read_u64(reader, buf.as_mut_slice())?;
+    let mut values = [None, None];
+    for value in values.iter_mut() {
+        let mut is_some = [0u8; 1];
+        reader.read_exact(&mut is_some)?;
+        if is_some[0] == 1 {
+            let len = read_u32(reader, &mut buf[..4])? as usize;
+            let mut bytes = vec![0u8; len];
+            reader.read_exact(&mut bytes)?;
+            *value = Some(bytes);
+        }
+    }
+    let [min_value, max_value] = values;
+    Ok(Statistics {
+        null_count,
+        min_value,
+        max_value,
+    })
+}
+
+// schema_size(4 bytes) + meta_size(4 bytes) + key_value_metadata size(4 bytes)
+// + format version(2 bytes) + magic(6 bytes)
+const FOOTER_TAIL_SIZE: usize = 20;
+// magic(6 bytes) + format version(2 bytes)
+const HEADER_SIZE: usize = 8;
+
+/// The sizes of the sections of the footer, the sections are written in the order of the fields.
+struct FooterSizes {
+    schema: usize,
+    meta: usize,
+    key_value_metadata: usize,
+    // the format version in the footer
+    version: u16,
+}
+
+impl FooterSizes {
+    /// The offset of the column metas from the end of the file.
+    fn meta_offset(&self) -> usize {
+        FOOTER_TAIL_SIZE + self.key_value_metadata + self.meta
+    }
+
+    /// The offset of the schema from the end of the file, i.e. the size of the footer.
+    fn schema_offset(&self) -> usize {
+        self.meta_offset() + self.schema
+    }
+}
+
+/// Validates the magic and the format version at the end of a file,
+/// returns the sizes of the sections of the footer.
+fn parse_footer_tail(tail: &[u8]) -> Result<FooterSizes> {
+    let version = check_magic_and_version(&tail[14..20], &tail[12..14], "footer")?;
+    let size = |i: usize| u32::from_le_bytes(tail[i..i + 4].try_into().unwrap()) as usize;
+    Ok(FooterSizes {
+        schema: size(0),
+        meta: size(4),
+        key_value_metadata: size(8),
+        version,
+    })
+}
+
+/// Validates the magic and the format version at the start of a file,
+/// the format version must match the one of the footer.
+fn parse_header(header: &[u8], footer_version: u16) -> Result<()> {
+    // header = magic(6 bytes) + format version(2 bytes)
+    let header_version = check_magic_and_version(&header[0..6], &header[6..8], "header")?;
+    if header_version != footer_version {
+        return Err(Error::OutOfSpec(format!(
+            "The format version {header_version} in the header of the file doesn't match \
+             the format version {footer_version} in its footer"
+        )));
+    }
+    Ok(())
+}
+
+fn check_file_size(len: u64) -> Result<()> {
+    if len < (FOOTER_TAIL_SIZE + HEADER_SIZE) as u64 {
+        return Err(Error::OutOfSpec(format!(
+            "The file is too small ({len} bytes) to be a strawboat file"
+        )));
+    }
+    Ok(())
+}
+
+fn check_magic_and_version(magic: &[u8], version: &[u8], location: &str) -> Result<u16> {
+    if magic != STRAWBOAT_MAGIC {
+        // files written before the format version start with the arrow magic
+        // and end with a continuation marker
+        if magic == b"ARROW2" || magic == [0xff, 0xff, 0, 0, 0, 0] {
+            return Err(Error::OutOfSpec(format!(
+                "The {location} of the file has no format version, the file was written by \
+                 an older version of strawboat and must be rewritten"
+            )));
+        }
+        return Err(Error::OutOfSpec(format!(
+            "Invalid magic {magic:?} in the {location} of the file, expected {STRAWBOAT_MAGIC:?}, \
+             it's not a strawboat file or it's corrupted"
+        )));
+    }
+    let version = u16::from_le_bytes(version.try_into().unwrap());
+    if version == 0 || version > FORMAT_VERSION {
+        return Err(Error::OutOfSpec(format!(
+            "Unsupported format version {version} in the {location} of the file, \
+             this version of strawboat supports format versions up to {FORMAT_VERSION}"
+        )));
+    }
+    Ok(version)
+}
+
+/// Validates the magic and the format version of both the header and the footer,
+/// returns the format version of the file.
+pub fn read_format_version<Reader: Read + Seek>(reader: &mut Reader) -> Result<u16> {
+    let sizes = read_footer_tail(reader)?;
+    read_header(reader, sizes.version)?;
+    Ok(sizes.version)
+}
+
+/// Reads the end of the file and validates its magic and format version,
+/// the header of the file is only validated when the file is opened by [`read_file_meta`].
+fn read_footer_tail<Reader: Read + Seek>(reader: &mut Reader) -> Result<FooterSizes> {
+    let len = reader.seek(SeekFrom::End(0))?;
+    check_file_size(len)?;
+    reader.seek(SeekFrom::End(-(FOOTER_TAIL_SIZE as i64)))?;
+    let mut tail = [0u8; FOOTER_TAIL_SIZE];
+    reader.read_exact(&mut tail)?;
+    parse_footer_tail(&tail)
+}
+
+async fn read_footer_tail_async<Reader: AsyncRead + AsyncSeek + Send + Unpin>(
+    reader: &mut Reader,
+) -> Result<FooterSizes> {
+    let len = reader.seek(SeekFrom::End(0)).await?;
+    check_file_size(len)?;
+    reader
+        .seek(SeekFrom::End(-(FOOTER_TAIL_SIZE as i64)))
+        .await?;
+    let mut tail = [0u8; FOOTER_TAIL_SIZE];
+    reader.read_exact(&mut tail).await?;
+    parse_footer_tail(&tail)
+}
+
+fn read_header<Reader: Read + Seek>(reader: &mut Reader, footer_version: u16) -> Result<()> {
+    reader.seek(SeekFrom::Start(0))?;
+    let mut header = [0u8; HEADER_SIZE];
+    reader.read_exact(&mut header)?;
+    parse_header(&header, footer_version)
+}
+
+async fn read_header_async<Reader: AsyncRead + AsyncSeek + Send + Unpin>(
+    reader: &mut Reader,
+    footer_version: u16,
+) -> Result<()> {
+    reader.seek(SeekFrom::Start(0)).await?;
+    let mut header = [0u8; HEADER_SIZE];
+    reader.read_exact(&mut header).await?;
+    parse_header(&header, footer_version)
+}
+
+fn deserialize_key_value_metadata(buf: &[u8]) -> Result<Metadata> {
+    let mut reader = std::io::Cursor::new(buf);
+    let mut buf = vec![0u8; 8];
+    let len = read_u64(&mut reader, buf.as_mut_slice())?;
+    let mut read_string = || -> Result<String> {
+        let len = read_u32(&mut reader, &mut buf[..4])? as usize;
+        let mut bytes = vec![0u8; len];
+        reader.read_exact(&mut bytes)?;
+        String::from_utf8(bytes).map_err(|err| Error::OutOfSpec(err.to_string()))
+    };
+    let mut metadata = Metadata::new();
+    for _ in 0..len {
+        let key = read_string()?;
+        let value = read_string()?;
+        metadata.insert(key, value);
+    }
+    Ok(metadata)
+}
+
+/// Deserializes the schema of the footer.
+///
+/// The IPC schema stores the extension types in the metadata of their fields,
+/// the keys are removed so that the fields are read as they were written.
+fn read_schema(bytes: &[u8]) -> Result<Schema> {
+    let (mut schema, _) = deserialize_schema(bytes)?;
+    schema.fields.iter_mut().for_each(remove_extension_metadata);
+    Ok(schema)
+}
+
+fn remove_extension_metadata(field: &mut Field) {
+    if let DataType::Extension(..) = field.data_type {
+        field.metadata.remove(EXTENSION_NAME);
+        field.metadata.remove(EXTENSION_METADATA);
+    }
+    let mut data_type = &mut field.data_type;
+    while let DataType::Extension(_, inner, _) = data_type {
+        data_type = inner.as_mut();
+    }
+    match data_type {
+        DataType::Struct(fields) | DataType::Union(fields, _, _) => {
+            fields.iter_mut().for_each(remove_extension_metadata)
+        }
+        DataType::List(inner)
+        | DataType::LargeList(inner)
+        | DataType::FixedSizeList(inner, _)
+        | DataType::Map(inner, _) => remove_extension_metadata(inner),
+        _ => {}
+    }
+}
+
+/// Splits the footer, without its tail, into the schema, the column metas
+/// and the key value metadata.
+fn deserialize_footer(footer: &[u8], sizes: &FooterSizes) -> Result<FileMeta> {
+    let (schema_bytes, rest) = footer.split_at(sizes.schema);
+    let (meta_bytes, key_value_bytes) = rest.split_at(sizes.meta);
+    let schema = read_schema(schema_bytes)?;
+    Ok(FileMeta {
+        schema,
+        metas: deserialize_meta(meta_bytes.to_vec())?,
+        key_value_metadata: deserialize_key_value_metadata(key_value_bytes)?,
+    })
+}
+
+/// Reads the schema, the column metas and the key value metadata of a file
+/// from its footer, without reading any page.
+///
+/// It opens the file and validates its header too, the other readers of the footer
+/// only validate the magic and the format version of the footer.
+pub fn read_file_meta<Reader: Read + Seek>(reader: &mut Reader) -> Result<FileMeta> {
+    let sizes = read_footer_tail(reader)?;
+    read_header(reader, sizes.version)?;
+    reader.seek(SeekFrom::End(-(sizes.schema_offset() as i64)))?;
+    let mut footer = vec![0u8; sizes.schema_offset() - FOOTER_TAIL_SIZE];
+    reader.read_exact(&mut footer)?;
+    deserialize_footer(&footer, &sizes)
+}
+
+/// Async version of [`read_file_meta`].
+pub async fn read_file_meta_async<Reader: AsyncRead + AsyncSeek + Send + Unpin>(
+    reader: &mut Reader,
+) -> Result<FileMeta> {
+    let sizes = read_footer_tail_async(reader).await?;
+    read_header_async(reader, sizes.version).await?;
+    reader
+        .seek(SeekFrom::End(-(sizes.schema_offset() as i64)))
+        .await?;
+    let mut footer = vec![0u8; sizes.schema_offset() - FOOTER_TAIL_SIZE];
+    reader.read_exact(&mut footer).await?;
+    deserialize_footer(&footer, &sizes)
+}
+
 pub fn read_meta<Reader: Read + Seek>(reader: &mut Reader) -> Result<Vec<ColumnMeta>> {
-    // EOS(8 bytes) + meta_size(4 bytes) = 12 bytes
-    reader.seek(SeekFrom::End(-12))?;
-    let mut buf = vec![0u8; 4];
-    let meta_size = read_u32(reader, buf.as_mut_slice())? as usize;
-    reader.seek(SeekFrom::End(-16 - meta_size as i64))?;
+    let sizes = read_footer_tail(reader)?;
+    reader.seek(SeekFrom::End(-(sizes.meta_offset() as i64)))?;
 
-    let mut meta_buf = vec![0u8; meta_size];
+    let mut meta_buf = vec![0u8; sizes.meta];
     reader.read_exact(&mut meta_buf)?;
     deserialize_meta(meta_buf)
 }
@@ -183,6 +715,7 @@ pub async fn read_meta_async<Reader: AsyncRead + AsyncSeek + Send + Unpin>(
 ) -> Result<Vec<ColumnMeta>> {
     match total_len {
         Some(total_len) => {
+            check_file_size(total_len as u64)?;
             // Pre-read footer data to reduce IO.
             let pre_read_len = total_len.min(DEFAULT_FOOTER_SIZE as usize);
 
@@ -190,34 +723,26 @@ pub async fn read_meta_async<Reader: AsyncRead + AsyncSeek + Send + Unpin>(
             let mut buf = vec![0u8; pre_read_len];
             reader.read_exact(&mut buf).await?;
 
-            let mut footer_reader = std::io::Cursor::new(buf);
-            // EOS(8 bytes) + meta_size(4 bytes) = 12 bytes
-            footer_reader.seek(SeekFrom::End(-12))?;
-            let mut buf = vec![0u8; 4];
-            let meta_size = read_u32(&mut footer_reader, buf.as_mut_slice())? as usize;
-
-            let footer_size = meta_size + 16;
-            if footer_size <= pre_read_len {
-                footer_reader.seek(SeekFrom::End(-16 - meta_size as i64))?;
-                let mut meta_buf = vec![0u8; meta_size];
-                footer_reader.read_exact(&mut meta_buf)?;
-                deserialize_meta(meta_buf)
+            let sizes = parse_footer_tail(&buf[pre_read_len - FOOTER_TAIL_SIZE..])?;
+            let meta_offset = sizes.meta_offset();
+            if meta_offset <= pre_read_len {
+                let meta_start = pre_read_len - meta_offset;
+                deserialize_meta(buf[meta_start..meta_start + sizes.meta].to_vec())
             } else {
                 // The readed data is not long enough to hold the meta data.
                 // Should read again.
-                reader.seek(SeekFrom::End(-(footer_size as i64))).await?;
-                let mut buf = vec![0u8; footer_size];
-                reader.read_exact(&mut buf).await?;
-                let mut final_reader = std::io::Cursor::new(buf);
-                read_meta(&mut final_reader)
+                reader.seek(SeekFrom::End(-(meta_offset as i64))).await?;
+                let mut meta_buf = vec![0u8; sizes.meta];
+                reader.read_exact(&mut meta_buf).await?;
+                deserialize_meta(meta_buf)
             }
         }
         _ => {
-            reader.seek(SeekFrom::End(-12)).await?;
-            let mut buf = vec![0u8; 4];
-            let meta_size = read_u32_async(reader, buf.as_mut_slice()).await? as usize;
-            let mut meta_buf = vec![0u8; meta_size];
-            reader.seek(SeekFrom::End(-16 - meta_size as i64)).await?;
+            let sizes = read_footer_tail_async(reader).await?;
+            let mut meta_buf = vec![0u8; sizes.meta];
+            reader
+                .seek(SeekFrom::End(-(sizes.meta_offset() as i64)))
+                .await?;
             reader.read_exact(&mut meta_buf).await?;
             deserialize_meta(meta_buf)
         }
@@ -225,38 +750,22 @@ pub async fn read_meta_async<Reader: AsyncRead + AsyncSeek + Send + Unpin>(
 }
 
 pub fn infer_schema<Reader: Read + Seek>(reader: &mut Reader) -> Result<Schema> {
-    // EOS(8 bytes) + meta_size(4 bytes) + schema_size(4bytes) = 16 bytes
-    reader.seek(SeekFrom::End(-16))?;
-    let mut buf = vec![0u8; 4];
-    let schema_size = read_u32(reader, buf.as_mut_slice())? as usize;
-    let column_meta_size = read_u32(reader, buf.as_mut_slice())? as usize;
-
-    reader.seek(SeekFrom::Current(
-        -(column_meta_size as i64) - (schema_size as i64) - 8,
-    ))?;
-    let mut schema_bytes = vec![0u8; schema_size];
+    let sizes = read_footer_tail(reader)?;
+    reader.seek(SeekFrom::End(-(sizes.schema_offset() as i64)))?;
+    let mut schema_bytes = vec![0u8; sizes.schema];
     reader.read_exact(&mut schema_bytes)?;
-    let (schema, _) = deserialize_schema(&schema_bytes).expect("deserialize schema error");
-    Ok(schema)
+    read_schema(&schema_bytes)
 }
 
 pub async fn infer_schema_async<Reader: AsyncRead + AsyncSeek + Send + Unpin>(
     reader: &mut Reader,
 ) -> Result<Schema> {
-    // EOS(8 bytes) + meta_size(4 bytes) + schema_size(4bytes) = 16 bytes
-    reader.seek(SeekFrom::End(-16)).await?;
-    let mut buf = vec![0u8; 4];
-    let schema_size = read_u32_async(reader, buf.as_mut_slice()).await? as usize;
-    let column_meta_size = read_u32_async(reader, buf.as_mut_slice()).await? as usize;
-
+    let sizes = read_footer_tail_async(reader).await?;
     reader
-        .seek(SeekFrom::Current(
-            -(column_meta_size as i64) - (schema_size as i64) - 8,
-        ))
+        .seek(SeekFrom::End(-(sizes.schema_offset() as i64)))
         .await?;
-    let mut schema_bytes = vec![0u8; schema_size];
+    let mut schema_bytes = vec![0u8; sizes.schema];
     reader.read_exact(&mut schema_bytes).await?;
 
-    let (schema, _) = deserialize_schema(&schema_bytes).expect("deserialize schema error");
-    Ok(schema)
+    read_schema(&schema_bytes)
 }
@@ -15,6 +15,7 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use crate::variant::is_variant;
 use crate::{compression::Compression, read::PageIterator, CommonCompression};
 use arrow::{
     datatypes::{Field, PhysicalType},
@@ -45,6 +46,7 @@ pub enum PageBody {
     Patas,
     Bitpack,
     DeltaBitpack,
+    ByteStreamSplit,
     Common(CommonCompression),
 }
 
@@ -60,12 +62,12 @@ pub struct DictPageBody {
     pub unique_num: u32,
 }
 
-pub fn stat_simple<'a, I: 'a>(reader: I, field: Field) -> Result<ColumnInfo>
+pub fn stat_simple<'a, I: 'a>(mut reader: I, field: Field) -> Result<ColumnInfo>
 where
     I: Iterator<Item = Result<(u64, Vec<u8>)>> + PageIterator + Send + Sync,
 {
     let mut pages = vec![];
-    for compressed in reader {
+    while let Some(compressed) = reader.next() {
         let (_, buffer) = compressed?;
 
         let mut buffer = buffer.as_slice();
@@ -76,17 +78,54 @@ where
             opt_validity_size = Some(u32::from_le_bytes(buffer[0..4].try_into().unwrap()));
         };
 
-        let physical_type = field.data_type.to_physical_type();
-        let page = stat_body(&mut buffer, opt_validity_size, physical_type)?;
+        if is_variant(&field.data_type) {
+            skip_variant_paths(&mut buffer);
+        }
+
+        let physical_type = match field.data_type.to_physical_type() {
+            // the remainders of variant values are described
+            _ if is_variant(&field.data_type) => PhysicalType::LargeBinary,
+            // the components of intervals are compressed one after another,
+            // only the first one, an Int32, is described
+            PhysicalType::Primitive(PrimitiveType::DaysMs | PrimitiveType::MonthDayNano) => {
+                PhysicalType::Primitive(PrimitiveType::Int32)
+            }
+            other => other,
+        };
+        // the pages of a column dictionary only hold the indices
+        let dictionary = reader.dictionary().map(|(_, bytes)| bytes);
+        let page = stat_body(&mut buffer, opt_validity_size, physical_type, dictionary)?;
         pages.push(page);
     }
     Ok(ColumnInfo { field, pages })
 }
 
+/// Skips the shredded paths of a variant page, up to the remainders of its values.
+fn skip_variant_paths(buffer: &mut &[u8]) {
+    let read_u32 = |buffer: &mut &[u8]| {
+        let value = u32::from_le_bytes(buffer[0..4].try_into().unwrap());
+        *buffer = &buffer[4..];
+        value as usize
+    };
+    let num_paths = read_u32(buffer);
+    for _ in 0..num_paths {
+        let num_keys = read_u32(buffer);
+        for _ in 0..num_keys {
+            let key_size = read_u32(buffer);
+            *buffer = &buffer[key_size..];
+        }
+        // the type of the scalars
+        *buffer = &buffer[1..];
+        let section_size = read_u32(buffer);
+        *buffer = &buffer[section_size..];
+    }
+}
+
 fn stat_body(
     buffer: &mut &[u8],
     opt_validity_size: Option<u32>,
     physical_type: PhysicalType,
+    dictionary: Option<&[u8]>,
 ) -> Result<PageInfo> {
     let codec = buffer[0];
     let compressed_size = u32::from_le_bytes(buffer[1..5].try_into().unwrap());
@@ -96,12 +135,13 @@ fn stat_body(
 
     let body = match codec {
         Compression::Rle => PageBody::Rle,
-        Compression::Dict => stat_dict_body(buffer, physical_type)?,
+        Compression::Dict => stat_dict_body(buffer, physical_type, dictionary)?,
         Compression::OneValue => PageBody::OneValue,
         Compression::Freq => stat_freq_body(buffer, physical_type)?,
         Compression::Bitpacking => PageBody::Bitpack,
         Compression::DeltaBitpacking => PageBody::DeltaBitpack,
         Compression::Patas => PageBody::Patas,
+        Compression::ByteStreamSplit => PageBody::ByteStreamSplit,
         _ => PageBody::Common(CommonCompression::try_from(&codec).unwrap()),
     };
     *buffer = &buffer[compressed_size as usize..];
@@ -120,7 +160,7 @@ fn stat_freq_body(mut buffer: &[u8], physical_type: PhysicalType) -> Result<Page
             buffer = &buffer[top_value_size..];
             let exceptions_bitmap_size = u32::from_le_bytes(buffer[0..4].try_into().unwrap());
             buffer = &buffer[4 + exceptions_bitmap_size as usize..];
-            let exceptions = stat_body(&mut buffer, None, physical_type)?;
+            let exceptions = stat_body(&mut buffer, None, physical_type, None)?;
             Ok(PageBody::Freq(FreqPageBody {
                 exceptions: Some(Box::new(exceptions)),
                 exceptions_bitmap_size,
@@ -142,9 +182,15 @@ fn stat_freq_body(mut buffer: &[u8], physical_type: PhysicalType) -> Result<Page
     }
 }
 
-fn stat_dict_body(mut buffer: &[u8], physical_type: PhysicalType) -> Result<PageBody> {
-    let indices = stat_body(&mut buffer, None, physical_type)?;
-    let unique_num = u32::from_le_bytes(buffer[0..4].try_into().unwrap());
+fn stat_dict_body(
+    mut buffer: &[u8],
+    physical_type: PhysicalType,
+    dictionary: Option<&[u8]>,
+) -> Result<PageBody> {
+    let indices = stat_body(&mut buffer, None, physical_type, None)?;
+    // the values follow the indices, or are in the column dictionary
+    let values = dictionary.unwrap_or(buffer);
+    let unique_num = u32::from_le_bytes(values[0..4].try_into().unwrap());
     Ok(PageBody::Dict(DictPageBody {
         indices: Box::new(indices),
         unique_num,
@@ -163,7 +209,7 @@ fn size_of_primitive(p: PrimitiveType) -> usize {
         PrimitiveType::UInt16 => 2,
         PrimitiveType::UInt32 => 4,
         PrimitiveType::UInt64 => 8,
-        PrimitiveType::Float16 => unimplemented!(),
+        PrimitiveType::Float16 => 2,
         PrimitiveType::Float32 => 4,
         PrimitiveType::Float64 => 8,
         PrimitiveType::DaysMs => unimplemented!(),
@@ -184,8 +230,8 @@ mod test {
     use crate::{
         read::reader::{is_primitive, NativeReader},
         stat::PageBody,
-        util::env::{remove_all_env, set_dict_env, set_freq_env},
         write::{NativeWriter, WriteOptions},
+        Compression,
     };
 
     use super::{stat_simple, ColumnInfo};
@@ -194,13 +240,23 @@ mod test {
     const PAGE_PER_COLUMN: usize = 10;
     const COLUMN_SIZE: usize = PAGE_SIZE * PAGE_PER_COLUMN;
 
-    fn write_and_stat_simple_column(array: Box<dyn Array>) -> ColumnInfo {
+    fn write_and_stat_simple_column(
+        array: Box<dyn Array>,
+        forced_compression: Option<Compression>,
+        column_dictionary: bool,
+    ) -> ColumnInfo {
         assert!(is_primitive(array.data_type()));
         let options = WriteOptions {
             default_compression: crate::CommonCompression::Lz4,
             max_page_size: Some(PAGE_SIZE),
+            max_page_bytes: None,
             default_compress_ratio: Some(1.2),
             forbidden_compressions: vec![],
+            forced_compression,
+            forced_compressions: vec![],
+            page_checksum: false,
+            column_dictionary,
+            column_options: vec![],
         };
 
         let mut bytes = Vec::new();
@@ -227,19 +283,18 @@ mod test {
 
     #[test]
     fn test_stat_simple() {
-        remove_all_env();
         let array = Box::new(BinaryArray::<i64>::from_iter_values(
             ["a"; COLUMN_SIZE].iter(),
         ));
-        let column_info = write_and_stat_simple_column(array.clone());
+        let column_info = write_and_stat_simple_column(array.clone(), None, false);
         assert_eq!(column_info.pages.len(), 10);
         for p in column_info.pages {
             assert_eq!(p.validity_size, None);
             assert!(matches!(p.body, PageBody::OneValue));
         }
 
-        set_dict_env();
-        let column_info = write_and_stat_simple_column(array.clone());
+        let column_info =
+            write_and_stat_simple_column(array.clone(), Some(Compression::Dict), false);
         assert_eq!(column_info.pages.len(), 10);
         for p in column_info.pages {
             assert_eq!(p.validity_size, None);
@@ -252,10 +307,8 @@ mod test {
                 _ => panic!("expect dict page"),
             }
         }
-        remove_all_env();
 
-        set_freq_env();
-        let column_info = write_and_stat_simple_column(array);
+        let column_info = write_and_stat_simple_column(array, Some(Compression::Freq), false);
         assert_eq!(column_info.pages.len(), 10);
         for p in column_info.pages {
             assert_eq!(p.validity_size, None);
@@ -266,5 +319,22 @@ mod test {
                 _ => panic!("expect freq page"),
             }
         }
+
+        // the pages of a column dictionary only hold the indices
+        let array = Box::new(BinaryArray::<i64>::from_iter_values(
+            ["a", "bb", "ccc"].iter().cycle().take(COLUMN_SIZE),
+        ));
+        let column_info = write_and_stat_simple_column(array, None, true);
+        assert_eq!(column_info.pages.len(), 10);
+        for p in column_info.pages {
+            assert_eq!(p.uncompressed_size as usize, PAGE_SIZE * 4);
+            match p.body {
+                PageBody::Dict(dict) => {
+                    assert_eq!(dict.unique_num, 3);
+                    assert_eq!(p.compressed_size, dict.indices.compressed_size + 9);
+                }
+                _ => panic!("expect dict page"),
+            }
+        }
     }
 }
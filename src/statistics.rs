@@ -0,0 +1,305 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::any::Any;
+use std::cmp::Ordering;
+
+use arrow::array::{
+    Array, BinaryArray, BooleanArray, FixedSizeBinaryArray, PrimitiveArray, Utf8Array,
+};
+use arrow::datatypes::{DataType, PhysicalType, PrimitiveType};
+use arrow::scalar::{
+    BinaryScalar, BooleanScalar, FixedSizeBinaryScalar, PrimitiveScalar, Scalar, Utf8Scalar,
+};
+use arrow::types::{f16, i256, NativeType, Offset};
+
+use crate::compression::double::DoubleType;
+use crate::variant::is_variant;
+use crate::with_match_primitive_type;
+
+/// Dispatches the integer primitive types, which include the decimal types.
+/// The float types are ordered by their values except NaN, and the
+/// interval types have no order and no min and max statistics.
+macro_rules! with_match_integer_type {
+    (
+    $key_type:expr, | $_:tt $T:ident | $body:tt, $other:expr
+) => {{
+        macro_rules! __with_ty__ {
+            ( $_ $T:ident ) => {
+                $body
+            };
+        }
+        match $key_type {
+            PrimitiveType::Int8 => __with_ty__! { i8 },
+            PrimitiveType::Int16 => __with_ty__! { i16 },
+            PrimitiveType::Int32 => __with_ty__! { i32 },
+            PrimitiveType::Int64 => __with_ty__! { i64 },
+            PrimitiveType::Int128 => __with_ty__! { i128 },
+            PrimitiveType::Int256 => __with_ty__! { i256 },
+            PrimitiveType::UInt8 => __with_ty__! { u8 },
+            PrimitiveType::UInt16 => __with_ty__! { u16 },
+            PrimitiveType::UInt32 => __with_ty__! { u32 },
+            PrimitiveType::UInt64 => __with_ty__! { u64 },
+            _ => $other,
+        }
+    }};
+}
+
+/// Statistics of a page or a column.
+/// The min and max values are stored as little-endian bytes of the physical type,
+/// they are `None` if all the values are null or the type has no order.
+/// The binary and utf8 values are truncated to [`STATISTICS_TRUNCATE_LENGTH`] bytes,
+/// the truncated max value is rounded up.
+/// The float values are ordered with NaN as the largest value, so the max value of
+/// the pages holding NaN is NaN.
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    Eq,
+    Hash,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct Statistics {
+    pub null_count: u64,
+    pub min_value: Option<Vec<u8>>,
+    pub max_value: Option<Vec<u8>>,
+}
+
+impl Statistics {
+    /// Computes the statistics of a leaf array.
+    pub fn from_array(array: &dyn Array) -> Self {
+        use PhysicalType::*;
+
+        let min_max = match array.data_type().to_physical_type() {
+            // the text of JSON values isn't ordered by the values
+            _ if is_variant(array.data_type()) => None,
+            Boolean => {
+                let array: &BooleanArray = array.as_any().downcast_ref().unwrap();
+                min_max_by(array.iter().flatten(), Ord::cmp)
+                    .map(|(min, max)| (vec![min as u8], vec![max as u8]))
+            }
+            Primitive(PrimitiveType::Float16) => double_min_max::<f16>(array),
+            Primitive(PrimitiveType::Float32) => double_min_max::<f32>(array),
+            Primitive(PrimitiveType::Float64) => double_min_max::<f64>(array),
+            Primitive(primitive) => with_match_integer_type!(primitive, |$T| {
+                let array: &PrimitiveArray<$T> = array.as_any().downcast_ref().unwrap();
+                min_max_by(array.iter().flatten().copied(), Ord::cmp)
+                    .map(|(min, max)| (encode_native(min), encode_native(max)))
+            }, None),
+            Binary => binary_min_max::<i32>(array),
+            LargeBinary => binary_min_max::<i64>(array),
+            Utf8 => utf8_min_max::<i32>(array),
+            LargeUtf8 => utf8_min_max::<i64>(array),
+            FixedSizeBinary => {
+                let array: &FixedSizeBinaryArray = array.as_any().downcast_ref().unwrap();
+                min_max_by(array.iter().flatten(), Ord::cmp)
+                    .map(|(min, max)| (min.to_vec(), max.to_vec()))
+            }
+            _ => None,
+        };
+
+        let (min_value, max_value) = match min_max {
+            Some((min, max)) => (Some(min), Some(max)),
+            None => (None, None),
+        };
+        Self {
+            null_count: array.null_count() as u64,
+            min_value,
+            max_value,
+        }
+    }
+
+    /// Merges the statistics of another page of the same column.
+    pub fn merge(&mut self, other: &Statistics, data_type: &DataType) {
+        self.null_count += other.null_count;
+        self.min_value = match (self.min_value.take(), &other.min_value) {
+            (Some(a), Some(b)) => match compare_value(data_type, &a, b) {
+                Some(Ordering::Greater) => Some(b.clone()),
+                _ => Some(a),
+            },
+            (a, b) => a.or_else(|| b.clone()),
+        };
+        self.max_value = match (self.max_value.take(), &other.max_value) {
+            (Some(a), Some(b)) => match compare_value(data_type, &a, b) {
+                Some(Ordering::Less) => Some(b.clone()),
+                _ => Some(a),
+            },
+            (a, b) => a.or_else(|| b.clone()),
+        };
+    }
+}
+
+/// Compares two values encoded by [`Statistics`] of the `data_type`,
+/// returns `None` if the type has no order.
+pub fn compare_value(data_type: &DataType, a: &[u8], b: &[u8]) -> Option<Ordering> {
+    use PhysicalType::*;
+
+    match data_type.to_physical_type() {
+        Boolean | Binary | LargeBinary | Utf8 | LargeUtf8 | FixedSizeBinary => Some(a.cmp(b)),
+        Primitive(PrimitiveType::Float16) => compare_double::<f16>(a, b),
+        Primitive(PrimitiveType::Float32) => compare_double::<f32>(a, b),
+        Primitive(PrimitiveType::Float64) => compare_double::<f64>(a, b),
+        Primitive(primitive) => with_match_integer_type!(primitive, |$T| {
+            decode_value::<$T>(a).partial_cmp(&decode_value::<$T>(b))
+        }, None),
+        _ => None,
+    }
+}
+
+// NaN is larger than the other values and equal to itself
+fn compare_double<T: DoubleType>(a: &[u8], b: &[u8]) -> Option<Ordering> {
+    decode_value::<T>(a)
+        .as_order()
+        .partial_cmp(&decode_value::<T>(b).as_order())
+}
+
+/// Returns whether a value encoded by [`Statistics`] of the `data_type` is NaN.
+pub fn is_nan_value(data_type: &DataType, value: &[u8]) -> bool {
+    match data_type.to_physical_type() {
+        PhysicalType::Primitive(PrimitiveType::Float16) => decode_value::<f16>(value).is_nan(),
+        PhysicalType::Primitive(PrimitiveType::Float32) => decode_value::<f32>(value).is_nan(),
+        PhysicalType::Primitive(PrimitiveType::Float64) => decode_value::<f64>(value).is_nan(),
+        _ => false,
+    }
+}
+
+/// Encodes a [`Scalar`] in the same way as the values of [`Statistics`],
+/// returns `None` if the scalar is null or its type is not supported.
+pub fn encode_scalar(scalar: &dyn Scalar) -> Option<Vec<u8>> {
+    use PhysicalType::*;
+
+    if !scalar.is_valid() {
+        return None;
+    }
+    let any = scalar.as_any();
+    match scalar.data_type().to_physical_type() {
+        Boolean => {
+            let scalar: &BooleanScalar = any.downcast_ref().unwrap();
+            scalar.value().map(|v| vec![v as u8])
+        }
+        Primitive(primitive) => with_match_primitive_type!(primitive, |$T| {
+            let scalar: &PrimitiveScalar<$T> = any.downcast_ref().unwrap();
+            scalar.value().map(|v| v.to_le_bytes().as_ref().to_vec())
+        }),
+        Binary => binary_scalar_value::<i32>(any),
+        LargeBinary => binary_scalar_value::<i64>(any),
+        Utf8 => utf8_scalar_value::<i32>(any),
+        LargeUtf8 => utf8_scalar_value::<i64>(any),
+        FixedSizeBinary => {
+            let scalar: &FixedSizeBinaryScalar = any.downcast_ref().unwrap();
+            scalar.value().map(|v| v.to_vec())
+        }
+        _ => None,
+    }
+}
+
+fn binary_scalar_value<O: Offset>(any: &dyn Any) -> Option<Vec<u8>> {
+    let scalar: &BinaryScalar<O> = any.downcast_ref().unwrap();
+    scalar.value().map(|v| v.to_vec())
+}
+
+fn utf8_scalar_value<O: Offset>(any: &dyn Any) -> Option<Vec<u8>> {
+    let scalar: &Utf8Scalar<O> = any.downcast_ref().unwrap();
+    scalar.value().map(|v| v.as_bytes().to_vec())
+}
+
+/// Decodes a value encoded by [`Statistics`] of primitive type `T`.
+pub fn decode_value<T: NativeType>(value: &[u8]) -> T {
+    match T::Bytes::try_from(value) {
+        Ok(bytes) => T::from_le_bytes(bytes),
+        Err(_) => unreachable!(),
+    }
+}
+
+fn encode_native<T: NativeType>(value: T) -> Vec<u8> {
+    value.to_le_bytes().as_ref().to_vec()
+}
+
+fn double_min_max<T: DoubleType>(array: &dyn Array) -> Option<(Vec<u8>, Vec<u8>)> {
+    let array: &PrimitiveArray<T> = array.as_any().downcast_ref().unwrap();
+    let values = array.iter().flatten().map(|v| v.as_order());
+    min_max_by(values, |a, b| a.partial_cmp(b).unwrap()).map(|(min, max)| {
+        (
+            encode_native(T::from_order(min)),
+            encode_native(T::from_order(max)),
+        )
+    })
+}
+
+fn binary_min_max<O: Offset>(array: &dyn Array) -> Option<(Vec<u8>, Vec<u8>)> {
+    let array: &BinaryArray<O> = array.as_any().downcast_ref().unwrap();
+    truncate_min_max(array.iter().flatten())
+}
+
+fn utf8_min_max<O: Offset>(array: &dyn Array) -> Option<(Vec<u8>, Vec<u8>)> {
+    let array: &Utf8Array<O> = array.as_any().downcast_ref().unwrap();
+    truncate_min_max(array.iter().flatten().map(str::as_bytes))
+}
+
+fn truncate_min_max<'a>(values: impl Iterator<Item = &'a [u8]>) -> Option<(Vec<u8>, Vec<u8>)> {
+    min_max_by(values, Ord::cmp).map(|(min, max)| (truncate_min(min), truncate_max(max)))
+}
+
+fn min_max_by<T: Copy, F>(mut values: impl Iterator<Item = T>, cmp: F) -> Option<(T, T)>
+where
+    F: Fn(&T, &T) -> Ordering,
+{
+    let first = values.next()?;
+    Some(values.fold((first, first), |(min, max), v| {
+        let min = if cmp(&v, &min) == Ordering::Less {
+            v
+        } else {
+            min
+        };
+        let max = if cmp(&v, &max) == Ordering::Greater {
+            v
+        } else {
+            max
+        };
+        (min, max)
+    }))
+}
+
+/// The max length of the binary min and max values.
+pub const STATISTICS_TRUNCATE_LENGTH: usize = 64;
+
+fn truncate_min(value: &[u8]) -> Vec<u8> {
+    value[..value.len().min(STATISTICS_TRUNCATE_LENGTH)].to_vec()
+}
+
+/// Truncates the max value and increments the last byte that can be
+/// incremented, so it is still an upper bound of the values. The value is
+/// kept untruncated if its prefix can't be incremented.
+fn truncate_max(value: &[u8]) -> Vec<u8> {
+    if value.len() <= STATISTICS_TRUNCATE_LENGTH {
+        return value.to_vec();
+    }
+    let mut prefix = value[..STATISTICS_TRUNCATE_LENGTH].to_vec();
+    match prefix.iter().rposition(|b| *b != u8::MAX) {
+        Some(i) => {
+            prefix[i] += 1;
+            prefix.truncate(i + 1);
+            prefix
+        }
+        None => value.to_vec(),
+    }
+}
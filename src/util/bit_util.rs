@@ -20,6 +20,7 @@ use std::mem::size_of;
 use arrow::buffer::Buffer;
 use arrow::error::Error;
 use arrow::error::Result;
+use arrow::types::f16;
 
 #[inline]
 pub fn from_le_slice<T: FromBytes>(bs: &[u8]) -> T {
@@ -142,6 +143,7 @@ gen_as_bytes!(u32);
 gen_as_bytes!(u64);
 gen_as_bytes!(f32);
 gen_as_bytes!(f64);
+gen_as_bytes!(f16);
 
 /// Reads `size` of bytes from `src`, and reinterprets them as type `ty`, in
 /// little-endian order.
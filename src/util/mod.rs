@@ -20,7 +20,6 @@ mod bit_pack;
 #[allow(dead_code)]
 mod bit_util;
 mod byte_writer;
-pub mod env;
 pub mod memory;
 
 pub use bit_util::*;
@@ -52,6 +51,38 @@ macro_rules! with_match_primitive_type {(
     }
 })}
 
+#[macro_export]
+macro_rules! with_match_interval_type {(
+    $key_type:expr, | $_:tt $T:ident | $($body:tt)*
+) => ({
+    macro_rules! __with_ty__ {( $_ $T:ident ) => ( $($body)* )}
+    use arrow::datatypes::PrimitiveType::*;
+    use arrow::types::{days_ms, months_days_ns};
+    match $key_type {
+        DaysMs => __with_ty__! { days_ms },
+        MonthDayNano => __with_ty__! { months_days_ns },
+        _ => unreachable!(),
+    }
+})}
+
+#[macro_export]
+macro_rules! with_match_dictionary_key_type {(
+    $key_type:expr, | $_:tt $K:ident | $($body:tt)*
+) => ({
+    macro_rules! __with_ty__ {( $_ $K:ident ) => ( $($body)* )}
+    use arrow::datatypes::IntegerType::*;
+    match $key_type {
+        Int8 => __with_ty__! { i8 },
+        Int16 => __with_ty__! { i16 },
+        Int32 => __with_ty__! { i32 },
+        Int64 => __with_ty__! { i64 },
+        UInt8 => __with_ty__! { u8 },
+        UInt16 => __with_ty__! { u16 },
+        UInt32 => __with_ty__! { u32 },
+        UInt64 => __with_ty__! { u64 },
+    }
+})}
+
 #[macro_export]
 macro_rules! with_match_integer_double_type {
     (
@@ -68,7 +99,7 @@ macro_rules! with_match_integer_double_type {
             };
         }
         use arrow::datatypes::PrimitiveType::*;
-        use arrow::types::i256;
+        use arrow::types::{f16, i256};
         match $key_type {
             Int8 => __with_ty__! { i8 },
             Int16 => __with_ty__! { i16 },
@@ -81,9 +112,9 @@ macro_rules! with_match_integer_double_type {
             UInt32 => __with_ty__! { u32 },
             UInt64 => __with_ty__! { u64 },
 
+            Float16 => __with_ty_double__! { f16 },
             Float32 => __with_ty_double__! { f32 },
             Float64 => __with_ty_double__! { f64 },
-            Float16 => unreachable! {},
             DaysMs => unreachable!(),
             MonthDayNano => unreachable!(),
         }
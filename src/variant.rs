@@ -0,0 +1,193 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Variant columns of JSON values.
+//!
+//! A variant column stores JSON values as the text of a `Utf8` or `LargeUtf8` array of the
+//! [`VARIANT_EXTENSION_NAME`] extension type. The scalars at the paths of objects occurring
+//! in most of the rows of a page are shredded into typed sub-columns compressed as integers,
+//! doubles, binaries or booleans, the remainder of the values is compressed as binary.
+//!
+//! The values are normalized when written, they are read back as the same JSON values but
+//! not as the same text: the whitespace is dropped, the keys of objects are sorted, only the
+//! last value of a duplicated key is kept and numbers out of the range of `i64` and `u64`
+//! are read back as `f64`.
+//!
+//! The shredded paths are chosen per page. A single path of a variant field can be
+//! projected, the pages are still read whole as the paths are stored in the page. The
+//! values of a shredded path are read without parsing JSON, and the remainders of a page
+//! aren't decompressed when every row of the page has a scalar at the path.
+
+use arrow::datatypes::DataType;
+use arrow::error::{Error, Result};
+use serde_json::Value;
+
+/// The name of the extension type of variant columns.
+pub const VARIANT_EXTENSION_NAME: &str = "strawboat.variant";
+
+/// Returns the variant type of JSON values stored as `LargeUtf8`.
+pub fn variant_data_type() -> DataType {
+    DataType::Extension(
+        VARIANT_EXTENSION_NAME.to_string(),
+        Box::new(DataType::LargeUtf8),
+        None,
+    )
+}
+
+/// Whether `data_type` is a variant type, with or without a path.
+pub fn is_variant(data_type: &DataType) -> bool {
+    matches!(
+        data_type,
+        DataType::Extension(name, inner, _)
+            if name == VARIANT_EXTENSION_NAME
+                && matches!(inner.as_ref(), DataType::Utf8 | DataType::LargeUtf8)
+    )
+}
+
+/// Returns the type of the values at `path` of the variant `data_type`, a field of this type
+/// is read as the JSON values at the path, or nulls where the path is missing.
+///
+/// The path is stored as the metadata of the extension type.
+pub fn variant_path_type<S: AsRef<str>>(data_type: &DataType, path: &[S]) -> DataType {
+    let mut full_path = variant_path(data_type).unwrap_or_default();
+    full_path.extend(path.iter().map(|s| s.as_ref().to_string()));
+    match data_type {
+        DataType::Extension(name, inner, _) => DataType::Extension(
+            name.clone(),
+            inner.clone(),
+            Some(serde_json::to_string(&full_path).unwrap()),
+        ),
+        other => other.clone(),
+    }
+}
+
+/// Returns the path of the values of a variant type, `None` for the whole values.
+pub fn variant_path(data_type: &DataType) -> Option<Vec<String>> {
+    match data_type {
+        DataType::Extension(_, _, Some(metadata)) if is_variant(data_type) => {
+            serde_json::from_str(metadata).ok()
+        }
+        _ => None,
+    }
+}
+
+/// The type of the values of a shredded path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub(crate) enum ShreddedType {
+    Int64 = 0,
+    Float64 = 1,
+    String = 2,
+    Boolean = 3,
+}
+
+impl ShreddedType {
+    /// The type of a scalar JSON value which can be shredded.
+    pub(crate) fn of(value: &Value) -> Option<Self> {
+        match value {
+            Value::Number(n) if n.is_i64() => Some(ShreddedType::Int64),
+            Value::Number(n) if n.is_f64() => Some(ShreddedType::Float64),
+            Value::String(_) => Some(ShreddedType::String),
+            Value::Bool(_) => Some(ShreddedType::Boolean),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn from_u8(tag: u8) -> Result<Self> {
+        match tag {
+            0 => Ok(ShreddedType::Int64),
+            1 => Ok(ShreddedType::Float64),
+            2 => Ok(ShreddedType::String),
+            3 => Ok(ShreddedType::Boolean),
+            other => Err(Error::OutOfSpec(format!(
+                "Unknown type {other} of a shredded variant path"
+            ))),
+        }
+    }
+}
+
+/// The value at a path of a JSON value.
+pub(crate) enum PathValue {
+    /// A scalar of the shredded type, it's removed from the value.
+    Shredded(Value),
+    /// A value of another type, it's kept in the value.
+    Other,
+    Missing,
+}
+
+/// Calls `f` with the path and type of every scalar which can be shredded, the paths are
+/// the keys of the nested objects, arrays aren't traversed.
+pub(crate) fn visit_scalars<F>(value: &Value, path: &mut Vec<String>, f: &mut F)
+where
+    F: FnMut(&[String], ShreddedType),
+{
+    if let Value::Object(map) = value {
+        for (key, value) in map {
+            path.push(key.clone());
+            match ShreddedType::of(value) {
+                Some(type_) => f(path, type_),
+                None => visit_scalars(value, path, f),
+            }
+            path.pop();
+        }
+    }
+}
+
+/// Returns the value at `path`, `None` if it's missing.
+pub(crate) fn get_path<'a>(value: &'a Value, path: &[String]) -> Option<&'a Value> {
+    path.iter()
+        .try_fold(value, |value, key| value.as_object()?.get(key))
+}
+
+/// Removes the scalar of `type_` at `path` from the value, the parent objects are kept
+/// so it can be inserted back.
+pub(crate) fn take_path(value: &mut Value, path: &[String], type_: ShreddedType) -> PathValue {
+    let (last, parents) = path.split_last().unwrap();
+    let parent = parents
+        .iter()
+        .try_fold(value, |value, key| value.as_object_mut()?.get_mut(key))
+        .and_then(|parent| parent.as_object_mut());
+    let parent = match parent {
+        Some(parent) => parent,
+        None => return PathValue::Missing,
+    };
+    match parent.get(last) {
+        Some(value) if ShreddedType::of(value) == Some(type_) => {
+            PathValue::Shredded(parent.remove(last).unwrap())
+        }
+        Some(_) => PathValue::Other,
+        None => PathValue::Missing,
+    }
+}
+
+/// Inserts a shredded scalar at `path` of the remainder of a value.
+pub(crate) fn insert_path(value: &mut Value, path: &[String], scalar: Value) -> Result<()> {
+    let (last, parents) = path.split_last().unwrap();
+    let parent = parents
+        .iter()
+        .try_fold(value, |value, key| value.as_object_mut()?.get_mut(key))
+        .and_then(|parent| parent.as_object_mut());
+    match parent {
+        Some(parent) => {
+            parent.insert(last.clone(), scalar);
+            Ok(())
+        }
+        None => Err(Error::OutOfSpec(format!(
+            "The remainder of a variant value has no object at the shredded path {}",
+            path.join(".")
+        ))),
+    }
+}
@@ -19,18 +19,27 @@ use std::io::Write;
 
 use arrow::array::*;
 use arrow::chunk::Chunk;
-use arrow::error::Result;
+use arrow::compute::aggregate::estimated_bytes_size;
+use arrow::datatypes::{DataType, Field, PhysicalType};
+use arrow::error::{Error, Result};
 use arrow::io::parquet::write::{
-    num_values, slice_parquet_array, to_leaves, to_nested, to_parquet_leaves, SchemaDescriptor,
+    num_values, slice_parquet_array, to_leaves, to_nested, to_parquet_leaves, Nested,
+    SchemaDescriptor,
 };
+use parquet2::schema::types::{ParquetType, PrimitiveType};
+use rayon::prelude::*;
 
 use crate::compression::CommonCompression;
 use crate::compression::Compression;
 use crate::ColumnMeta;
+use crate::DictionaryMeta;
 use crate::PageMeta;
-use crate::CONTINUATION_MARKER;
+use crate::Statistics;
 
-use super::{write, NativeWriter};
+use super::dictionary::{normalize_dictionary_nulls, ColumnDictionary};
+use super::serialize::write_page;
+use super::union::normalize_union;
+use super::NativeWriter;
 
 /// Options declaring the behaviour of writing to IPC
 #[derive(Debug, Clone, PartialEq, Default)]
@@ -40,78 +49,239 @@ pub struct WriteOptions {
     pub default_compression: CommonCompression,
     /// If some encoding method performs over this ratio, we will switch to use it.
     pub default_compress_ratio: Option<f64>,
+    /// The max number of rows of a page.
     pub max_page_size: Option<usize>,
+    /// The max estimated uncompressed bytes of a page, the number of rows of
+    /// each leaf column's pages is adjusted to fit it.
+    pub max_page_bytes: Option<usize>,
     pub forbidden_compressions: Vec<Compression>,
+    /// Always use this compression, instead of choosing one by the statistics of pages.
+    /// It takes precedence over `forbidden_compressions`. There is no fallback: the write
+    /// fails and poisons the writer if it can't be used for any page, or doesn't suit it.
+    /// E.g. `Bitpacking` and `DeltaBitpacking` need every page, including the last page
+    /// of a chunk, to hold a multiple of 128 values, and `Dict` fails for a page of mostly
+    /// distinct values.
+    pub forced_compression: Option<Compression>,
+    /// The forced compression of each physical type, it takes precedence over
+    /// `forced_compression` and must suit every page of the type in the same way.
+    pub forced_compressions: Vec<(PhysicalType, Compression)>,
+    /// Whether to store the CRC32C of each page, it's verified when the page is read.
+    pub page_checksum: bool,
+    /// Whether to build a dictionary of each integer, float and binary leaf column of a
+    /// chunk, it's stored once before the pages which only hold the indices of the values.
+    /// It's not used for a column of too many distinct values, or forced to another compression.
+    pub column_dictionary: bool,
+    /// Overrides of the options for some columns, all the matching overrides
+    /// are applied to a leaf column in order.
+    pub column_options: Vec<(ColumnSelector, ColumnWriteOptions)>,
+}
+
+/// Selects the leaf columns a [`ColumnWriteOptions`] is applied to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ColumnSelector {
+    /// The path of a field such as `a.b` or `list_col.item.x`, it selects all the
+    /// leaf columns of the field.
+    Path(String),
+    /// The index of a leaf column in the file.
+    Leaf(usize),
+}
+
+impl ColumnSelector {
+    fn matches(&self, path: &str, leaf_index: usize) -> bool {
+        match self {
+            ColumnSelector::Path(prefix) => {
+                path == prefix
+                    || (path.starts_with(prefix.as_str())
+                        && path.as_bytes().get(prefix.len()) == Some(&b'.'))
+            }
+            ColumnSelector::Leaf(index) => *index == leaf_index,
+        }
+    }
+}
+
+/// Options of some columns overriding the [`WriteOptions`], `None` means unchanged.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ColumnWriteOptions {
+    pub default_compression: Option<CommonCompression>,
+    pub default_compress_ratio: Option<f64>,
+    pub max_page_size: Option<usize>,
+    pub max_page_bytes: Option<usize>,
+    pub forbidden_compressions: Option<Vec<Compression>>,
+    /// See [`WriteOptions::forced_compression`], it must suit every page of the columns.
+    pub forced_compression: Option<Compression>,
+    pub column_dictionary: Option<bool>,
+}
+
+impl WriteOptions {
+    /// Adds an override of the options for the selected columns.
+    pub fn with_column_options(
+        mut self,
+        selector: ColumnSelector,
+        options: ColumnWriteOptions,
+    ) -> Self {
+        self.column_options.push((selector, options));
+        self
+    }
+
+    /// Resolves the options of the leaf column of `path`, `leaf_index` and `data_type`.
+    pub fn for_column(&self, path: &str, leaf_index: usize, data_type: &DataType) -> WriteOptions {
+        let mut options = WriteOptions {
+            forced_compressions: vec![],
+            column_options: vec![],
+            ..self.clone()
+        };
+        let physical_type = data_type.to_physical_type();
+        for (t, compression) in self.forced_compressions.iter() {
+            if *t == physical_type {
+                options.forced_compression = Some(*compression);
+            }
+        }
+        for (selector, column_options) in self.column_options.iter() {
+            if !selector.matches(path, leaf_index) {
+                continue;
+            }
+            if let Some(compression) = column_options.default_compression {
+                options.default_compression = compression;
+            }
+            if let Some(ratio) = column_options.default_compress_ratio {
+                options.default_compress_ratio = Some(ratio);
+            }
+            if let Some(max_page_size) = column_options.max_page_size {
+                options.max_page_size = Some(max_page_size);
+            }
+            if let Some(max_page_bytes) = column_options.max_page_bytes {
+                options.max_page_bytes = Some(max_page_bytes);
+            }
+            if let Some(forbidden) = &column_options.forbidden_compressions {
+                options.forbidden_compressions = forbidden.clone();
+            }
+            if let Some(compression) = column_options.forced_compression {
+                options.forced_compression = Some(compression);
+            }
+            if let Some(column_dictionary) = column_options.column_dictionary {
+                options.column_dictionary = column_dictionary;
+            }
+        }
+        options
+    }
 }
 
 impl<W: Write> NativeWriter<W> {
-    /// Encode and write a [`Chunk`] to the file
+    /// Encode and write a [`Chunk`] to the file,
+    /// the pages are appended to the [`ColumnMeta`] of each leaf column.
     pub fn encode_chunk(
         &mut self,
         schema_descriptor: SchemaDescriptor,
         chunk: &Chunk<Box<dyn Array>>,
     ) -> Result<()> {
-        let page_size = self
-            .options
-            .max_page_size
-            .unwrap_or(chunk.len())
-            .min(chunk.len());
-
-        for (array, type_) in chunk
+        let mut leaves = vec![];
+        for ((array, type_), field) in chunk
             .arrays()
             .iter()
             .zip(schema_descriptor.fields().to_vec())
+            .zip(self.schema.fields.iter())
         {
-            let array = array.as_ref();
-            let nested = to_nested(array, &type_)?;
-            let types: Vec<parquet2::schema::types::PrimitiveType> = to_parquet_leaves(type_);
-            let leaf_arrays = to_leaves(array);
-            let length = array.len();
-
-            for ((leaf_array, nested), type_) in leaf_arrays
-                .iter()
-                .zip(nested.into_iter())
-                .zip(types.into_iter())
-            {
-                let start = self.writer.offset;
-                let leaf_array = leaf_array.to_boxed();
-
-                let page_metas: Vec<PageMeta> = (0..length)
-                    .step_by(page_size)
-                    .map(|offset| {
-                        let length = if offset + page_size > length {
-                            length - offset
-                        } else {
-                            page_size
-                        };
-                        let mut sub_array = leaf_array.clone();
-                        let mut sub_nested = nested.clone();
-                        slice_parquet_array(sub_array.as_mut(), &mut sub_nested, offset, length);
-                        let page_start = self.writer.offset;
-                        write(
-                            &mut self.writer,
-                            sub_array.as_ref(),
-                            &sub_nested,
-                            type_.clone(),
-                            length,
-                            self.options.clone(),
-                            &mut self.scratch,
-                        )
-                        .unwrap();
-
-                        let page_end = self.writer.offset;
-                        let num_values = num_values(&sub_nested);
-                        PageMeta {
-                            length: (page_end - page_start),
-                            num_values: num_values as u64,
-                        }
+            let mut paths = leaf_paths(field).into_iter();
+            self.push_leaves(array.as_ref(), type_, field, &mut paths, &mut leaves)?;
+        }
+
+        leaves
+            .par_iter_mut()
+            .enumerate()
+            .try_for_each(|(leaf_index, leaf)| {
+                // the values of dictionary arrays are always written as the dictionary
+                if let PhysicalType::Dictionary(_) = leaf.array.data_type().to_physical_type() {
+                    let dictionary = ColumnDictionary::try_from_dictionary(leaf.array.as_ref())
+                        .map_err(|err| leaf.error(leaf_index, 0, err))?;
+                    leaf.dictionary = Some(dictionary);
+                } else if leaf.options.column_dictionary
+                    && matches!(
+                        leaf.options.forced_compression,
+                        None | Some(Compression::Dict)
+                    )
+                {
+                    leaf.dictionary = ColumnDictionary::try_new(leaf.array.as_ref());
+                }
+                Ok::<_, Error>(())
+            })?;
+
+        // the pages of all the leaf columns are encoded in parallel into separate buffers,
+        // then written in order, so the layout is the same as encoding them sequentially
+        let pages = leaves
+            .iter()
+            .enumerate()
+            .flat_map(|(leaf, encode_leaf)| {
+                (0..encode_leaf.length)
+                    .step_by(encode_leaf.page_size)
+                    .enumerate()
+                    .map(move |(page, offset)| (leaf, page, offset))
+            })
+            .collect::<Vec<_>>();
+        let encoded_pages = pages
+            .into_par_iter()
+            .map(|(leaf, page, offset)| {
+                let encode_leaf = &leaves[leaf];
+                encode_leaf
+                    .encode_page(offset)
+                    .map_err(|err| encode_leaf.error(leaf, page, err))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut encoded_pages = encoded_pages.into_iter();
+        for (leaf_index, encode_leaf) in leaves.iter().enumerate() {
+            let start = self.writer.offset;
+            // the dictionary is written before the pages of the column chunk
+            let dictionary = match &encode_leaf.dictionary {
+                Some(dictionary) => {
+                    self.writer
+                        .write_all(&dictionary.bytes)
+                        .map_err(|err| encode_leaf.error(leaf_index, 0, err.into()))?;
+                    Some(DictionaryMeta {
+                        offset: start,
+                        length: dictionary.bytes.len() as u64,
+                        checksum: encode_leaf
+                            .options
+                            .page_checksum
+                            .then(|| crc32c::crc32c(&dictionary.bytes)),
                     })
-                    .collect();
+                }
+                None => None,
+            };
+            let num_pages = (0..encode_leaf.length)
+                .step_by(encode_leaf.page_size)
+                .count();
 
+            let mut page_metas = Vec::with_capacity(num_pages);
+            for (page_index, page) in encoded_pages.by_ref().take(num_pages).enumerate() {
+                let page_start = self.writer.offset;
+                self.writer
+                    .write_all(&page.buffer)
+                    .map_err(|err| encode_leaf.error(leaf_index, page_index, err.into()))?;
+                page_metas.push(PageMeta {
+                    offset: page_start,
+                    length: page.buffer.len() as u64,
+                    num_values: page.num_values,
+                    statistics: page.statistics,
+                    checksum: page.checksum,
+                    dictionary: dictionary.clone(),
+                });
+            }
+
+            if leaf_index == self.metas.len() {
                 self.metas.push(ColumnMeta {
                     offset: start,
-                    pages: page_metas,
-                })
+                    statistics: Statistics::default(),
+                    pages: vec![],
+                });
+            }
+            let meta = &mut self.metas[leaf_index];
+            if meta.pages.is_empty() {
+                meta.offset = start;
+            }
+            for page_meta in page_metas {
+                meta.statistics
+                    .merge(&page_meta.statistics, encode_leaf.array.data_type());
+                meta.pages.push(page_meta);
             }
         }
 
@@ -119,10 +289,185 @@ impl<W: Write> NativeWriter<W> {
     }
 }
 
-/// Write a record batch to the writer, writing the message size before the message
-/// if the record batch is being written to a stream
-pub fn write_continuation<W: Write>(writer: &mut W, total_len: i32) -> Result<usize> {
-    writer.write_all(&CONTINUATION_MARKER)?;
-    writer.write_all(&total_len.to_le_bytes()[..])?;
-    Ok(8)
+impl<W: Write> NativeWriter<W> {
+    /// Pushes the leaf columns of a field to encode, the type ids of a union are a leaf
+    /// column followed by the leaf columns of each child.
+    fn push_leaves(
+        &self,
+        array: &dyn Array,
+        type_: ParquetType,
+        field: &Field,
+        paths: &mut impl Iterator<Item = String>,
+        leaves: &mut Vec<EncodeLeaf>,
+    ) -> Result<()> {
+        let length = array.len();
+        if let DataType::Union(children, _, _) = field.data_type().to_logical_type() {
+            let array = normalize_union(array.as_any().downcast_ref().unwrap());
+            let mut types = match type_ {
+                ParquetType::GroupType { fields, .. } => fields.into_iter(),
+                ParquetType::PrimitiveType(_) => unreachable!(),
+            };
+            let type_ = match types.next() {
+                Some(ParquetType::PrimitiveType(type_)) => type_,
+                _ => unreachable!(),
+            };
+            let path = paths.next().unwrap();
+            let options = self
+                .options
+                .for_column(&path, leaves.len(), array.data_type());
+            let page_size = page_size(&options, &array, length);
+            leaves.push(EncodeLeaf {
+                path,
+                options,
+                array: array.clone().boxed(),
+                nested: vec![Nested::Primitive(None, false, length)],
+                type_,
+                length,
+                page_size,
+                dictionary: None,
+            });
+            for ((child_array, child_type), child) in
+                array.fields().iter().zip(types).zip(children.iter())
+            {
+                self.push_leaves(child_array.as_ref(), child_type, child, paths, leaves)?;
+            }
+            return Ok(());
+        }
+
+        let nested = to_nested(array, &type_)?;
+        let types: Vec<PrimitiveType> = to_parquet_leaves(type_);
+        let leaf_arrays = to_leaves(array);
+
+        for (((leaf_array, nested), type_), path) in leaf_arrays
+            .iter()
+            .zip(nested.into_iter())
+            .zip(types.into_iter())
+            .zip(paths)
+        {
+            let mut nested = nested;
+            let leaf_array = normalize_dictionary_nulls(*leaf_array, &mut nested)
+                .unwrap_or_else(|| leaf_array.to_boxed());
+            let options = self
+                .options
+                .for_column(&path, leaves.len(), leaf_array.data_type());
+            let page_size = page_size(&options, leaf_array.as_ref(), length);
+            leaves.push(EncodeLeaf {
+                path,
+                options,
+                array: leaf_array,
+                nested,
+                type_,
+                length,
+                page_size,
+                dictionary: None,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Number of rows of each page of the leaf column, it respects both
+/// `max_page_size` and `max_page_bytes` of the [`WriteOptions`].
+fn page_size(options: &WriteOptions, leaf_array: &dyn Array, length: usize) -> usize {
+    let mut page_size = options.max_page_size.unwrap_or(length).min(length);
+    if let Some(max_page_bytes) = options.max_page_bytes {
+        let total_bytes = estimated_bytes_size(leaf_array);
+        if total_bytes > 0 {
+            let row_bytes = total_bytes as f64 / length as f64;
+            let rows = (max_page_bytes as f64 / row_bytes) as usize;
+            page_size = page_size.min(rows);
+        }
+    }
+    page_size.max(1)
+}
+
+/// The paths of the leaf columns of a field, in the same order as [`to_leaves`].
+fn leaf_paths(field: &Field) -> Vec<String> {
+    match field.data_type().to_logical_type() {
+        DataType::Struct(children) => children
+            .iter()
+            .flat_map(|child| {
+                leaf_paths(child)
+                    .into_iter()
+                    .map(|path| format!("{}.{path}", field.name))
+            })
+            .collect(),
+        // the type ids of a union are stored at the path of the union
+        DataType::Union(children, _, _) => std::iter::once(field.name.clone())
+            .chain(children.iter().flat_map(|child| {
+                leaf_paths(child)
+                    .into_iter()
+                    .map(|path| format!("{}.{path}", field.name))
+            }))
+            .collect(),
+        DataType::List(inner)
+        | DataType::LargeList(inner)
+        | DataType::FixedSizeList(inner, _)
+        | DataType::Map(inner, _) => leaf_paths(inner)
+            .into_iter()
+            .map(|path| format!("{}.{path}", field.name))
+            .collect(),
+        _ => vec![field.name.clone()],
+    }
+}
+
+/// A leaf column of a [`Chunk`] to encode.
+struct EncodeLeaf {
+    path: String,
+    options: WriteOptions,
+    array: Box<dyn Array>,
+    nested: Vec<Nested>,
+    type_: PrimitiveType,
+    length: usize,
+    page_size: usize,
+    dictionary: Option<ColumnDictionary>,
+}
+
+/// A page encoded into its own buffer.
+struct EncodedPage {
+    buffer: Vec<u8>,
+    num_values: u64,
+    statistics: Statistics,
+    checksum: Option<u32>,
+}
+
+impl EncodeLeaf {
+    /// Adds the context of the column and page to an error.
+    fn error(&self, leaf_index: usize, page_index: usize, err: Error) -> Error {
+        Error::External(
+            format!(
+                "Failed to write page {page_index} of column {} (leaf column {leaf_index})",
+                self.path
+            ),
+            Box::new(err),
+        )
+    }
+
+    /// Encodes the page of rows `[offset, offset + page_size)`.
+    fn encode_page(&self, offset: usize) -> Result<EncodedPage> {
+        let length = self.page_size.min(self.length - offset);
+        let mut sub_array = self.array.clone();
+        let mut sub_nested = self.nested.clone();
+        slice_parquet_array(sub_array.as_mut(), &mut sub_nested, offset, length);
+
+        let mut buffer = vec![];
+        let mut scratch = vec![];
+        write_page(
+            &mut buffer,
+            sub_array.as_ref(),
+            &sub_nested,
+            self.type_.clone(),
+            length,
+            self.options.clone(),
+            self.dictionary.as_ref(),
+            &mut scratch,
+        )?;
+        let checksum = self.options.page_checksum.then(|| crc32c::crc32c(&buffer));
+        Ok(EncodedPage {
+            buffer,
+            checksum,
+            num_values: num_values(&sub_nested) as u64,
+            statistics: Statistics::from_array(sub_array.as_ref()),
+        })
+    }
 }
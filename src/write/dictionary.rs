@@ -0,0 +1,246 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::io::Write;
+
+use arrow::array::{
+    Array, BinaryArray, DictionaryArray, FixedSizeBinaryArray, PrimitiveArray, Utf8Array,
+};
+use arrow::bitmap::Bitmap;
+use arrow::datatypes::{PhysicalType, PrimitiveType};
+use arrow::error::{Error, Result};
+use arrow::io::parquet::write::Nested;
+use arrow::types::{NativeType, Offset};
+
+use crate::compression::integer::compress_integer;
+use crate::compression::Compression;
+use crate::variant::is_variant;
+use crate::{with_match_dictionary_key_type, with_match_primitive_type};
+
+use super::WriteOptions;
+
+// same as the `Dict` compression of a page
+const MIN_DICT_RATIO: usize = 3;
+
+/// A dictionary of the values of a leaf column chunk, shared by all its pages.
+///
+/// The dictionary is written once before the pages, in the same layout as the values
+/// at the end of a `Dict` page. The pages are `Dict` pages holding only the indices,
+/// the reader decodes the dictionary once and shares it with the pages.
+pub(crate) struct ColumnDictionary {
+    index: HashMap<Vec<u8>, u32>,
+    /// The number of values followed by the values.
+    pub(crate) bytes: Vec<u8>,
+}
+
+impl ColumnDictionary {
+    /// Builds the dictionary of a leaf array, returns `None` if the type isn't supported
+    /// or there are too many distinct values.
+    pub(crate) fn try_new(array: &dyn Array) -> Option<Self> {
+        let (values, width) = leaf_values(array)?;
+
+        let mut index: HashMap<&[u8], u32> = HashMap::new();
+        let mut sets = vec![];
+        let mut count = 0;
+        for value in values.into_iter().flatten() {
+            count += 1;
+            index.entry(value).or_insert_with(|| {
+                sets.push(value);
+                sets.len() as u32 - 1
+            });
+        }
+        if count == 0 || sets.len() * MIN_DICT_RATIO >= count {
+            return None;
+        }
+
+        let bytes = encode_values(sets.into_iter().map(Some), width);
+        Some(Self {
+            index: index
+                .into_iter()
+                .map(|(value, i)| (value.to_vec(), i))
+                .collect(),
+            bytes,
+        })
+    }
+
+    /// Builds the dictionary of the values of a [`DictionaryArray`], its keys are written
+    /// as the indices of the pages.
+    pub(crate) fn try_from_dictionary(array: &dyn Array) -> Result<Self> {
+        let values = dictionary_values(array);
+        let (values, width) = leaf_values(values).ok_or_else(|| {
+            Error::NotYetImplemented(format!(
+                "Writing dictionaries of values of type {:?}",
+                values.data_type()
+            ))
+        })?;
+        Ok(Self {
+            index: HashMap::new(),
+            bytes: encode_values(values.into_iter(), width),
+        })
+    }
+
+    /// Writes the values of a page of the column chunk as a `Dict` body holding only
+    /// the indices into the dictionary, the nulls are written as the first index.
+    pub(crate) fn write_page<W: Write>(
+        &self,
+        w: &mut W,
+        array: &dyn Array,
+        write_options: WriteOptions,
+        scratch: &mut Vec<u8>,
+    ) -> Result<()> {
+        let indices = match array.data_type().to_physical_type() {
+            PhysicalType::Dictionary(key_type) => with_match_dictionary_key_type!(key_type, |$K| {
+                let array: &DictionaryArray<$K> = array.as_any().downcast_ref().unwrap();
+                array
+                    .keys_iter()
+                    .map(|key| key.unwrap_or_default() as u32)
+                    .collect::<Vec<_>>()
+            }),
+            _ => {
+                let (values, _) = leaf_values(array).unwrap();
+                values
+                    .iter()
+                    .map(|value| value.map_or(0, |value| self.index[value]))
+                    .collect::<Vec<_>>()
+            }
+        };
+        let uncompressed_size = indices.len() * std::mem::size_of::<u32>();
+
+        scratch.clear();
+        let mut write_options = write_options;
+        write_options.forbidden_compressions.push(Compression::Dict);
+        write_options.forced_compression = None;
+        compress_integer(&PrimitiveArray::from_vec(indices), write_options, scratch)?;
+
+        w.write_all(&[u8::from(Compression::Dict)])?;
+        w.write_all(&(scratch.len() as u32).to_le_bytes())?;
+        w.write_all(&(uncompressed_size as u32).to_le_bytes())?;
+        w.write_all(scratch)?;
+        Ok(())
+    }
+}
+
+/// Sets the keys of a [`DictionaryArray`] pointing to null values as nulls, as only the
+/// validity of the keys is written. The validity of the leaf of `nested` is updated too.
+/// Returns `None` if the array isn't a dictionary or has no null values.
+pub(crate) fn normalize_dictionary_nulls(
+    array: &dyn Array,
+    nested: &mut [Nested],
+) -> Option<Box<dyn Array>> {
+    let key_type = match array.data_type().to_physical_type() {
+        PhysicalType::Dictionary(key_type) => key_type,
+        _ => return None,
+    };
+    with_match_dictionary_key_type!(key_type, |$K| {
+        let array: &DictionaryArray<$K> = array.as_any().downcast_ref().unwrap();
+        let values = array.values();
+        if values.null_count() == 0 {
+            return None;
+        }
+        let validity = array
+            .keys_iter()
+            .map(|key| key.map_or(false, |key| values.is_valid(key)))
+            .collect::<Bitmap>();
+        if let Some(Nested::Primitive(leaf_validity, _, _)) = nested.last_mut() {
+            *leaf_validity = Some(validity.clone());
+        }
+        let keys = array.keys().clone().with_validity(Some(validity));
+        let array =
+            DictionaryArray::<$K>::try_new(array.data_type().clone(), keys, values.clone()).ok()?;
+        Some(array.boxed())
+    })
+}
+
+fn dictionary_values(array: &dyn Array) -> &dyn Array {
+    match array.data_type().to_physical_type() {
+        PhysicalType::Dictionary(key_type) => with_match_dictionary_key_type!(key_type, |$K| {
+            let array: &DictionaryArray<$K> = array.as_any().downcast_ref().unwrap();
+            array.values().as_ref()
+        }),
+        _ => unreachable!(),
+    }
+}
+
+/// Encodes the number of values followed by the values, the nulls are written as
+/// zeroed or empty values.
+fn encode_values<'a>(
+    values: impl Iterator<Item = Option<&'a [u8]>>,
+    width: Option<usize>,
+) -> Vec<u8> {
+    let mut count = 0u32;
+    let mut bytes = vec![0u8; 4];
+    for value in values {
+        count += 1;
+        match (value, width) {
+            (Some(value), None) => {
+                bytes.extend_from_slice(&(value.len() as u64).to_le_bytes());
+                bytes.extend_from_slice(value);
+            }
+            (Some(value), Some(_)) => bytes.extend_from_slice(value),
+            (None, None) => bytes.extend_from_slice(&0u64.to_le_bytes()),
+            (None, Some(width)) => bytes.resize(bytes.len() + width, 0),
+        }
+    }
+    bytes[..4].copy_from_slice(&count.to_le_bytes());
+    bytes
+}
+
+// the values of a leaf array, with the size of each value or `None` for binary values
+type LeafValues<'a> = (Vec<Option<&'a [u8]>>, Option<usize>);
+
+/// The values of a leaf array as little-endian bytes.
+pub(crate) fn leaf_values(array: &dyn Array) -> Option<LeafValues> {
+    if is_variant(array.data_type()) {
+        return None;
+    }
+    match array.data_type().to_physical_type() {
+        PhysicalType::Primitive(PrimitiveType::DaysMs | PrimitiveType::MonthDayNano) => None,
+        PhysicalType::Primitive(primitive) => with_match_primitive_type!(primitive, |$T| {
+            Some((primitive_values::<$T>(array), Some(std::mem::size_of::<$T>())))
+        }),
+        PhysicalType::Binary => Some((binary_values::<i32>(array), None)),
+        PhysicalType::LargeBinary => Some((binary_values::<i64>(array), None)),
+        PhysicalType::Utf8 => Some((utf8_values::<i32>(array), None)),
+        PhysicalType::LargeUtf8 => Some((utf8_values::<i64>(array), None)),
+        PhysicalType::FixedSizeBinary => {
+            let array: &FixedSizeBinaryArray = array.as_any().downcast_ref().unwrap();
+            Some((array.iter().collect(), Some(array.size())))
+        }
+        _ => None,
+    }
+}
+
+fn primitive_values<T: NativeType>(array: &dyn Array) -> Vec<Option<&[u8]>> {
+    let array: &PrimitiveArray<T> = array.as_any().downcast_ref().unwrap();
+    let values: &[u8] = bytemuck::cast_slice(array.values().as_slice());
+    values
+        .chunks_exact(std::mem::size_of::<T>())
+        .enumerate()
+        .map(|(i, value)| array.is_valid(i).then_some(value))
+        .collect()
+}
+
+fn binary_values<O: Offset>(array: &dyn Array) -> Vec<Option<&[u8]>> {
+    let array: &BinaryArray<O> = array.as_any().downcast_ref().unwrap();
+    array.iter().collect()
+}
+
+fn utf8_values<O: Offset>(array: &dyn Array) -> Vec<Option<&[u8]>> {
+    let array: &Utf8Array<O> = array.as_any().downcast_ref().unwrap();
+    array.iter().map(|v| v.map(|v| v.as_bytes())).collect()
+}
@@ -18,10 +18,16 @@
 pub(crate) mod binary;
 pub(crate) mod boolean;
 pub(crate) mod common;
+pub(crate) mod dictionary;
+pub(crate) mod fixed_binary;
 pub(crate) mod primitive;
+mod schema;
 mod serialize;
+pub(crate) mod union;
+pub(crate) mod variant;
 pub(crate) mod writer;
 
-pub use common::WriteOptions;
+pub use common::{ColumnSelector, ColumnWriteOptions, WriteOptions};
+pub use schema::{n_columns, to_parquet_schema};
 pub use serialize::write;
 pub use writer::NativeWriter;
@@ -19,11 +19,12 @@ use std::io::Write;
 
 use arrow::array::Array;
 use arrow::error::Result;
-use arrow::types::i256;
+use arrow::types::{days_ms, f16, i256, months_days_ns};
 use arrow::{array::PrimitiveArray, types::NativeType};
 
 use crate::compression::double::compress_double;
 use crate::compression::integer::compress_integer;
+use crate::compression::interval::IntervalType;
 
 use super::WriteOptions;
 
@@ -76,6 +77,11 @@ pub(crate) fn write_primitive<T: NativeType, W: Write>(
             let array: &PrimitiveArray<i256> = array.as_any().downcast_ref().unwrap();
             compress_integer(array, write_options, scratch)?;
         }
+        arrow::types::PrimitiveType::Float16 => {
+            let array: &PrimitiveArray<f16> = array.as_any().downcast_ref().unwrap();
+
+            compress_double(array, write_options, scratch)?;
+        }
         arrow::types::PrimitiveType::Float32 => {
             let array: &PrimitiveArray<f32> = array.as_any().downcast_ref().unwrap();
 
@@ -86,10 +92,14 @@ pub(crate) fn write_primitive<T: NativeType, W: Write>(
 
             compress_double(array, write_options, scratch)?;
         }
-
-        arrow::types::PrimitiveType::Float16 => unimplemented!(),
-        arrow::types::PrimitiveType::DaysMs => unimplemented!(),
-        arrow::types::PrimitiveType::MonthDayNano => unimplemented!(),
+        arrow::types::PrimitiveType::DaysMs => {
+            let array: &PrimitiveArray<days_ms> = array.as_any().downcast_ref().unwrap();
+            days_ms::compress(array, write_options, scratch)?;
+        }
+        arrow::types::PrimitiveType::MonthDayNano => {
+            let array: &PrimitiveArray<months_days_ns> = array.as_any().downcast_ref().unwrap();
+            months_days_ns::compress(array, write_options, scratch)?;
+        }
     }
     w.write_all(scratch.as_slice())?;
     Ok(())
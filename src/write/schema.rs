@@ -0,0 +1,105 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::error::{Error, Result};
+use arrow::io::parquet::write::SchemaDescriptor;
+
+/// Converts a [`Schema`] to the parquet schema of its leaf columns, which gives
+/// the levels of the nested columns.
+///
+/// Only the nesting and the nullability matter for the levels, the leaf types parquet
+/// doesn't support, such as `Float16`, are replaced by supported types.
+/// A union is a required group of its `Int8` type ids followed by its children, so
+/// it adds no levels. Unions are only supported as top level fields or union children.
+pub fn to_parquet_schema(schema: &Schema) -> Result<SchemaDescriptor> {
+    let fields = schema
+        .fields
+        .iter()
+        .map(|field| level_field(field, true))
+        .collect::<Result<Vec<_>>>()?;
+    arrow::io::parquet::write::to_parquet_schema(&Schema::from(fields))
+}
+
+/// Returns the number of leaf columns of a field of `data_type`, the type ids of a
+/// union are a leaf column followed by the leaf columns of its children.
+pub fn n_columns(data_type: &DataType) -> usize {
+    match data_type.to_logical_type() {
+        DataType::Union(fields, _, _) => {
+            1 + fields
+                .iter()
+                .map(|f| n_columns(&f.data_type))
+                .sum::<usize>()
+        }
+        DataType::Struct(fields) => fields.iter().map(|f| n_columns(&f.data_type)).sum(),
+        DataType::List(inner)
+        | DataType::LargeList(inner)
+        | DataType::FixedSizeList(inner, _)
+        | DataType::Map(inner, _) => n_columns(&inner.data_type),
+        _ => 1,
+    }
+}
+
+fn level_field(field: &Field, allow_union: bool) -> Result<Field> {
+    if let DataType::Union(children, _, _) = field.data_type().to_logical_type() {
+        if !allow_union {
+            return Err(Error::NotYetImplemented(format!(
+                "Union field {} nested in a struct, list or map",
+                field.name
+            )));
+        }
+        let mut fields = vec![Field::new("types", DataType::Int8, false)];
+        for child in children {
+            fields.push(level_field(child, true)?);
+        }
+        return Ok(Field::new(
+            field.name.clone(),
+            DataType::Struct(fields),
+            false,
+        ));
+    }
+    Ok(Field::new(
+        field.name.clone(),
+        level_type(field.data_type())?,
+        field.is_nullable,
+    ))
+}
+
+fn level_type(data_type: &DataType) -> Result<DataType> {
+    Ok(match data_type {
+        DataType::Float16 => DataType::Float32,
+        DataType::Struct(fields) => DataType::Struct(
+            fields
+                .iter()
+                .map(|f| level_field(f, false))
+                .collect::<Result<_>>()?,
+        ),
+        DataType::List(inner) => DataType::List(Box::new(level_field(inner, false)?)),
+        DataType::LargeList(inner) => DataType::LargeList(Box::new(level_field(inner, false)?)),
+        DataType::FixedSizeList(inner, size) => {
+            DataType::FixedSizeList(Box::new(level_field(inner, false)?), *size)
+        }
+        DataType::Map(inner, sorted) => {
+            DataType::Map(Box::new(level_field(inner, false)?), *sorted)
+        }
+        DataType::Dictionary(key, values, sorted) => {
+            DataType::Dictionary(*key, Box::new(level_type(values)?), *sorted)
+        }
+        DataType::Extension(_, inner, _) => level_type(inner)?,
+        other => other.clone(),
+    })
+}
@@ -21,7 +21,7 @@ use arrow::{
     array::*,
     bitmap::Bitmap,
     datatypes::{DataType, PhysicalType},
-    error::Result,
+    error::{Error, Result},
     io::parquet::write::{write_def_levels, write_rep_and_def, Nested, Version},
 };
 use parquet2::schema::{
@@ -29,7 +29,16 @@ use parquet2::schema::{
     Repetition,
 };
 
-use super::{boolean::write_bitmap, primitive::write_primitive, WriteOptions};
+use super::{
+    boolean::write_bitmap,
+    dictionary::{normalize_dictionary_nulls, ColumnDictionary},
+    fixed_binary::write_fixed_binary,
+    primitive::write_primitive,
+    union::write_union,
+    variant::write_variant,
+    WriteOptions,
+};
+use crate::variant::is_variant;
 use crate::{with_match_primitive_type, write::binary::write_binary};
 
 /// Writes an [`Array`] to the file
@@ -41,24 +50,64 @@ pub fn write<W: Write>(
     length: usize,
     write_options: WriteOptions,
     scratch: &mut Vec<u8>,
+) -> Result<()> {
+    let mut nested = nested.to_vec();
+    let normalized = normalize_dictionary_nulls(array, &mut nested);
+    write_page(
+        w,
+        normalized.as_deref().unwrap_or(array),
+        &nested,
+        type_,
+        length,
+        write_options,
+        None,
+        scratch,
+    )
+}
+
+/// Writes an [`Array`] to the file, the values are written as the indices of the
+/// `dictionary` of the column chunk if it's some.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn write_page<W: Write>(
+    w: &mut W,
+    array: &dyn Array,
+    nested: &[Nested],
+    type_: PrimitiveType,
+    length: usize,
+    write_options: WriteOptions,
+    dictionary: Option<&ColumnDictionary>,
+    scratch: &mut Vec<u8>,
 ) -> Result<()> {
     if nested.len() == 1 {
-        return write_simple(w, array, type_, write_options, scratch);
+        return write_simple(w, array, type_, write_options, dictionary, scratch);
     }
-    write_nested(w, array, nested, length, write_options, scratch)
+    write_nested(w, array, nested, length, write_options, dictionary, scratch)
 }
 
 /// Writes an [`Array`] to `arrow_data`
-pub fn write_simple<W: Write>(
+pub(crate) fn write_simple<W: Write>(
     w: &mut W,
     array: &dyn Array,
     type_: PrimitiveType,
     write_options: WriteOptions,
+    dictionary: Option<&ColumnDictionary>,
     scratch: &mut Vec<u8>,
 ) -> Result<()> {
     use PhysicalType::*;
 
     let is_optional = is_nullable(&type_.field_info);
+    if let Some(dictionary) = dictionary {
+        if is_optional {
+            write_validity::<W>(w, is_optional, array.validity(), array.len(), scratch)?;
+        }
+        return dictionary.write_page(w, array, write_options, scratch);
+    }
+    if is_variant(array.data_type()) {
+        if is_optional {
+            write_validity::<W>(w, is_optional, array.validity(), array.len(), scratch)?;
+        }
+        return write_variant_array(w, array, write_options, scratch);
+    }
     match array.data_type().to_physical_type() {
         Null => {}
         Boolean => {
@@ -119,30 +168,56 @@ pub fn write_simple<W: Write>(
             );
             write_binary::<i64, W>(w, &binary_array, write_options, scratch)?;
         }
+        FixedSizeBinary => {
+            let array: &FixedSizeBinaryArray = array.as_any().downcast_ref().unwrap();
+            if is_optional {
+                write_validity::<W>(w, is_optional, array.validity(), array.len(), scratch)?;
+            }
+            write_fixed_binary::<W>(w, array, write_options, scratch)?;
+        }
+        Dictionary(_) => {
+            if is_optional {
+                write_validity::<W>(w, is_optional, array.validity(), array.len(), scratch)?;
+            }
+            write_dictionary(w, array, write_options, scratch)?;
+        }
         Struct => unreachable!(),
         List => unreachable!(),
         FixedSizeList => unreachable!(),
-        Dictionary(_key_type) => unreachable!(),
-        Union => unreachable!(),
+        Union => {
+            let array: &UnionArray = array.as_any().downcast_ref().unwrap();
+            write_union::<W>(w, array, write_options, scratch)?;
+        }
         Map => unreachable!(),
-        _ => todo!(),
+        other => {
+            return Err(Error::NotYetImplemented(format!(
+                "Writing physical type {other:?} is not supported yet"
+            )))
+        }
     }
 
     Ok(())
 }
 
 /// Writes a nested [`Array`] to `arrow_data`
-pub fn write_nested<W: Write>(
+pub(crate) fn write_nested<W: Write>(
     w: &mut W,
     array: &dyn Array,
     nested: &[Nested],
     length: usize,
     write_options: WriteOptions,
+    dictionary: Option<&ColumnDictionary>,
     scratch: &mut Vec<u8>,
 ) -> Result<()> {
     write_nested_validity::<W>(w, nested, length, scratch)?;
 
     scratch.clear();
+    if let Some(dictionary) = dictionary {
+        return dictionary.write_page(w, array, write_options, scratch);
+    }
+    if is_variant(array.data_type()) {
+        return write_variant_array(w, array, write_options, scratch);
+    }
 
     use PhysicalType::*;
     match array.data_type().to_physical_type() {
@@ -185,15 +260,54 @@ pub fn write_nested<W: Write>(
 
             write_binary::<i64, W>(w, &binary_array, write_options, scratch)?;
         }
+        FixedSizeBinary => {
+            let array: &FixedSizeBinaryArray = array.as_any().downcast_ref().unwrap();
+            write_fixed_binary::<W>(w, array, write_options, scratch)?;
+        }
+        Dictionary(_) => write_dictionary(w, array, write_options, scratch)?,
         Struct => unreachable!(),
         List => unreachable!(),
         FixedSizeList => unreachable!(),
-        Dictionary(_key_type) => unreachable!(),
         Union => unreachable!(),
         Map => unreachable!(),
-        _ => todo!(),
+        other => {
+            return Err(Error::NotYetImplemented(format!(
+                "Writing physical type {other:?} is not supported yet"
+            )))
+        }
+    }
+
+    Ok(())
+}
+
+fn write_variant_array<W: Write>(
+    w: &mut W,
+    array: &dyn Array,
+    write_options: WriteOptions,
+    scratch: &mut Vec<u8>,
+) -> Result<()> {
+    match array.data_type().to_physical_type() {
+        PhysicalType::Utf8 => {
+            let array: &Utf8Array<i32> = array.as_any().downcast_ref().unwrap();
+            write_variant::<i32, W>(w, array, write_options, scratch)
+        }
+        _ => {
+            let array: &Utf8Array<i64> = array.as_any().downcast_ref().unwrap();
+            write_variant::<i64, W>(w, array, write_options, scratch)
+        }
     }
+}
 
+// the values of a dictionary array not shared by the column chunk are written in the page
+fn write_dictionary<W: Write>(
+    w: &mut W,
+    array: &dyn Array,
+    write_options: WriteOptions,
+    scratch: &mut Vec<u8>,
+) -> Result<()> {
+    let dictionary = ColumnDictionary::try_from_dictionary(array)?;
+    dictionary.write_page(w, array, write_options, scratch)?;
+    w.write_all(&dictionary.bytes)?;
     Ok(())
 }
 
@@ -0,0 +1,115 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::io::Write;
+
+use arrow::array::growable::make_growable;
+use arrow::array::{Array, PrimitiveArray, UnionArray};
+use arrow::error::Result;
+
+use crate::compression::integer::compress_integer;
+
+use super::WriteOptions;
+
+/// Writes the type ids of the rows of a union, followed by the offsets of a dense union.
+///
+/// The `array` must be normalized by [`normalize_union`], the offsets are written
+/// relative to the first row of each type in the page.
+pub(crate) fn write_union<W: Write>(
+    w: &mut W,
+    array: &UnionArray,
+    write_options: WriteOptions,
+    scratch: &mut Vec<u8>,
+) -> Result<()> {
+    scratch.clear();
+    let types = PrimitiveArray::<i8>::from_vec(array.types().to_vec());
+    compress_integer(&types, write_options.clone(), scratch)?;
+    w.write_all(scratch.as_slice())?;
+
+    if array.offsets().is_some() {
+        let mut counts = vec![0i32; array.fields().len()];
+        let offsets = (0..array.len())
+            .map(|i| {
+                let (field, _) = array.index(i);
+                counts[field] += 1;
+                counts[field] - 1
+            })
+            .collect::<Vec<_>>();
+        scratch.clear();
+        compress_integer(&PrimitiveArray::from_vec(offsets), write_options, scratch)?;
+        w.write_all(scratch.as_slice())?;
+    }
+    Ok(())
+}
+
+/// Returns the union with the children holding only its rows, in order.
+///
+/// The children of a sparse union are sliced to its rows, the children of a dense union
+/// are compacted to the slots of its rows, so the offset of a row is the number of
+/// previous rows of the same type.
+pub(crate) fn normalize_union(array: &UnionArray) -> UnionArray {
+    let length = array.len();
+    let (fields, offsets) = match array.offsets() {
+        None => {
+            let offset = if length == 0 { 0 } else { array.index(0).1 };
+            let fields = array
+                .fields()
+                .iter()
+                .map(|field| field.sliced(offset, length))
+                .collect();
+            (fields, None)
+        }
+        Some(_) => {
+            let mut slots = vec![vec![]; array.fields().len()];
+            let mut offsets = Vec::with_capacity(length);
+            for i in 0..length {
+                let (field, slot) = array.index(i);
+                offsets.push(slots[field].len() as i32);
+                slots[field].push(slot);
+            }
+            let fields = array
+                .fields()
+                .iter()
+                .zip(slots.iter())
+                .map(|(field, slots)| take_slots(field.as_ref(), slots))
+                .collect();
+            (fields, Some(offsets.into()))
+        }
+    };
+    UnionArray::new(
+        array.data_type().clone(),
+        array.types().clone(),
+        fields,
+        offsets,
+    )
+}
+
+// the runs of consecutive slots are extended at once
+fn take_slots(array: &dyn Array, slots: &[usize]) -> Box<dyn Array> {
+    let mut growable = make_growable(&[array], array.null_count() > 0, slots.len());
+    let mut i = 0;
+    while i < slots.len() {
+        let start = slots[i];
+        let mut len = 1;
+        while i + len < slots.len() && slots[i + len] == start + len {
+            len += 1;
+        }
+        growable.extend(0, start, len);
+        i += len;
+    }
+    growable.as_box()
+}
@@ -0,0 +1,179 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::io::Write;
+
+use arrow::array::{BinaryArray, BooleanArray, PrimitiveArray, Utf8Array};
+use arrow::bitmap::MutableBitmap;
+use arrow::datatypes::DataType;
+use arrow::error::{Error, Result};
+use arrow::types::Offset;
+use serde_json::Value;
+
+use crate::compression::binary::compress_binary;
+use crate::compression::boolean::compress_boolean;
+use crate::compression::double::compress_double;
+use crate::compression::integer::compress_integer;
+use crate::variant::{take_path, visit_scalars, PathValue, ShreddedType};
+
+use super::WriteOptions;
+
+/// The minimum ratio of the non-null rows of a page having a scalar of the same type at
+/// a path for the path to be shredded.
+const MIN_SHRED_RATIO: f64 = 0.5;
+/// The maximum number of shredded paths of a page.
+const MAX_SHREDDED_PATHS: usize = 64;
+
+/// Writes the JSON values of a variant array, the frequent paths are shredded into
+/// sub-columns followed by the remainder of the values.
+///
+/// Each shredded path is written as its keys and type, then the byte length of its
+/// sub-column: the rows with a scalar of the type, the rows with another value at the
+/// path, and the scalars. The remainders of the values are written as a binary column.
+pub(crate) fn write_variant<O: Offset, W: Write>(
+    w: &mut W,
+    array: &Utf8Array<O>,
+    write_options: WriteOptions,
+    scratch: &mut Vec<u8>,
+) -> Result<()> {
+    let mut values = array
+        .iter()
+        .enumerate()
+        .map(|(i, value)| {
+            value
+                .map(|value| {
+                    serde_json::from_str::<Value>(value).map_err(|err| {
+                        Error::InvalidArgumentError(format!(
+                            "Invalid JSON value of variant row {i}: {err}"
+                        ))
+                    })
+                })
+                .transpose()
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let paths = shredded_paths(&values);
+    w.write_all(&(paths.len() as u32).to_le_bytes())?;
+    let mut sub_options = write_options.clone();
+    sub_options.forced_compression = None;
+    for (path, type_) in paths {
+        let mut shredded = MutableBitmap::with_capacity(values.len());
+        let mut other = MutableBitmap::with_capacity(values.len());
+        let mut scalars = vec![];
+        for value in values.iter_mut() {
+            let path_value = match value {
+                Some(value) => take_path(value, &path, type_),
+                None => PathValue::Missing,
+            };
+            shredded.push(matches!(path_value, PathValue::Shredded(_)));
+            other.push(matches!(path_value, PathValue::Other));
+            if let PathValue::Shredded(scalar) = path_value {
+                scalars.push(scalar);
+            }
+        }
+
+        w.write_all(&(path.len() as u32).to_le_bytes())?;
+        for key in path.iter() {
+            w.write_all(&(key.len() as u32).to_le_bytes())?;
+            w.write_all(key.as_bytes())?;
+        }
+        w.write_all(&[type_ as u8])?;
+
+        scratch.clear();
+        for bitmap in [shredded, other] {
+            let array = BooleanArray::new(DataType::Boolean, bitmap.into(), None);
+            compress_boolean(&array, scratch, sub_options.clone())?;
+        }
+        compress_scalars(&scalars, type_, sub_options.clone(), scratch)?;
+        w.write_all(&(scratch.len() as u32).to_le_bytes())?;
+        w.write_all(scratch)?;
+    }
+
+    let remainders = values
+        .iter()
+        .map(|value| match value {
+            Some(value) => serde_json::to_vec(value).unwrap(),
+            None => vec![],
+        })
+        .collect::<Vec<_>>();
+    let remainders = BinaryArray::<i64>::from_iter_values(remainders.iter());
+    scratch.clear();
+    compress_binary(&remainders, scratch, write_options)?;
+    w.write_all(scratch)?;
+    Ok(())
+}
+
+/// Returns the paths of the page to shred, with the type of their scalars.
+fn shredded_paths(values: &[Option<Value>]) -> Vec<(Vec<String>, ShreddedType)> {
+    let mut counts: HashMap<(Vec<String>, ShreddedType), usize> = HashMap::new();
+    let mut path = vec![];
+    for value in values.iter().flatten() {
+        visit_scalars(value, &mut path, &mut |path, type_| {
+            *counts.entry((path.to_vec(), type_)).or_default() += 1;
+        });
+    }
+
+    let min_count = (values.iter().flatten().count() as f64 * MIN_SHRED_RATIO).max(1.0);
+    let mut paths = counts
+        .into_iter()
+        .filter(|(_, count)| *count as f64 >= min_count)
+        .collect::<Vec<_>>();
+    // the most frequent paths are chosen, a path is shredded with a single type
+    paths.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then_with(|| a.cmp(b)));
+    let mut chosen: Vec<(Vec<String>, ShreddedType)> = vec![];
+    for (path, _) in paths {
+        if chosen.len() == MAX_SHREDDED_PATHS {
+            break;
+        }
+        if chosen.iter().all(|(p, _)| *p != path.0) {
+            chosen.push(path);
+        }
+    }
+    chosen.sort();
+    chosen
+}
+
+fn compress_scalars(
+    scalars: &[Value],
+    type_: ShreddedType,
+    write_options: WriteOptions,
+    buf: &mut Vec<u8>,
+) -> Result<()> {
+    match type_ {
+        ShreddedType::Int64 => {
+            let values = scalars.iter().map(|v| v.as_i64().unwrap()).collect();
+            compress_integer(&PrimitiveArray::<i64>::from_vec(values), write_options, buf)
+        }
+        ShreddedType::Float64 => {
+            let values = scalars.iter().map(|v| v.as_f64().unwrap()).collect();
+            compress_double(&PrimitiveArray::<f64>::from_vec(values), write_options, buf)
+        }
+        ShreddedType::String => {
+            let array =
+                BinaryArray::<i64>::from_iter_values(scalars.iter().map(|v| v.as_str().unwrap()));
+            compress_binary(&array, buf, write_options)
+        }
+        ShreddedType::Boolean => {
+            let values = scalars
+                .iter()
+                .map(|v| v.as_bool().unwrap())
+                .collect::<Vec<_>>();
+            compress_boolean(&BooleanArray::from_slice(values), buf, write_options)
+        }
+    }
+}
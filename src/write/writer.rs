@@ -17,18 +17,17 @@
 
 use std::io::Write;
 
-use super::common::write_continuation;
-use super::{super::ARROW_MAGIC, common::WriteOptions};
-use arrow::datatypes::Schema;
+use super::common::WriteOptions;
+use arrow::datatypes::{Metadata, Schema};
 
 use arrow::array::Array;
 use arrow::chunk::Chunk;
 use arrow::error::{Error, Result};
 use arrow::io::ipc::write::{default_ipc_fields, schema_to_bytes};
 
-use arrow::io::parquet::write::to_parquet_schema;
+use super::to_parquet_schema;
 
-use crate::ColumnMeta;
+use crate::{ColumnMeta, Statistics, FORMAT_VERSION, STRAWBOAT_MAGIC};
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub(crate) enum State {
@@ -36,6 +35,8 @@ pub(crate) enum State {
     Started,
     Written,
     Finished,
+    /// A previous write failed, the file may be half-written.
+    Poisoned,
 }
 
 /// Arrow file writer
@@ -50,7 +51,9 @@ pub struct NativeWriter<W: Write> {
     /// Record blocks that will be written as part of the strawboat footer
     pub metas: Vec<ColumnMeta>,
 
-    pub(crate) scratch: Vec<u8>,
+    /// Application metadata that will be written as part of the strawboat footer
+    pub(crate) key_value_metadata: Metadata,
+
     /// Whether the writer footer has been written, and the writer is finished
     pub(crate) state: State,
 }
@@ -75,11 +78,17 @@ impl<W: Write> NativeWriter<W> {
             options,
             schema,
             metas: Vec::with_capacity(num_cols),
-            scratch: Vec::with_capacity(0),
+            key_value_metadata: Metadata::new(),
             state: State::None,
         }
     }
 
+    /// Sets a key value pair of application metadata, it can be called at any time
+    /// before `finish` and replaces the previous value of the key.
+    pub fn set_key_value_metadata(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.key_value_metadata.insert(key.into(), value.into());
+    }
+
     /// Consumes itself into the inner writer
     pub fn into_inner(self) -> W {
         self.writer.w
@@ -94,31 +103,39 @@ impl<W: Write> NativeWriter<W> {
                 "The strawboat file can only be started once".to_string(),
             ));
         }
-        // write magic to header
-        self.writer.write_all(&ARROW_MAGIC[..])?;
-        // create an 8-byte boundary after the header
-        self.writer.write_all(&[0, 0])?;
+        // header = magic(6 bytes) + format version(2 bytes)
+        let header = [&STRAWBOAT_MAGIC[..], &FORMAT_VERSION.to_le_bytes()].concat();
+        if let Err(err) = self.writer.write_all(&header) {
+            self.state = State::Poisoned;
+            return Err(err.into());
+        }
 
         self.state = State::Started;
         Ok(())
     }
 
-    /// Writes [`Chunk`] to the file
+    /// Writes [`Chunk`] to the file, it can be called multiple times before `finish`,
+    /// the pages of each column will be appended to the previous ones.
     pub fn write(&mut self, chunk: &Chunk<Box<dyn Array>>) -> Result<()> {
-        if self.state == State::Written {
-            return Err(Error::OutOfSpec(
-                "The strawboat file can only accept one RowGroup in a single file".to_string(),
-            ));
-        }
-        if self.state != State::Started {
+        self.check_poisoned()?;
+        if self.state != State::Started && self.state != State::Written {
             return Err(Error::OutOfSpec(
                 "The strawboat file must be started before it can be written to. Call `start` before `write`".to_string(),
             ));
         }
-        assert_eq!(chunk.arrays().len(), self.schema.fields.len());
+        if chunk.arrays().len() != self.schema.fields.len() {
+            return Err(Error::InvalidArgumentError(format!(
+                "The chunk has {} columns, but the schema has {} fields",
+                chunk.arrays().len(),
+                self.schema.fields.len()
+            )));
+        }
 
         let schema_descriptor = to_parquet_schema(&self.schema)?;
-        self.encode_chunk(schema_descriptor, chunk)?;
+        if let Err(err) = self.encode_chunk(schema_descriptor, chunk) {
+            self.state = State::Poisoned;
+            return Err(err);
+        }
 
         self.state = State::Written;
         Ok(())
@@ -126,14 +143,40 @@ impl<W: Write> NativeWriter<W> {
 
     /// Write footer and closing tag, then mark the writer as done
     pub fn finish(&mut self) -> Result<()> {
+        self.check_poisoned()?;
         if self.state != State::Written {
             return Err(Error::OutOfSpec(
                 "The strawboat file must be written before it can be finished. Call `start` before `finish`".to_string(),
             ));
         }
+        if let Err(err) = self.write_footer() {
+            self.state = State::Poisoned;
+            return Err(err);
+        }
+        self.state = State::Finished;
+        Ok(())
+    }
+
+    /// Whether a previous write failed and the writer can't be used anymore.
+    pub fn is_poisoned(&self) -> bool {
+        self.state == State::Poisoned
+    }
+
+    fn check_poisoned(&self) -> Result<()> {
+        if self.is_poisoned() {
+            return Err(Error::InvalidArgumentError(
+                "The strawboat writer is poisoned by a previous failed write".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn write_footer(&mut self) -> Result<()> {
         // write footer
         // footer = schema(variable bytes) + column_meta(variable bytes)
-        // + schema size(4 bytes) + column_meta size(4bytes) + EOS(8 bytes)
+        // + key_value_metadata(variable bytes)
+        // + schema size(4 bytes) + column_meta size(4bytes) + key_value_metadata size(4 bytes)
+        // + format version(2 bytes) + magic(6 bytes)
         let schema_bytes = schema_to_bytes(&self.schema, &default_ipc_fields(&self.schema.fields));
         // write the schema, set the written bytes to the schema
         self.writer.write_all(&schema_bytes)?;
@@ -143,26 +186,52 @@ impl<W: Write> NativeWriter<W> {
             self.writer.write_all(&self.metas.len().to_le_bytes())?;
             for meta in &self.metas {
                 self.writer.write_all(&meta.offset.to_le_bytes())?;
+                write_statistics(&mut self.writer, &meta.statistics)?;
                 self.writer.write_all(&meta.pages.len().to_le_bytes())?;
 
                 for page in meta.pages.iter() {
+                    self.writer.write_all(&page.offset.to_le_bytes())?;
                     self.writer.write_all(&page.length.to_le_bytes())?;
                     self.writer.write_all(&page.num_values.to_le_bytes())?;
+                    write_statistics(&mut self.writer, &page.statistics)?;
+                    write_checksum(&mut self.writer, page.checksum)?;
+                    match &page.dictionary {
+                        Some(dictionary) => {
+                            self.writer.write_all(&[1])?;
+                            self.writer.write_all(&dictionary.offset.to_le_bytes())?;
+                            self.writer.write_all(&dictionary.length.to_le_bytes())?;
+                            write_checksum(&mut self.writer, dictionary.checksum)?;
+                        }
+                        None => self.writer.write_all(&[0])?,
+                    }
                 }
             }
         }
         let meta_end = self.writer.offset();
 
+        self.writer
+            .write_all(&self.key_value_metadata.len().to_le_bytes())?;
+        for (key, value) in &self.key_value_metadata {
+            for bytes in [key.as_bytes(), value.as_bytes()] {
+                self.writer.write_all(&(bytes.len() as u32).to_le_bytes())?;
+                self.writer.write_all(bytes)?;
+            }
+        }
+        let key_value_metadata_size = self.writer.offset() - meta_end;
+
         // 4 bytes for schema size
         let schema_size = schema_bytes.len();
         self.writer.write_all(&(schema_size as u32).to_le_bytes())?;
         // 4 bytes for meta_size
         self.writer
             .write_all(&((meta_end - meta_start) as u32).to_le_bytes())?;
-        // write EOS
-        write_continuation(&mut self.writer, 0)?;
+        // 4 bytes for key_value_metadata size
+        self.writer
+            .write_all(&(key_value_metadata_size as u32).to_le_bytes())?;
+        // format version and magic
+        self.writer.write_all(&FORMAT_VERSION.to_le_bytes())?;
+        self.writer.write_all(&STRAWBOAT_MAGIC)?;
         self.writer.flush()?;
-        self.state = State::Finished;
         Ok(())
     }
 
@@ -172,6 +241,34 @@ impl<W: Write> NativeWriter<W> {
     }
 }
 
+// statistics = null_count(8 bytes) + min value + max value
+// value = is_some(1 byte) + len(4 bytes) + bytes
+fn write_statistics<W: Write>(w: &mut W, statistics: &Statistics) -> Result<()> {
+    w.write_all(&statistics.null_count.to_le_bytes())?;
+    for value in [&statistics.min_value, &statistics.max_value] {
+        match value {
+            Some(value) => {
+                w.write_all(&[1])?;
+                w.write_all(&(value.len() as u32).to_le_bytes())?;
+                w.write_all(value)?;
+            }
+            None => w.write_all(&[0])?,
+        }
+    }
+    Ok(())
+}
+
+fn write_checksum<W: Write>(w: &mut W, checksum: Option<u32>) -> Result<()> {
+    match checksum {
+        Some(checksum) => {
+            w.write_all(&[1])?;
+            w.write_all(&checksum.to_le_bytes())?;
+        }
+        None => w.write_all(&[0])?,
+    }
+    Ok(())
+}
+
 pub struct OffsetWriter<W: Write> {
     pub w: W,
     pub offset: u64,
@@ -0,0 +1,893 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use arrow::{
+    array::{
+        Array, BooleanArray, DictionaryArray, Int32Array, Int64Array, ListArray, PrimitiveArray,
+        StructArray, UnionArray, Utf8Array,
+    },
+    chunk::Chunk,
+    compute::{cast::cast, concatenate::concatenate},
+    datatypes::{
+        DataType, Field, IntegerType, Metadata, PhysicalType, PrimitiveType, Schema, TimeUnit,
+        UnionMode,
+    },
+    error::Result,
+    offset::OffsetsBuffer,
+};
+use strawboat::{
+    read::NativeFileReader,
+    variant::{variant_data_type, variant_path},
+    write::{NativeWriter, WriteOptions},
+    CommonCompression, Compression,
+};
+
+const SIZE: usize = 5000;
+
+fn write_data() -> (Vec<u8>, Chunk<Box<dyn Array>>) {
+    let struct_fields = vec![
+        Field::new("x", DataType::Int64, false),
+        Field::new("y", DataType::Boolean, true),
+    ];
+    let chunk = Chunk::new(vec![
+        Box::new(Int64Array::from_vec((0..SIZE as i64).collect())) as Box<dyn Array>,
+        Box::new(Utf8Array::<i32>::from_iter_values(
+            (0..SIZE).map(|i| format!("{i:0>50}")),
+        )) as _,
+        Box::new(StructArray::new(
+            DataType::Struct(struct_fields.clone()),
+            vec![
+                Box::new(Int64Array::from_vec((0..SIZE as i64).rev().collect())) as _,
+                Box::new(BooleanArray::from_iter((0..SIZE).map(|i| Some(i % 3 == 0)))) as _,
+            ],
+            None,
+        )) as _,
+    ]);
+    let schema = Schema::from(vec![
+        Field::new("a", DataType::Int64, false),
+        Field::new("b", DataType::Utf8, false),
+        Field::new("c", DataType::Struct(struct_fields), false),
+    ]);
+
+    let mut buf = vec![];
+    let mut writer = NativeWriter::new(
+        &mut buf,
+        schema,
+        WriteOptions {
+            max_page_size: Some(1000),
+            // the pages of the utf8 column have less rows
+            max_page_bytes: Some(16 * 1024),
+            ..Default::default()
+        },
+    );
+    writer.start().unwrap();
+    // the columns are written in two chunks
+    writer.write(&chunk.clone().sliced(0, 3000)).unwrap();
+    writer
+        .write(&chunk.clone().sliced(3000, SIZE - 3000))
+        .unwrap();
+    writer.finish().unwrap();
+    (buf, chunk)
+}
+
+trait SliceChunk {
+    fn sliced(self, offset: usize, length: usize) -> Self;
+}
+
+impl SliceChunk for Chunk<Box<dyn Array>> {
+    fn sliced(self, offset: usize, length: usize) -> Self {
+        Chunk::new(
+            self.into_arrays()
+                .into_iter()
+                .map(|array| array.sliced(offset, length))
+                .collect(),
+        )
+    }
+}
+
+fn concat_chunks(chunks: &[Chunk<Box<dyn Array>>]) -> Result<Chunk<Box<dyn Array>>> {
+    let columns = (0..chunks[0].arrays().len())
+        .map(|i| {
+            let arrays: Vec<&dyn Array> = chunks.iter().map(|c| c.arrays()[i].as_ref()).collect();
+            concatenate(&arrays)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Chunk::try_new(columns)
+}
+
+#[test]
+fn test_file_reader() -> Result<()> {
+    let (buf, expected) = write_data();
+
+    let reader = NativeFileReader::try_new(std::io::Cursor::new(buf))?.with_batch_size(700);
+    assert_eq!(reader.schema().fields.len(), 3);
+    let chunks = reader.collect::<Result<Vec<_>>>()?;
+    assert_eq!(chunks.len(), (SIZE + 699) / 700);
+    assert!(chunks[..chunks.len() - 1].iter().all(|c| c.len() == 700));
+    assert_eq!(concat_chunks(&chunks)?, expected);
+    Ok(())
+}
+
+#[test]
+fn test_file_reader_read_range() -> Result<()> {
+    let (buf, expected) = write_data();
+    let reader = NativeFileReader::try_new(std::io::Cursor::new(buf))?;
+
+    for (start, end) in [
+        (0, 10),
+        (950, 3050),
+        (3000, 4000),
+        (SIZE - 1, SIZE),
+        (100, 100),
+    ] {
+        let chunk = reader.read_range(start, end)?;
+        assert_eq!(chunk, expected.clone().sliced(start, end - start));
+    }
+    assert!(reader.read_range(SIZE - 10, SIZE + 1).is_err());
+    assert!(reader.read_range(10, 5).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_file_reader_projection() -> Result<()> {
+    let (buf, expected) = write_data();
+    let expected = Chunk::new(vec![
+        expected.arrays()[2].clone(),
+        expected.arrays()[0].clone(),
+    ]);
+
+    let reader = NativeFileReader::try_new(std::io::Cursor::new(buf.clone()))?
+        .with_projection(vec![2, 0])?;
+    let schema = reader.schema();
+    assert_eq!(schema.fields[0].name, "c");
+    assert_eq!(schema.fields[1].name, "a");
+    let chunks = reader.collect::<Result<Vec<_>>>()?;
+    assert_eq!(chunks.len(), 1);
+    assert_eq!(chunks[0], expected);
+
+    let reader = NativeFileReader::try_new(std::io::Cursor::new(buf.clone()))?
+        .with_projection(vec![2, 0])?;
+    assert_eq!(reader.read_all()?, expected);
+
+    assert!(NativeFileReader::try_new(std::io::Cursor::new(buf))?
+        .with_projection(vec![3])
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn test_file_reader_projection_paths() -> Result<()> {
+    let size: usize = 1000;
+    // s: struct<a: struct<b: i64, c: utf8>, d: bool>
+    let a_fields = vec![
+        Field::new("b", DataType::Int64, false),
+        Field::new("c", DataType::Utf8, false),
+    ];
+    let b = Int64Array::from_vec((0..size as i64).collect()).boxed();
+    let c = Utf8Array::<i32>::from_iter_values((0..size).map(|i| format!("{i}"))).boxed();
+    let a = StructArray::new(
+        DataType::Struct(a_fields.clone()),
+        vec![b.clone(), c.clone()],
+        None,
+    );
+    let s_fields = vec![
+        Field::new("a", a.data_type().clone(), false),
+        Field::new("d", DataType::Boolean, false),
+    ];
+    let d = BooleanArray::from_iter((0..size).map(|i| Some(i % 2 == 0))).boxed();
+    let s = StructArray::new(
+        DataType::Struct(s_fields.clone()),
+        vec![a.clone().boxed(), d.clone()],
+        None,
+    );
+    // l: list<struct<x: i64, y: utf8>>
+    let item_fields = vec![
+        Field::new("x", DataType::Int64, false),
+        Field::new("y", DataType::Utf8, false),
+    ];
+    let x = Int64Array::from_vec((0..size as i64 * 2).collect()).boxed();
+    let y = Utf8Array::<i32>::from_iter_values((0..size * 2).map(|i| format!("y{i}"))).boxed();
+    let item = StructArray::new(
+        DataType::Struct(item_fields.clone()),
+        vec![x.clone(), y],
+        None,
+    );
+    let offsets = OffsetsBuffer::try_from((0..=size as i32).map(|i| i * 2).collect::<Vec<_>>())?;
+    let l = ListArray::<i32>::new(
+        ListArray::<i32>::default_datatype(item.data_type().clone()),
+        offsets.clone(),
+        item.boxed(),
+        None,
+    );
+
+    let chunk = Chunk::new(vec![s.boxed(), l.boxed()]);
+    let schema = Schema::from(vec![
+        Field::new("s", chunk.arrays()[0].data_type().clone(), false),
+        Field::new("l", chunk.arrays()[1].data_type().clone(), false),
+    ]);
+    let mut buf = vec![];
+    let mut writer = NativeWriter::new(&mut buf, schema, WriteOptions::default());
+    writer.start()?;
+    writer.write(&chunk)?;
+    writer.finish()?;
+
+    let pruned_item_fields = vec![item_fields[0].clone()];
+    let pruned_l = ListArray::<i32>::new(
+        ListArray::<i32>::default_datatype(DataType::Struct(pruned_item_fields.clone())),
+        offsets,
+        StructArray::new(DataType::Struct(pruned_item_fields), vec![x], None).boxed(),
+        None,
+    );
+    let pruned_a_fields = vec![a_fields[1].clone()];
+    let pruned_s_fields = vec![
+        Field::new("a", DataType::Struct(pruned_a_fields.clone()), false),
+        s_fields[1].clone(),
+    ];
+    let pruned_s = StructArray::new(
+        DataType::Struct(pruned_s_fields),
+        vec![
+            StructArray::new(DataType::Struct(pruned_a_fields), vec![c], None).boxed(),
+            d,
+        ],
+        None,
+    );
+    let expected = Chunk::new(vec![pruned_l.boxed(), pruned_s.boxed()]);
+
+    let paths = ["l.item.x", "s.a.c", "s.d"];
+    let reader = NativeFileReader::try_new(std::io::Cursor::new(buf.clone()))?
+        .with_projection_paths(&paths)?;
+    assert_eq!(
+        reader.schema().fields[0].data_type(),
+        expected.arrays()[0].data_type()
+    );
+    assert_eq!(
+        reader.schema().fields[1].data_type(),
+        expected.arrays()[1].data_type()
+    );
+    let chunks = reader.collect::<Result<Vec<_>>>()?;
+    assert_eq!(concat_chunks(&chunks)?, expected);
+
+    let reader = NativeFileReader::try_new(std::io::Cursor::new(buf.clone()))?
+        .with_projection_paths(&paths)?;
+    assert_eq!(reader.read_all()?, expected);
+
+    // a path to a struct selects all its children
+    let reader = NativeFileReader::try_new(std::io::Cursor::new(buf.clone()))?
+        .with_projection_paths(&["s.a.b", "s.a"])?;
+    let s_array = reader.read_all()?.into_arrays().pop().unwrap();
+    let s_array = s_array.as_any().downcast_ref::<StructArray>().unwrap();
+    assert_eq!(s_array.values()[0].as_ref(), &a as &dyn Array);
+
+    for path in ["s.e", "l.x", "s.d.e", "t"] {
+        assert!(
+            NativeFileReader::try_new(std::io::Cursor::new(buf.clone()))?
+                .with_projection_paths(&[path])
+                .is_err()
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn test_file_reader_projection_paths_extension() -> Result<()> {
+    let size: usize = 1000;
+    let extension = |inner: DataType| {
+        DataType::Extension("ext".to_string(), Box::new(inner), Some("meta".to_string()))
+    };
+    let fields = vec![
+        Field::new("x", DataType::Int64, false),
+        Field::new("y", DataType::Utf8, false),
+    ];
+    let x = Int64Array::from_vec((0..size as i64).collect()).boxed();
+    let y = Utf8Array::<i32>::from_iter_values((0..size).map(|i| format!("y{i}"))).boxed();
+    let item = StructArray::new(DataType::Struct(fields.clone()), vec![x.clone(), y], None);
+    let offsets = OffsetsBuffer::try_from((0..=size as i32).collect::<Vec<_>>())?;
+    let list_type = |item_type: DataType| extension(ListArray::<i32>::default_datatype(item_type));
+    let chunk = Chunk::new(vec![
+        StructArray::new(
+            extension(item.data_type().clone()),
+            item.values().to_vec(),
+            None,
+        )
+        .boxed(),
+        ListArray::<i32>::new(
+            list_type(item.data_type().clone()),
+            offsets.clone(),
+            item.boxed(),
+            None,
+        )
+        .boxed(),
+    ]);
+    let schema = Schema::from(vec![
+        Field::new("e", chunk.arrays()[0].data_type().clone(), false),
+        Field::new("l", chunk.arrays()[1].data_type().clone(), false),
+    ]);
+    let mut buf = vec![];
+    let mut writer = NativeWriter::new(&mut buf, schema, WriteOptions::default());
+    writer.start()?;
+    writer.write(&chunk)?;
+    writer.finish()?;
+
+    // the pruned types keep their extensions
+    let pruned_type = DataType::Struct(vec![fields[0].clone()]);
+    let pruned_item = StructArray::new(pruned_type.clone(), vec![x.clone()], None);
+    let expected = Chunk::new(vec![
+        StructArray::new(extension(pruned_type.clone()), vec![x], None).boxed(),
+        ListArray::<i32>::new(list_type(pruned_type), offsets, pruned_item.boxed(), None).boxed(),
+    ]);
+    let paths = ["e.x", "l.item.x"];
+    let reader = NativeFileReader::try_new(std::io::Cursor::new(buf.clone()))?
+        .with_projection_paths(&paths)?;
+    assert_eq!(
+        reader.schema().fields[0].data_type(),
+        expected.arrays()[0].data_type()
+    );
+    assert_eq!(
+        reader.schema().fields[1].data_type(),
+        expected.arrays()[1].data_type()
+    );
+    assert_eq!(reader.read_all()?, expected);
+
+    for path in ["e.z", "l.x"] {
+        assert!(
+            NativeFileReader::try_new(std::io::Cursor::new(buf.clone()))?
+                .with_projection_paths(&[path])
+                .is_err()
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn test_page_checksum() -> Result<()> {
+    let chunk = Chunk::new(vec![
+        Box::new(Int64Array::from_vec((0..SIZE as i64).collect())) as Box<dyn Array>,
+        Box::new(Utf8Array::<i32>::from_iter_values(
+            (0..SIZE).map(|i| format!("{i:0>50}")),
+        )) as _,
+    ]);
+    let schema = Schema::from(vec![
+        Field::new("a", DataType::Int64, false),
+        Field::new("b", DataType::Utf8, false),
+    ]);
+    let mut buf = vec![];
+    let mut writer = NativeWriter::new(
+        &mut buf,
+        schema,
+        WriteOptions {
+            max_page_size: Some(1000),
+            page_checksum: true,
+            ..Default::default()
+        },
+    );
+    writer.start()?;
+    writer.write(&chunk)?;
+    writer.finish()?;
+    let metas = writer.metas.clone();
+    assert!(metas
+        .iter()
+        .all(|meta| meta.pages.iter().all(|page| page.checksum.is_some())));
+
+    let reader = NativeFileReader::try_new(std::io::Cursor::new(buf.clone()))?;
+    assert_eq!(reader.metas(), metas.as_slice());
+    assert_eq!(reader.read_range(0, SIZE)?, chunk);
+    let reader = NativeFileReader::try_new(std::io::Cursor::new(buf.clone()))?;
+    assert_eq!(reader.read_all()?, chunk);
+
+    // flip a bit in the third page of `b`
+    let page = &metas[1].pages[2];
+    buf[(page.offset + page.length / 2) as usize] ^= 1;
+    let reader = NativeFileReader::try_new(std::io::Cursor::new(buf.clone()))?;
+    let err = reader.collect::<Result<Vec<_>>>().unwrap_err().to_string();
+    assert!(
+        err.contains("Checksum mismatch of page 2 of column b"),
+        "{err}"
+    );
+    let reader = NativeFileReader::try_new(std::io::Cursor::new(buf))?;
+    let err = reader.read_all().unwrap_err().to_string();
+    assert!(
+        err.contains("Checksum mismatch of page 2 of column b"),
+        "{err}"
+    );
+
+    // flip a bit in the column dictionary of `b`
+    let mut buf = vec![];
+    let mut writer = NativeWriter::new(
+        &mut buf,
+        Schema::from(vec![Field::new("b", DataType::Utf8, false)]),
+        WriteOptions {
+            max_page_size: Some(1000),
+            page_checksum: true,
+            column_dictionary: true,
+            ..Default::default()
+        },
+    );
+    let values = Utf8Array::<i32>::from_iter_values((0..SIZE).map(|i| format!("{}", i % 10)));
+    writer.start()?;
+    writer.write(&Chunk::new(vec![values.boxed()]))?;
+    writer.finish()?;
+    let dictionary = writer.metas[0].pages[0].dictionary.clone().unwrap();
+    buf[(dictionary.offset + dictionary.length - 1) as usize] ^= 1;
+    let reader = NativeFileReader::try_new(std::io::Cursor::new(buf))?;
+    let err = reader.read_all().unwrap_err().to_string();
+    assert!(
+        err.contains("Checksum mismatch of the dictionary of page 0 of column b"),
+        "{err}"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_file_reader_column_dictionary() -> Result<()> {
+    let chunk = Chunk::new(vec![
+        Box::new(Int64Array::from_iter(
+            (0..SIZE as i64).map(|i| (i % 3 != 0).then_some(i % 5)),
+        )) as Box<dyn Array>,
+        Box::new(Utf8Array::<i32>::from_iter_values(
+            (0..SIZE).map(|i| format!("{:0>20}", i % 10)),
+        )) as _,
+    ]);
+    let schema = Schema::from(vec![
+        Field::new("a", DataType::Int64, true),
+        Field::new("b", DataType::Utf8, false),
+    ]);
+
+    let mut buf = vec![];
+    let mut writer = NativeWriter::new(
+        &mut buf,
+        schema,
+        WriteOptions {
+            max_page_size: Some(1000),
+            page_checksum: true,
+            column_dictionary: true,
+            ..Default::default()
+        },
+    );
+    writer.start()?;
+    writer.write(&chunk.clone().sliced(0, 3000))?;
+    writer.write(&chunk.clone().sliced(3000, SIZE - 3000))?;
+    writer.finish()?;
+    assert!(writer.metas[1].pages[4].dictionary.is_some());
+
+    let reader = NativeFileReader::try_new(std::io::Cursor::new(buf))?;
+    for (start, end) in [(0, SIZE), (1500, 2500), (2500, 4200), (4100, SIZE)] {
+        let range = reader.read_range(start, end)?;
+        assert_eq!(range, chunk.clone().sliced(start, end - start));
+    }
+    assert_eq!(reader.read_all()?, chunk);
+    Ok(())
+}
+
+fn decode_dictionary(array: &dyn Array) -> Result<Box<dyn Array>> {
+    let array = array
+        .as_any()
+        .downcast_ref::<DictionaryArray<u32>>()
+        .unwrap();
+    cast(array, array.values().data_type(), Default::default())
+}
+
+#[test]
+fn test_file_reader_dictionary_columns() -> Result<()> {
+    let chunk = Chunk::new(vec![
+        Box::new(Int64Array::from_iter(
+            (0..SIZE as i64).map(|i| (i % 3 != 0).then_some(i % 7)),
+        )) as Box<dyn Array>,
+        // the dictionaries of the pages differ
+        Box::new(Utf8Array::<i32>::from_iter_values(
+            (0..SIZE).map(|i| format!("v{}", i % 10 + i / 1000)),
+        )) as _,
+        Box::new(Int32Array::from_vec((0..SIZE as i32).collect())) as _,
+    ]);
+    let schema = Schema::from(vec![
+        Field::new("a", DataType::Int64, true),
+        Field::new("b", DataType::Utf8, false),
+        Field::new("c", DataType::Int32, false),
+    ]);
+
+    let page_dictionary = WriteOptions {
+        max_page_size: Some(1000),
+        forced_compressions: vec![
+            (
+                PhysicalType::Primitive(PrimitiveType::Int64),
+                Compression::Dict,
+            ),
+            (PhysicalType::Utf8, Compression::Dict),
+        ],
+        ..Default::default()
+    };
+    let column_dictionary = WriteOptions {
+        max_page_size: Some(1000),
+        column_dictionary: true,
+        ..Default::default()
+    };
+    for options in [page_dictionary.clone(), column_dictionary.clone()] {
+        let mut buf = vec![];
+        let mut writer = NativeWriter::new(&mut buf, schema.clone(), options);
+        writer.start()?;
+        writer.write(&chunk)?;
+        writer.finish()?;
+
+        let reader = NativeFileReader::try_new(std::io::Cursor::new(buf))?
+            .with_dictionary_columns(&["a", "b", "c"])?
+            .with_batch_size(700);
+        assert!(reader.schema().fields.iter().all(|f| matches!(
+            f.data_type(),
+            DataType::Dictionary(IntegerType::UInt32, _, _)
+        )));
+
+        let range = reader.read_range(1500, 2700)?;
+        for (array, expected) in range.arrays().iter().zip(chunk.arrays()) {
+            assert_eq!(
+                decode_dictionary(array.as_ref())?,
+                expected.sliced(1500, 1200)
+            );
+        }
+
+        let chunks = reader.collect::<Result<Vec<_>>>()?;
+        let chunks = chunks
+            .iter()
+            .map(|c| {
+                let arrays = c
+                    .arrays()
+                    .iter()
+                    .map(|array| decode_dictionary(array.as_ref()))
+                    .collect::<Result<Vec<_>>>()?;
+                Chunk::try_new(arrays)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(concat_chunks(&chunks)?, chunk);
+    }
+
+    // the batches of a column dictionary share its values
+    let mut buf = vec![];
+    let mut writer = NativeWriter::new(&mut buf, schema.clone(), column_dictionary);
+    writer.start()?;
+    writer.write(&chunk)?;
+    writer.finish()?;
+    let reader = NativeFileReader::try_new(std::io::Cursor::new(buf))?
+        .with_dictionary_columns(&["a"])?
+        .with_batch_size(700);
+    let values = reader
+        .map(|chunk| {
+            let a = chunk?.arrays()[0].clone();
+            let a = a.as_any().downcast_ref::<DictionaryArray<u32>>().unwrap();
+            let values = a.values().as_any().downcast_ref::<Int64Array>().unwrap();
+            Ok(values.values().clone())
+        })
+        .collect::<Result<Vec<_>>>()?;
+    assert_eq!(values.len(), (SIZE + 699) / 700);
+    assert!(values.iter().all(|v| v.as_ptr() == values[0].as_ptr()));
+
+    let mut buf = vec![];
+    let mut writer = NativeWriter::new(&mut buf, schema, page_dictionary);
+    writer.start()?;
+    writer.write(&chunk)?;
+    writer.finish()?;
+    let reader = NativeFileReader::try_new(std::io::Cursor::new(buf.clone()))?;
+    assert!(reader.with_dictionary_columns(&["d"]).is_err());
+
+    let reader = NativeFileReader::try_new(std::io::Cursor::new(buf))?;
+    let all = reader.with_dictionary_columns(&["a", "b"])?.read_all()?;
+    // the dictionaries of the pages are unified
+    let b = all.arrays()[1]
+        .as_any()
+        .downcast_ref::<DictionaryArray<u32>>()
+        .unwrap();
+    assert_eq!(b.values().len(), 14);
+    assert_eq!(
+        decode_dictionary(all.arrays()[0].as_ref())?,
+        chunk.arrays()[0]
+    );
+    assert_eq!(decode_dictionary(b)?, chunk.arrays()[1]);
+    assert_eq!(all.arrays()[2], chunk.arrays()[2]);
+    Ok(())
+}
+
+#[test]
+fn test_file_reader_union() -> Result<()> {
+    let fields = vec![
+        Field::new("x", DataType::Int64, true),
+        Field::new("y", DataType::Utf8, true),
+    ];
+    for mode in [UnionMode::Sparse, UnionMode::Dense] {
+        let types = (0..SIZE).map(|i| (i % 3 == 0) as i8).collect::<Vec<_>>();
+        let offsets = mode
+            .is_dense()
+            .then(|| (0..SIZE as i32).collect::<Vec<_>>().into());
+        let union = UnionArray::new(
+            DataType::Union(fields.clone(), None, mode),
+            types.into(),
+            vec![
+                Int64Array::from_vec((0..SIZE as i64).collect()).boxed(),
+                Utf8Array::<i32>::from_iter_values((0..SIZE).map(|i| format!("{i}"))).boxed(),
+            ],
+            offsets,
+        );
+        let chunk = Chunk::new(vec![
+            union.boxed(),
+            Int64Array::from_vec((0..SIZE as i64).rev().collect()).boxed(),
+        ]);
+        let schema = Schema::from(vec![
+            Field::new("u", chunk.arrays()[0].data_type().clone(), false),
+            Field::new("a", DataType::Int64, false),
+        ]);
+
+        let mut buf = vec![];
+        let mut writer = NativeWriter::new(
+            &mut buf,
+            schema,
+            WriteOptions {
+                max_page_size: Some(1000),
+                ..Default::default()
+            },
+        );
+        writer.start()?;
+        // the sliced unions are written
+        writer.write(&chunk.clone().sliced(0, 3000))?;
+        writer.write(&chunk.clone().sliced(3000, SIZE - 3000))?;
+        writer.finish()?;
+
+        let reader = NativeFileReader::try_new(std::io::Cursor::new(buf.clone()))?;
+        assert_eq!(reader.read_all()?, chunk);
+
+        let reader =
+            NativeFileReader::try_new(std::io::Cursor::new(buf.clone()))?.with_batch_size(700);
+        for (i, batch) in reader.enumerate() {
+            let offset = i * 700;
+            let length = 700.min(SIZE - offset);
+            assert_eq!(batch?, chunk.clone().sliced(offset, length));
+        }
+
+        let reader = NativeFileReader::try_new(std::io::Cursor::new(buf.clone()))?;
+        assert_eq!(
+            reader.read_range(950, 3050)?,
+            chunk.clone().sliced(950, 2100)
+        );
+
+        let reader = NativeFileReader::try_new(std::io::Cursor::new(buf.clone()))?
+            .with_projection(vec![1])?;
+        assert_eq!(reader.read_all()?.arrays()[0], chunk.arrays()[1]);
+
+        // the children of unions can't be projected
+        let reader = NativeFileReader::try_new(std::io::Cursor::new(buf.clone()))?
+            .with_projection_paths(&["u"])?;
+        assert_eq!(reader.read_all()?.arrays()[0], chunk.arrays()[0]);
+        assert!(NativeFileReader::try_new(std::io::Cursor::new(buf))?
+            .with_projection_paths(&["u.x"])
+            .is_err());
+    }
+    Ok(())
+}
+
+#[test]
+fn test_file_reader_variant() -> Result<()> {
+    // the integer ids and the tags are shredded, the other values of the paths are not
+    let values = (0..SIZE)
+        .map(|i| match i % 8 {
+            0 | 2 | 4 => Some(format!(r#"{{"id":{i},"tags":{{"hot":true}}}}"#)),
+            1 | 5 => Some(format!(r#"{{"id":"id{i}","name":"n{}"}}"#, i % 10)),
+            6 => Some("[1,2]".to_string()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let values = Utf8Array::<i64>::from(values);
+    let variant = Utf8Array::try_new(
+        variant_data_type(),
+        values.offsets().clone(),
+        values.values().clone(),
+        values.validity().cloned(),
+    )?;
+    let chunk = Chunk::new(vec![
+        variant.boxed(),
+        Int64Array::from_vec((0..SIZE as i64).collect()).boxed(),
+    ]);
+    let schema = Schema::from(vec![
+        Field::new("v", variant_data_type(), true),
+        Field::new("a", DataType::Int64, false),
+    ]);
+
+    let mut buf = vec![];
+    let mut writer = NativeWriter::new(
+        &mut buf,
+        schema,
+        WriteOptions {
+            max_page_size: Some(1000),
+            ..Default::default()
+        },
+    );
+    writer.start()?;
+    writer.write(&chunk)?;
+    writer.finish()?;
+
+    let reader = NativeFileReader::try_new(std::io::Cursor::new(buf.clone()))?;
+    assert_eq!(reader.read_all()?, chunk);
+
+    // the paths are read as the JSON values at the paths, or nulls where missing
+    type Expected = fn(usize) -> Option<String>;
+    let paths: [(Vec<&str>, Expected); 4] = [
+        (vec!["id"], |i| match i % 8 {
+            0 | 2 | 4 => Some(format!("{i}")),
+            1 | 5 => Some(format!(r#""id{i}""#)),
+            _ => None,
+        }),
+        (vec!["tags", "hot"], |i| {
+            matches!(i % 8, 0 | 2 | 4).then(|| "true".to_string())
+        }),
+        (vec!["name"], |i| {
+            matches!(i % 8, 1 | 5).then(|| format!(r#""n{}""#, i % 10))
+        }),
+        (vec!["missing"], |_| None),
+    ];
+    for (path, expected) in paths {
+        let path = format!("v.{}", path.join("."));
+        let reader = NativeFileReader::try_new(std::io::Cursor::new(buf.clone()))?
+            .with_projection_paths(&[path.as_str(), "a"])?;
+        let chunk = reader.read_all()?;
+        let values = chunk.arrays()[0]
+            .as_any()
+            .downcast_ref::<Utf8Array<i64>>()
+            .unwrap();
+        let expected = (0..SIZE).map(expected).collect::<Vec<_>>();
+        assert_eq!(
+            values
+                .iter()
+                .map(|v| v.map(str::to_string))
+                .collect::<Vec<_>>(),
+            expected
+        );
+        assert_eq!(
+            variant_path(values.data_type()).unwrap().len(),
+            path.split('.').count() - 1
+        );
+    }
+
+    let reader = NativeFileReader::try_new(std::io::Cursor::new(buf))?;
+    assert!(reader.with_projection_paths(&["v.id", "v.name"]).is_err());
+
+    // the remainders aren't decompressed when all the rows have a scalar at the projected
+    // path, the compressed values of the remainders are corrupted to check it
+    let values =
+        Utf8Array::<i64>::from_iter_values((0..SIZE).map(|i| format!(r#"{{"id":{i},"x":[{i}]}}"#)));
+    let variant = Utf8Array::try_new(
+        variant_data_type(),
+        values.offsets().clone(),
+        values.values().clone(),
+        None,
+    )?;
+    let schema = Schema::from(vec![Field::new("v", variant_data_type(), false)]);
+    let mut buf = vec![];
+    let mut writer = NativeWriter::new(
+        &mut buf,
+        schema,
+        WriteOptions {
+            default_compression: CommonCompression::Lz4,
+            max_page_size: Some(1000),
+            ..Default::default()
+        },
+    );
+    writer.start()?;
+    writer.write(&Chunk::new(vec![variant.boxed()]))?;
+    writer.finish()?;
+    // the values of the remainders end the page, after their codec, compressed size
+    // and uncompressed size
+    for page in writer.metas[0].pages.clone() {
+        let end = (page.offset + page.length) as usize;
+        let start = (page.offset as usize..end - 9)
+            .rev()
+            .find(|i| {
+                let size = u32::from_le_bytes(buf[i + 1..i + 5].try_into().unwrap()) as usize;
+                buf[*i] == u8::from(Compression::Lz4) && i + 9 + size == end
+            })
+            .unwrap();
+        buf[start + 9..end].fill(0xff);
+    }
+
+    let reader = NativeFileReader::try_new(std::io::Cursor::new(buf.clone()))?;
+    assert!(reader.read_all().is_err());
+    let reader =
+        NativeFileReader::try_new(std::io::Cursor::new(buf))?.with_projection_paths(&["v.id"])?;
+    let chunk = reader.read_all()?;
+    let ids = chunk.arrays()[0]
+        .as_any()
+        .downcast_ref::<Utf8Array<i64>>()
+        .unwrap();
+    assert!(ids
+        .values_iter()
+        .enumerate()
+        .all(|(i, id)| id == i.to_string()));
+    Ok(())
+}
+
+#[test]
+fn test_file_reader_logical_types() -> Result<()> {
+    let extension = |inner: DataType| {
+        DataType::Extension("ext".to_string(), Box::new(inner), Some("meta".to_string()))
+    };
+    let timestamp = DataType::Timestamp(TimeUnit::Microsecond, Some("Asia/Shanghai".to_string()));
+    let values = Int64Array::from_vec((0..SIZE as i64).collect());
+    let strings = Utf8Array::<i32>::from_iter_values((0..SIZE).map(|i| format!("v{}", i % 10)));
+    let struct_type = DataType::Struct(vec![Field::new("a", extension(DataType::Utf8), false)]);
+    let chunk = Chunk::new(vec![
+        values.clone().to(timestamp.clone()).boxed(),
+        values
+            .iter()
+            .map(|v| v.map(|v| *v as i128))
+            .collect::<PrimitiveArray<i128>>()
+            .to(DataType::Decimal(38, 4))
+            .boxed(),
+        values.to(extension(timestamp)).boxed(),
+        Utf8Array::<i32>::try_new(
+            extension(DataType::Utf8),
+            strings.offsets().clone(),
+            strings.values().clone(),
+            None,
+        )?
+        .boxed(),
+        StructArray::try_new(
+            extension(struct_type),
+            vec![Utf8Array::<i32>::try_new(
+                extension(DataType::Utf8),
+                strings.offsets().clone(),
+                strings.values().clone(),
+                None,
+            )?
+            .boxed()],
+            None,
+        )?
+        .boxed(),
+    ]);
+    let metadata = Metadata::from([("key".to_string(), "value".to_string())]);
+    let schema = Schema::from(
+        chunk
+            .arrays()
+            .iter()
+            .enumerate()
+            .map(|(i, array)| {
+                Field::new(format!("c{i}"), array.data_type().clone(), false)
+                    .with_metadata(metadata.clone())
+            })
+            .collect::<Vec<_>>(),
+    );
+
+    for column_dictionary in [false, true] {
+        let mut buf = vec![];
+        let mut writer = NativeWriter::new(
+            &mut buf,
+            schema.clone(),
+            WriteOptions {
+                max_page_size: Some(1000),
+                column_dictionary,
+                ..Default::default()
+            },
+        );
+        writer.start()?;
+        writer.write(&chunk)?;
+        writer.finish()?;
+
+        let reader = NativeFileReader::try_new(std::io::Cursor::new(buf.clone()))?;
+        assert_eq!(reader.schema(), schema);
+        assert_eq!(reader.read_all()?, chunk);
+
+        let reader = NativeFileReader::try_new(std::io::Cursor::new(buf))?.with_batch_size(700);
+        for (i, batch) in reader.enumerate() {
+            let offset = i * 700;
+            let length = 700.min(SIZE - offset);
+            assert_eq!(batch?, chunk.clone().sliced(offset, length));
+        }
+    }
+    Ok(())
+}
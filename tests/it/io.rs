@@ -17,30 +17,38 @@
 
 use arrow::{
     array::{
-        Array, BinaryArray, BooleanArray, Float32Array, Float64Array, Int16Array, Int32Array,
-        Int64Array, Int8Array, ListArray, MapArray, PrimitiveArray, StructArray, UInt16Array,
-        UInt32Array, UInt64Array, UInt8Array, Utf8Array,
+        Array, BinaryArray, BooleanArray, DictionaryArray, FixedSizeBinaryArray, Float32Array,
+        Float64Array, Int16Array, Int32Array, Int64Array, Int8Array, ListArray, MapArray,
+        PrimitiveArray, StructArray, UInt16Array, UInt32Array, UInt64Array, UInt8Array, UnionArray,
+        Utf8Array,
     },
     bitmap::{Bitmap, MutableBitmap},
     chunk::Chunk,
     compute,
-    datatypes::{DataType, Field, Schema},
-    io::parquet::{
-        read::{n_columns, ColumnDescriptor},
-        write::to_parquet_schema,
+    datatypes::{
+        DataType, Field, IntervalUnit, PhysicalType, PrimitiveType, Schema, TimeUnit, UnionMode,
     },
+    io::parquet::read::ColumnDescriptor,
     offset::OffsetsBuffer,
+    types::{days_ms, f16, months_days_ns},
 };
+use futures::{AsyncSeekExt, TryStreamExt};
 use rand::{rngs::StdRng, Rng, SeedableRng};
 use std::io::{BufRead, BufReader};
 use strawboat::{
     read::{
-        batch_read::batch_read_array,
+        batch_read::{batch_read_array, batch_read_array_async},
         deserialize::column_iter_to_arrays,
-        reader::{is_primitive, NativeReader},
+        reader::{is_primitive, AsyncNativeReader, NativeReader},
+        NativeFileReader,
     },
-    write::{NativeWriter, WriteOptions},
-    ColumnMeta, CommonCompression, PageMeta,
+    stat::{stat_simple, PageBody},
+    variant::variant_data_type,
+    write::{
+        n_columns, to_parquet_schema, ColumnSelector, ColumnWriteOptions, NativeWriter,
+        WriteOptions,
+    },
+    ColumnMeta, CommonCompression, Compression, PageMeta,
 };
 
 pub const WRITE_PAGE: usize = 2048;
@@ -164,6 +172,430 @@ fn test_onevalue() {
     test_write_read(chunk);
 }
 
+#[test]
+fn test_multiple_chunks() {
+    let size = WRITE_PAGE * 3 + 100;
+    let chunks = (0..3)
+        .map(|i| {
+            Chunk::new(vec![
+                Box::new(create_random_bool(size + i, 0.1)) as _,
+                Box::new(create_random_index(size + i, 0.1, size)) as _,
+                Box::new(create_random_double(size + i, 0.2, 8)) as _,
+                Box::new(create_random_string(size + i, 0.3, size)) as _,
+                Box::new(create_struct(size + i, 0.2, size)) as _,
+                Box::new(create_list(size + i, 0.2)) as _,
+            ])
+        })
+        .collect::<Vec<_>>();
+    test_write_read_chunks_with_options(
+        chunks,
+        WriteOptions {
+            default_compression: CommonCompression::Lz4,
+            max_page_size: Some(WRITE_PAGE),
+            max_page_bytes: None,
+            default_compress_ratio: Some(2.0f64),
+            forbidden_compressions: vec![],
+            forced_compression: None,
+            forced_compressions: vec![],
+            page_checksum: false,
+            column_dictionary: false,
+            column_options: vec![],
+        },
+    );
+}
+
+#[test]
+fn test_page_layout() {
+    let size = WRITE_PAGE * 3 + 100;
+    let chunk = Chunk::new(vec![
+        Box::new(create_random_index(size, 0.1, size)) as _,
+        Box::new(create_struct(size, 0.2, size)) as _,
+        Box::new(create_random_string(size, 0.3, size)) as Box<dyn Array>,
+    ]);
+    let fields: Vec<Field> = chunk
+        .iter()
+        .map(|array| Field::new("name", array.data_type().clone(), true))
+        .collect();
+    let mut writer = NativeWriter::new(
+        vec![],
+        Schema::from(fields),
+        WriteOptions {
+            max_page_size: Some(WRITE_PAGE),
+            ..Default::default()
+        },
+    );
+    writer.start().unwrap();
+    writer.write(&chunk).unwrap();
+
+    // the pages are written in the order of leaf columns and pages
+    let mut offset = writer.metas[0].offset;
+    for meta in writer.metas.iter() {
+        assert_eq!(meta.offset, offset);
+        assert_eq!(meta.pages.len(), 4);
+        for page in meta.pages.iter() {
+            assert_eq!(page.offset, offset);
+            offset += page.length;
+        }
+    }
+    writer.finish().unwrap();
+}
+
+/// A writer which fails after writing `limit` bytes.
+struct LimitedWriter {
+    written: usize,
+    limit: usize,
+}
+
+impl std::io::Write for LimitedWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if self.written + buf.len() > self.limit {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::WriteZero,
+                "limit exceeded",
+            ));
+        }
+        self.written += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_write_error() {
+    let size = WRITE_PAGE * 2;
+    let chunk = Chunk::new(vec![
+        Box::new(Int64Array::from_vec((0..size as i64).collect())) as Box<dyn Array>,
+        Box::new(create_random_string(size, 0.1, size)) as _,
+    ]);
+    let schema = Schema::from(vec![
+        Field::new("a", DataType::Int64, false),
+        Field::new("b", DataType::LargeBinary, true),
+    ]);
+    let mut writer = NativeWriter::new(
+        LimitedWriter {
+            written: 0,
+            limit: 1024,
+        },
+        schema,
+        WriteOptions {
+            max_page_size: Some(WRITE_PAGE),
+            ..Default::default()
+        },
+    );
+    writer.start().unwrap();
+    let err = writer.write(&chunk).unwrap_err().to_string();
+    assert!(err.contains("column"), "{err}");
+    assert!(err.contains("page"), "{err}");
+
+    assert!(writer.is_poisoned());
+    assert!(writer.write(&chunk).is_err());
+    assert!(writer.finish().is_err());
+}
+
+#[test]
+fn test_column_options() {
+    let size = WRITE_PAGE * 2;
+    let values = |prefix: &'static str| {
+        Utf8Array::<i32>::from_iter_values((0..size).map(move |i| format!("{prefix}{i:0>20}")))
+    };
+    let chunk = Chunk::new(vec![
+        Box::new(values("a")) as Box<dyn Array>,
+        Box::new(values("b")) as _,
+        Box::new(StructArray::new(
+            DataType::Struct(vec![Field::new("c", DataType::Utf8, false)]),
+            vec![values("c").boxed()],
+            None,
+        )) as _,
+    ]);
+    let schema = Schema::from(vec![
+        Field::new("a", DataType::Utf8, false),
+        Field::new("b", DataType::Utf8, false),
+        Field::new("s", chunk.arrays()[2].data_type().clone(), false),
+    ]);
+    let options = WriteOptions {
+        default_compression: CommonCompression::Lz4,
+        max_page_size: Some(WRITE_PAGE),
+        ..Default::default()
+    }
+    .with_column_options(
+        ColumnSelector::Path("b".to_string()),
+        ColumnWriteOptions {
+            default_compression: Some(CommonCompression::Zstd(0)),
+            ..Default::default()
+        },
+    )
+    .with_column_options(
+        ColumnSelector::Leaf(1),
+        ColumnWriteOptions {
+            default_compression: Some(CommonCompression::None),
+            ..Default::default()
+        },
+    )
+    .with_column_options(
+        ColumnSelector::Path("s".to_string()),
+        ColumnWriteOptions {
+            max_page_size: Some(WRITE_PAGE / 2),
+            ..Default::default()
+        },
+    );
+
+    let mut bytes = vec![];
+    let mut writer = NativeWriter::new(&mut bytes, schema, options.clone());
+    writer.start().unwrap();
+    writer.write(&chunk).unwrap();
+    writer.finish().unwrap();
+    let metas = writer.metas.clone();
+
+    assert_eq!(metas[0].pages.len(), 2);
+    assert_eq!(metas[1].pages.len(), 2);
+    assert_eq!(metas[2].pages.len(), 4);
+
+    // the later override of the leaf index wins
+    let expected = [CommonCompression::Lz4, CommonCompression::None];
+    for (meta, compression) in metas.iter().zip(expected) {
+        let mut reader = std::io::Cursor::new(bytes.clone());
+        reader.consume(meta.offset as usize);
+        let reader = NativeReader::new(reader, meta.pages.clone(), vec![]);
+        let info = stat_simple(reader, Field::new("name", DataType::Utf8, false)).unwrap();
+        for page in info.pages {
+            match page.body {
+                PageBody::Common(c) => assert_eq!(c, compression),
+                other => panic!("unexpected page {other:?}"),
+            }
+        }
+    }
+
+    test_write_read_with_options(chunk, options);
+}
+
+#[test]
+fn test_forced_compression() {
+    let size = WRITE_PAGE * 2;
+    let chunk = Chunk::new(vec![
+        Box::new(create_random_index(size, 0.0, 10)) as Box<dyn Array>,
+        Box::new(create_random_index(size, 0.0, 10)) as _,
+        Box::new(create_random_double(size, 0.0, 10)) as _,
+    ]);
+    let schema = Schema::from(vec![
+        Field::new("a", DataType::Int32, false),
+        Field::new("b", DataType::Int32, false),
+        Field::new("c", DataType::Float64, false),
+    ]);
+    let options = WriteOptions {
+        max_page_size: Some(WRITE_PAGE),
+        default_compress_ratio: Some(2.0f64),
+        forced_compression: Some(Compression::Snappy),
+        forced_compressions: vec![(
+            PhysicalType::Primitive(PrimitiveType::Int32),
+            Compression::Rle,
+        )],
+        ..Default::default()
+    }
+    .with_column_options(
+        ColumnSelector::Path("b".to_string()),
+        ColumnWriteOptions {
+            forced_compression: Some(Compression::Bitpacking),
+            ..Default::default()
+        },
+    );
+
+    let mut bytes = vec![];
+    let mut writer = NativeWriter::new(&mut bytes, schema.clone(), options.clone());
+    writer.start().unwrap();
+    writer.write(&chunk).unwrap();
+    writer.finish().unwrap();
+    let metas = writer.metas.clone();
+
+    for ((meta, field), expected) in metas.iter().zip(schema.fields.iter()).zip([
+        Compression::Rle,
+        Compression::Bitpacking,
+        Compression::Snappy,
+    ]) {
+        let mut reader = std::io::Cursor::new(bytes.clone());
+        reader.consume(meta.offset as usize);
+        let reader = NativeReader::new(reader, meta.pages.clone(), vec![]);
+        let info = stat_simple(reader, field.clone()).unwrap();
+        for page in info.pages {
+            let compression = match page.body {
+                PageBody::Rle => Compression::Rle,
+                PageBody::Bitpack => Compression::Bitpacking,
+                PageBody::Common(c) => c.to_compression(),
+                other => panic!("unexpected page {other:?}"),
+            };
+            assert_eq!(compression, expected);
+        }
+    }
+    test_write_read_with_options(chunk.clone(), options);
+
+    // the forced compression can't be used for the pages
+    for compression in [Compression::Patas, Compression::OneValue] {
+        let options = WriteOptions {
+            forced_compression: Some(compression),
+            ..Default::default()
+        };
+        let mut writer = NativeWriter::new(vec![], schema.clone(), options);
+        writer.start().unwrap();
+        assert!(writer.write(&chunk).is_err());
+    }
+
+    // the bitpacked pages, including the last page, must hold a multiple of 128 values
+    let size = 1000;
+    let chunk = Chunk::new(vec![
+        Box::new(UInt32Array::from_vec((0..size as u32).collect())) as Box<dyn Array>,
+        Box::new(Int64Array::from_vec((0..size as i64).collect())) as _,
+    ]);
+    for (array, max_page_size) in chunk.into_arrays().into_iter().zip([100, 128]) {
+        let schema = Schema::from(vec![Field::new("a", array.data_type().clone(), false)]);
+        let options = WriteOptions {
+            max_page_size: Some(max_page_size),
+            forced_compression: Some(Compression::Bitpacking),
+            ..Default::default()
+        };
+        let mut writer = NativeWriter::new(vec![], schema, options);
+        writer.start().unwrap();
+        let err = writer.write(&Chunk::new(vec![array])).unwrap_err();
+        assert!(err.to_string().contains("Bitpacking"), "{err}");
+    }
+}
+
+#[test]
+fn test_compression_level() {
+    let chunk = Chunk::new(vec![Box::new(Utf8Array::<i32>::from_iter_values(
+        (0..10000).map(|i| format!("value {} of row {i}", i % 100)),
+    )) as Box<dyn Array>]);
+    let write = |compression| {
+        let options = WriteOptions {
+            default_compression: compression,
+            ..Default::default()
+        };
+        test_write_read_with_options(chunk.clone(), options.clone());
+        let schema = Schema::from(vec![Field::new("a", DataType::Utf8, false)]);
+        let mut writer = NativeWriter::new(vec![], schema, options);
+        writer.start().unwrap();
+        writer.write(&chunk).unwrap();
+        writer.finish().unwrap();
+        writer.total_size()
+    };
+
+    assert!(write(CommonCompression::Zstd(19)) < write(CommonCompression::Zstd(1)));
+    assert!(write(CommonCompression::Lz4Hc(12)) < write(CommonCompression::Lz4));
+    assert!(write(CommonCompression::Lz4Hc(12)) <= write(CommonCompression::Lz4));
+}
+
+#[test]
+fn test_column_dictionary() {
+    let size = WRITE_PAGE * 4;
+    let new_chunk = |seed: usize| {
+        let (offsets, validity) = create_random_offsets(size, 0.1);
+        let values = Box::new(Utf8Array::<i32>::from_iter_values(
+            (0..*offsets.last().unwrap()).map(|i| format!("{}", (i as usize + seed) % 7)),
+        ));
+        Chunk::new(vec![
+            Box::new(create_random_index(size, 0.1, 20)) as Box<dyn Array>,
+            Box::new(create_random_double(size, 0.0, 20)) as _,
+            Box::new(create_random_string(size, 0.2, 50 + seed)) as _,
+            Box::new(ListArray::<i32>::new(
+                DataType::List(Box::new(Field::new("item", DataType::Utf8, false))),
+                OffsetsBuffer::try_from(offsets).unwrap(),
+                values,
+                validity,
+            )) as _,
+            // too many distinct values for a dictionary
+            Box::new(Int64Array::from_vec((0..size as i64).collect())) as _,
+        ])
+    };
+    let chunks = vec![new_chunk(0), new_chunk(1)];
+    let options = WriteOptions {
+        max_page_size: Some(WRITE_PAGE),
+        default_compress_ratio: Some(2.0f64),
+        page_checksum: true,
+        column_dictionary: true,
+        ..Default::default()
+    };
+    test_write_read_chunks_with_options(chunks.clone(), options.clone());
+
+    let write = |options: WriteOptions| {
+        let schema = Schema::from(
+            chunks[0]
+                .iter()
+                .map(|array| Field::new("name", array.data_type().clone(), true))
+                .collect::<Vec<_>>(),
+        );
+        let mut writer = NativeWriter::new(vec![], schema, options);
+        writer.start().unwrap();
+        for chunk in chunks.iter() {
+            writer.write(chunk).unwrap();
+        }
+        writer.finish().unwrap();
+        (writer.total_size(), writer.metas)
+    };
+    let (size, metas) = write(options.clone());
+    for meta in &metas[..4] {
+        // a dictionary per column chunk
+        let dictionaries = meta
+            .pages
+            .iter()
+            .map(|page| page.dictionary.as_ref().unwrap().offset)
+            .collect::<std::collections::BTreeSet<_>>();
+        assert_eq!(dictionaries.len(), 2);
+    }
+    assert!(metas[4].pages.iter().all(|page| page.dictionary.is_none()));
+
+    let (size_without_dictionary, _) = write(WriteOptions {
+        column_dictionary: false,
+        ..options
+    });
+    assert!(size < size_without_dictionary);
+}
+
+#[test]
+fn test_max_page_bytes() {
+    let size = 10000;
+    let chunk = Chunk::new(vec![
+        Box::new(Int8Array::from_vec(vec![1; size])) as _,
+        Box::new(Utf8Array::<i32>::from_iter_values(
+            (0..size).map(|i| format!("{i:0>100}")),
+        )) as _,
+    ]);
+    let options = WriteOptions {
+        default_compression: CommonCompression::Lz4,
+        max_page_size: Some(WRITE_PAGE),
+        max_page_bytes: Some(64 * 1024),
+        default_compress_ratio: Some(2.0f64),
+        forbidden_compressions: vec![],
+        forced_compression: None,
+        forced_compressions: vec![],
+        page_checksum: false,
+        column_dictionary: false,
+        column_options: vec![],
+    };
+
+    let schema = Schema::from(vec![
+        Field::new("a", DataType::Int8, false),
+        Field::new("b", DataType::Utf8, false),
+    ]);
+    let mut writer = NativeWriter::new(vec![], schema, options.clone());
+    writer.start().unwrap();
+    writer.write(&chunk).unwrap();
+    writer.finish().unwrap();
+
+    // the i8 column is limited by `max_page_size`
+    assert_eq!(
+        writer.metas[0].pages.len(),
+        (size + WRITE_PAGE - 1) / WRITE_PAGE
+    );
+    // the string column is limited by `max_page_bytes`, about 104 bytes per row
+    for page in writer.metas[1].pages.iter() {
+        assert!(page.num_values * 104 <= 64 * 1024);
+    }
+    assert!(writer.metas[1].pages.len() > writer.metas[0].pages.len());
+
+    test_write_read_with_options(chunk, options);
+}
+
 #[test]
 fn test_struct() {
     let struct_array = create_struct(1000, 0.2, 1000);
@@ -178,6 +610,221 @@ fn test_float() {
     test_write_read(chunk);
 }
 
+#[test]
+fn test_float16() {
+    let size = 10000;
+    let chunk = Chunk::new(vec![
+        Box::new(create_random_float16(size, 0.1, 50)) as _,
+        Box::new(create_random_float16(size, 0.0, size)) as _,
+        // the repeated values of types narrower than f64 in patas pages
+        Box::new(
+            create_random_float16(size, 0.1, 50)
+                .iter()
+                .map(|v| v.map(|v| v.to_f32()))
+                .collect::<Float32Array>(),
+        ) as _,
+    ]);
+    test_write_read(chunk.clone());
+
+    // the write fails for a page a forced compression doesn't suit, so the data suits them
+    let repeated = Chunk::new(vec![chunk.arrays()[0].clone(), chunk.arrays()[2].clone()]);
+    let frequent = Chunk::new(vec![create_random_float16(size, 0.05, 1).boxed()]);
+    for (chunk, compression) in [
+        (repeated.clone(), Compression::Patas),
+        (repeated.clone(), Compression::Rle),
+        (repeated, Compression::Dict),
+        (frequent, Compression::Freq),
+    ] {
+        test_write_read_with_options(
+            chunk,
+            WriteOptions {
+                max_page_size: Some(WRITE_PAGE),
+                forced_compression: Some(compression),
+                ..Default::default()
+            },
+        );
+    }
+}
+
+#[test]
+fn test_interval() {
+    let size = 10000;
+    let (day_time, month_day_nano) = create_random_interval(size, 0.1, 100);
+    let chunk = Chunk::new(vec![day_time.clone().boxed(), month_day_nano.boxed()]);
+    test_write_read(chunk);
+
+    let (offsets, validity) = create_random_offsets(2000, 0.1);
+    let length = *offsets.last().unwrap() as usize;
+    let list_array = ListArray::try_new(
+        DataType::List(Box::new(Field::new(
+            "item",
+            day_time.data_type().clone(),
+            true,
+        ))),
+        OffsetsBuffer::try_from(offsets).unwrap(),
+        day_time.sliced(0, length).boxed(),
+        validity,
+    )
+    .unwrap();
+    test_write_read(Chunk::new(vec![list_array.boxed()]));
+}
+
+#[test]
+fn test_union() {
+    let size = 10000;
+    let sparse = create_random_union(size, true);
+    let dense = create_random_union(size, false);
+    test_write_read(Chunk::new(vec![
+        sparse.clone().boxed(),
+        dense.clone().boxed(),
+    ]));
+
+    // the type ids and offsets are forced to the integer compressions
+    for (union, compression) in [(dense, Compression::Rle), (sparse, Compression::Dict)] {
+        test_write_read_with_options(
+            Chunk::new(vec![union.boxed()]),
+            WriteOptions {
+                default_compression: CommonCompression::Lz4,
+                max_page_size: Some(WRITE_PAGE),
+                forced_compressions: vec![(PhysicalType::Union, compression)],
+                ..Default::default()
+            },
+        );
+    }
+
+    // only the top level unions are supported
+    let union = create_random_union(100, true).boxed();
+    let union_field = Field::new("u", union.data_type().clone(), true);
+    let nested = [
+        StructArray::new(
+            DataType::Struct(vec![union_field.clone()]),
+            vec![union.clone()],
+            None,
+        )
+        .boxed(),
+        ListArray::<i32>::new(
+            DataType::List(Box::new(union_field)),
+            OffsetsBuffer::try_from(vec![0, 50, 100]).unwrap(),
+            union,
+            None,
+        )
+        .boxed(),
+    ];
+    for array in nested {
+        let schema = Schema::from(vec![Field::new("a", array.data_type().clone(), true)]);
+        let mut writer = NativeWriter::new(vec![], schema, WriteOptions::default());
+        writer.start().unwrap();
+        let err = writer.write(&Chunk::new(vec![array])).unwrap_err();
+        assert!(
+            matches!(err, arrow::error::Error::NotYetImplemented(_)),
+            "{err}"
+        );
+    }
+}
+
+#[test]
+fn test_variant() {
+    let size = 10000;
+    let variant = create_random_variant(size, 0.1);
+    let required = create_random_variant(size, 0.0);
+    test_write_read(Chunk::new(vec![variant.clone().boxed(), required.boxed()]));
+
+    let (offsets, validity) = create_random_offsets(2000, 0.1);
+    let length = *offsets.last().unwrap() as usize;
+    let list_array = ListArray::try_new(
+        DataType::List(Box::new(Field::new("item", variant_data_type(), true))),
+        OffsetsBuffer::try_from(offsets).unwrap(),
+        variant.sliced(0, length).boxed(),
+        validity,
+    )
+    .unwrap();
+    test_write_read(Chunk::new(vec![list_array.boxed()]));
+}
+
+#[test]
+fn test_logical_types() {
+    let size = 3000;
+    let int64 = create_random_index(size, 0.1, 100)
+        .iter()
+        .map(|v| v.map(|v| *v as i64))
+        .collect::<Int64Array>();
+    let int128 = int64
+        .iter()
+        .map(|v| v.map(|v| *v as i128))
+        .collect::<PrimitiveArray<i128>>();
+    let string = create_random_string(size, 0.1, 100);
+    let utf8 = string
+        .iter()
+        .map(|v| v.map(|v| std::str::from_utf8(v).unwrap()))
+        .collect::<Utf8Array<i32>>();
+    let extension = |name: &str, inner: DataType| {
+        DataType::Extension(
+            name.to_string(),
+            Box::new(inner),
+            Some("{\"a\":1}".to_string()),
+        )
+    };
+
+    let timestamp = DataType::Timestamp(TimeUnit::Millisecond, Some("+08:00".to_string()));
+    let mut arrays = vec![
+        int64.clone().to(timestamp.clone()).boxed(),
+        int64
+            .clone()
+            .to(DataType::Time64(TimeUnit::Microsecond))
+            .boxed(),
+        int64
+            .clone()
+            .to(DataType::Duration(TimeUnit::Second))
+            .boxed(),
+        create_random_index(size, 0.1, 100)
+            .to(DataType::Date32)
+            .boxed(),
+        int128.clone().to(DataType::Decimal(20, 3)).boxed(),
+        int64
+            .clone()
+            .to(extension("ext.timestamp", timestamp))
+            .boxed(),
+        int128
+            .to(extension("ext.decimal", DataType::Decimal(20, 3)))
+            .boxed(),
+        create_random_double(size, 0.1, 100)
+            .to(extension("ext.double", DataType::Float64))
+            .boxed(),
+        create_random_bool(size, 0.1).boxed(),
+        utf8.boxed(),
+        string.boxed(),
+        create_random_fixed_binary(size, 0.1, 100, 4).boxed(),
+        create_struct(size, 0.1, 100).boxed(),
+        create_list(size, 0.1).boxed(),
+        create_map(size, 0.1).boxed(),
+        create_random_union(size, true).boxed(),
+        DictionaryArray::try_from_keys(
+            create_random_index(size, 0.1, 10),
+            Utf8Array::<i32>::from_iter_values((0..10).map(|i| format!("value{i}"))).boxed(),
+        )
+        .unwrap()
+        .boxed(),
+    ];
+    // the extensions of the other types, and of the children of nested types
+    for i in 8..arrays.len() {
+        let data_type = extension("ext.other", arrays[i].data_type().clone());
+        arrays.push(with_data_type(arrays[i].as_ref(), data_type));
+    }
+    let item = Field::new("item", extension("ext.item", DataType::Int64), true);
+    let list = ListArray::<i32>::try_new(
+        DataType::List(Box::new(item)),
+        OffsetsBuffer::try_from(vec![0, 2, 2, 5]).unwrap(),
+        int64
+            .sliced(0, 5)
+            .to(extension("ext.item", DataType::Int64))
+            .boxed(),
+        None,
+    )
+    .unwrap();
+    test_write_read(Chunk::new(arrays));
+    test_write_read(Chunk::new(vec![list.boxed()]));
+}
+
 #[test]
 fn test_list() {
     let list_array = create_list(1000, 0.2);
@@ -277,6 +924,259 @@ fn test_struct_list() {
     test_write_read(chunk);
 }
 
+#[test]
+fn test_dictionary() {
+    let size = 10000;
+    let keys = create_random_index(size, 0.1, 100);
+    let values = Utf8Array::<i32>::from_iter_values((0..100).map(|i| format!("value{i}")));
+    let utf8 = DictionaryArray::try_from_keys(keys.clone(), values.boxed()).unwrap();
+    let keys = keys
+        .iter()
+        .map(|key| key.map(|key| *key as u16 % 50))
+        .collect::<UInt16Array>();
+    let values = Int64Array::from_vec((0..50).map(|i| i * 1000).collect());
+    let int64 = DictionaryArray::try_from_keys(keys, values.boxed()).unwrap();
+
+    let chunk = Chunk::new(vec![Box::new(utf8) as _, Box::new(int64) as _]);
+    test_write_read(chunk.clone());
+    test_write_read_with_options(
+        chunk,
+        WriteOptions {
+            max_page_size: Some(WRITE_PAGE),
+            page_checksum: true,
+            column_dictionary: true,
+            ..Default::default()
+        },
+    );
+}
+
+#[test]
+fn test_list_dictionary() {
+    let (offsets, validity) = create_random_offsets(2000, 0.1);
+    let length = *offsets.last().unwrap() as usize;
+    let keys = create_random_index(length, 0.2, 20);
+    let values = BinaryArray::<i64>::from_iter_values((0..20).map(|i| format!("{i:0>10}")));
+    let dictionary = DictionaryArray::try_from_keys(keys, values.boxed()).unwrap();
+    let list_array = ListArray::try_new(
+        DataType::List(Box::new(Field::new(
+            "item",
+            dictionary.data_type().clone(),
+            true,
+        ))),
+        OffsetsBuffer::try_from(offsets).unwrap(),
+        dictionary.boxed(),
+        validity,
+    )
+    .unwrap();
+
+    let chunk = Chunk::new(vec![Box::new(list_array) as _]);
+    test_write_read(chunk);
+}
+
+#[test]
+fn test_dictionary_null_values() {
+    let values = Utf8Array::<i32>::from([Some("a"), None, Some("c")]);
+    let keys = Int8Array::from([Some(0), Some(1), Some(2), Some(1), None]);
+    let dictionary = DictionaryArray::try_from_keys(keys, values.clone().boxed()).unwrap();
+    let schema = Schema::from(vec![Field::new("a", dictionary.data_type().clone(), true)]);
+
+    let mut bytes = vec![];
+    let mut writer = NativeWriter::new(&mut bytes, schema.clone(), WriteOptions::default());
+    writer.start().unwrap();
+    writer.write(&Chunk::new(vec![dictionary.boxed()])).unwrap();
+    writer.finish().unwrap();
+
+    let reader = NativeFileReader::try_new(std::io::Cursor::new(bytes)).unwrap();
+    assert_eq!(reader.schema(), schema);
+    // the keys of the null values are read as nulls
+    let keys = Int8Array::from([Some(0), None, Some(2), None, None]);
+    let expected = DictionaryArray::try_from_keys(keys, values.boxed()).unwrap();
+    assert_eq!(
+        reader.read_all().unwrap().arrays()[0].as_ref(),
+        &expected as &dyn Array
+    );
+}
+
+#[test]
+fn test_fixed_size_binary() {
+    let size = 10000;
+    let chunk = Chunk::new(vec![
+        Box::new(create_random_fixed_binary(size, 0.0, size, 16)) as _,
+        Box::new(create_random_fixed_binary(size, 0.1, 20, 16)) as _,
+        Box::new(create_random_fixed_binary(size, 0.1, 1, 32)) as _,
+    ]);
+    test_write_read(chunk.clone());
+    test_write_read_with_options(
+        chunk.clone(),
+        WriteOptions {
+            max_page_size: Some(WRITE_PAGE),
+            column_dictionary: true,
+            ..Default::default()
+        },
+    );
+
+    for compression in [
+        Compression::Dict,
+        Compression::ByteStreamSplit,
+        Compression::Zstd,
+    ] {
+        let array = chunk.arrays()[1].clone();
+        let field = Field::new("a", array.data_type().clone(), true);
+        let options = WriteOptions {
+            default_compression: CommonCompression::Lz4,
+            max_page_size: Some(WRITE_PAGE),
+            forced_compression: Some(compression),
+            ..Default::default()
+        };
+        let mut bytes = vec![];
+        let mut writer = NativeWriter::new(
+            &mut bytes,
+            Schema::from(vec![field.clone()]),
+            options.clone(),
+        );
+        writer.start().unwrap();
+        writer.write(&Chunk::new(vec![array])).unwrap();
+        writer.finish().unwrap();
+
+        let meta = &writer.metas[0];
+        let mut reader = std::io::Cursor::new(bytes.clone());
+        reader.consume(meta.offset as usize);
+        let reader = NativeReader::new(reader, meta.pages.clone(), vec![]);
+        let info = stat_simple(reader, field).unwrap();
+        for page in info.pages {
+            let page_compression = match page.body {
+                PageBody::Dict(_) => Compression::Dict,
+                PageBody::ByteStreamSplit => Compression::ByteStreamSplit,
+                PageBody::Common(c) => c.to_compression(),
+                other => panic!("unexpected page {other:?}"),
+            };
+            assert_eq!(page_compression, compression);
+        }
+        let chunk = Chunk::new(chunk.arrays()[1..].to_vec());
+        test_write_read_with_options(chunk, options);
+    }
+}
+
+#[test]
+fn test_list_fixed_size_binary() {
+    let (offsets, validity) = create_random_offsets(2000, 0.1);
+    let length = *offsets.last().unwrap() as usize;
+    let values = create_random_fixed_binary(length, 0.2, 30, 8);
+    let list_array = ListArray::try_new(
+        DataType::List(Box::new(Field::new(
+            "item",
+            values.data_type().clone(),
+            true,
+        ))),
+        OffsetsBuffer::try_from(offsets).unwrap(),
+        values.boxed(),
+        validity,
+    )
+    .unwrap();
+
+    let chunk = Chunk::new(vec![Box::new(list_array) as _]);
+    test_write_read(chunk);
+}
+
+#[test]
+fn test_async_page_stream() {
+    let size = WRITE_PAGE * 2 + 10;
+    let chunks = (0..2)
+        .map(|_| Chunk::new(vec![Box::new(create_random_index(size, 0.1, size)) as _]))
+        .collect::<Vec<Chunk<Box<dyn Array>>>>();
+    let schema = Schema::from(vec![Field::new("a", DataType::Int32, true)]);
+
+    let mut bytes = Vec::new();
+    let mut writer = NativeWriter::new(
+        &mut bytes,
+        schema,
+        WriteOptions {
+            max_page_size: Some(WRITE_PAGE),
+            ..Default::default()
+        },
+    );
+    writer.start().unwrap();
+    for chunk in chunks.iter() {
+        writer.write(chunk).unwrap();
+    }
+    writer.finish().unwrap();
+    let meta = writer.metas[0].clone();
+
+    let mut reader = std::io::Cursor::new(bytes.clone());
+    reader.consume(meta.offset as usize);
+    let expected = NativeReader::new(reader, meta.pages.clone(), vec![])
+        .collect::<arrow::error::Result<Vec<_>>>()
+        .unwrap();
+    assert_eq!(expected.len(), 6);
+
+    let pages = async_std::task::block_on(async {
+        let mut reader = async_std::io::Cursor::new(bytes);
+        reader
+            .seek(std::io::SeekFrom::Start(meta.offset))
+            .await
+            .unwrap();
+        AsyncNativeReader::new(reader, meta.pages.clone(), vec![])
+            .into_stream()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap()
+    });
+    assert_eq!(expected, pages);
+}
+
+// the array with a logical type of the same physical type
+fn with_data_type(array: &dyn Array, data_type: DataType) -> Box<dyn Array> {
+    let any = array.as_any();
+    let validity = array.validity().cloned();
+    match array.data_type().to_physical_type() {
+        PhysicalType::Boolean => {
+            let array = any.downcast_ref::<BooleanArray>().unwrap();
+            BooleanArray::new(data_type, array.values().clone(), validity).boxed()
+        }
+        PhysicalType::Utf8 => {
+            let array = any.downcast_ref::<Utf8Array<i32>>().unwrap();
+            let (offsets, values) = (array.offsets().clone(), array.values().clone());
+            Utf8Array::new(data_type, offsets, values, validity).boxed()
+        }
+        PhysicalType::LargeBinary => {
+            let array = any.downcast_ref::<BinaryArray<i64>>().unwrap();
+            let (offsets, values) = (array.offsets().clone(), array.values().clone());
+            BinaryArray::new(data_type, offsets, values, validity).boxed()
+        }
+        PhysicalType::FixedSizeBinary => {
+            let array = any.downcast_ref::<FixedSizeBinaryArray>().unwrap();
+            FixedSizeBinaryArray::new(data_type, array.values().clone(), validity).boxed()
+        }
+        PhysicalType::Struct => {
+            let array = any.downcast_ref::<StructArray>().unwrap();
+            StructArray::new(data_type, array.values().to_vec(), validity).boxed()
+        }
+        PhysicalType::List => {
+            let array = any.downcast_ref::<ListArray<i32>>().unwrap();
+            let (offsets, values) = (array.offsets().clone(), array.values().clone());
+            ListArray::new(data_type, offsets, values, validity).boxed()
+        }
+        PhysicalType::Map => {
+            let array = any.downcast_ref::<MapArray>().unwrap();
+            let (offsets, field) = (array.offsets().clone(), array.field().clone());
+            MapArray::new(data_type, offsets, field, validity).boxed()
+        }
+        PhysicalType::Union => {
+            let array = any.downcast_ref::<UnionArray>().unwrap();
+            let (types, fields) = (array.types().clone(), array.fields().clone());
+            UnionArray::new(data_type, types, fields, array.offsets().cloned()).boxed()
+        }
+        PhysicalType::Dictionary(_) => {
+            let array = any.downcast_ref::<DictionaryArray<i32>>().unwrap();
+            let (keys, values) = (array.keys().clone(), array.values().clone());
+            DictionaryArray::try_new(data_type, keys, values)
+                .unwrap()
+                .boxed()
+        }
+        _ => unreachable!(),
+    }
+}
+
 fn create_list(size: usize, null_density: f32) -> ListArray<i32> {
     let (offsets, bitmap) = create_random_offsets(size, 0.1);
     let length = *offsets.last().unwrap() as usize;
@@ -382,6 +1282,114 @@ fn create_random_double(size: usize, null_density: f32, uniq: usize) -> Primitiv
         .collect::<PrimitiveArray<f64>>()
 }
 
+fn create_random_float16(size: usize, null_density: f32, uniq: usize) -> PrimitiveArray<f16> {
+    let mut rng = StdRng::seed_from_u64(42);
+    (0..size)
+        .map(|_| {
+            if rng.gen::<f32>() > null_density {
+                let value = rng.gen_range::<i32, _>(0i32..uniq as i32);
+                Some(f16::from_f32(value as f32 / 8.0))
+            } else {
+                None
+            }
+        })
+        .collect::<PrimitiveArray<f16>>()
+}
+
+fn create_random_interval(
+    size: usize,
+    null_density: f32,
+    uniq: usize,
+) -> (PrimitiveArray<days_ms>, PrimitiveArray<months_days_ns>) {
+    let mut rng = StdRng::seed_from_u64(42);
+    let values = (0..size)
+        .map(|_| {
+            if rng.gen::<f32>() > null_density {
+                let value = rng.gen_range::<i32, _>(0i32..uniq as i32);
+                Some((value % 12, value, value as i64 * 1_000_000))
+            } else {
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+    let day_time = values
+        .iter()
+        .map(|v| v.map(|(_, days, ms)| days_ms::new(days, ms as i32 / 1000)))
+        .collect::<PrimitiveArray<days_ms>>()
+        .to(DataType::Interval(IntervalUnit::DayTime));
+    let month_day_nano = values
+        .iter()
+        .map(|v| v.map(|(months, days, ns)| months_days_ns::new(months, days, ns)))
+        .collect::<PrimitiveArray<months_days_ns>>()
+        .to(DataType::Interval(IntervalUnit::MonthDayNano));
+    (day_time, month_day_nano)
+}
+
+fn create_random_union(size: usize, is_sparse: bool) -> UnionArray {
+    let mut rng = StdRng::seed_from_u64(42);
+    let fields = vec![
+        Field::new("int", DataType::Int32, true),
+        Field::new("str", DataType::LargeBinary, true),
+        Field::new("list", create_list(1, 0.2).data_type().clone(), true),
+    ];
+    // the children of the dense union are longer than needed and the offsets are random
+    let children = vec![
+        create_random_index(size, 0.1, 100).boxed(),
+        create_random_string(size, 0.1, 100).boxed(),
+        create_list(size, 0.2).boxed(),
+    ];
+    let types = (0..size)
+        .map(|_| rng.gen_range::<i8, _>(0..3))
+        .collect::<Vec<_>>();
+    if is_sparse {
+        let types = types
+            .iter()
+            .map(|t| [5, 1, 3][*t as usize])
+            .collect::<Vec<_>>();
+        let data_type = DataType::Union(fields, Some(vec![5, 1, 3]), UnionMode::Sparse);
+        UnionArray::new(data_type, types.into(), children, None)
+    } else {
+        let offsets = (0..size)
+            .map(|_| rng.gen_range::<i32, _>(0..size as i32))
+            .collect::<Vec<_>>();
+        let data_type = DataType::Union(fields, None, UnionMode::Dense);
+        UnionArray::new(data_type, types.into(), children, Some(offsets.into()))
+    }
+}
+
+// the values are normalized JSON text, with the keys of the objects sorted
+fn create_random_variant(size: usize, null_density: f32) -> Utf8Array<i64> {
+    let mut rng = StdRng::seed_from_u64(42);
+    let values = (0..size)
+        .map(|i| {
+            if rng.gen::<f32>() < null_density {
+                return None;
+            }
+            let value = match rng.gen_range(0..10) {
+                // the paths of a few values are missing or of another type
+                0 => format!(r#"{{"id":"id{i}"}}"#),
+                1 => format!(r#"[{i},"item"]"#),
+                2 => format!("{:?}", i as f64 / 4.0),
+                _ => format!(
+                    r#"{{"id":{},"name":"name{}","score":{:?},"tags":{{"hot":{},"list":[1,2]}}}}"#,
+                    rng.gen_range(0..100),
+                    rng.gen_range(0..20),
+                    rng.gen_range(0..1000) as f64 / 8.0,
+                    rng.gen::<bool>(),
+                ),
+            };
+            Some(value)
+        })
+        .collect::<Utf8Array<i64>>();
+    Utf8Array::try_new(
+        variant_data_type(),
+        values.offsets().clone(),
+        values.values().clone(),
+        values.validity().cloned(),
+    )
+    .unwrap()
+}
+
 fn create_random_string(size: usize, null_density: f32, uniq: usize) -> BinaryArray<i64> {
     let mut rng = StdRng::seed_from_u64(42);
     (0..size)
@@ -396,6 +1404,29 @@ fn create_random_string(size: usize, null_density: f32, uniq: usize) -> BinaryAr
         .collect::<BinaryArray<i64>>()
 }
 
+// the values have leading zero bytes, as small decimals
+fn create_random_fixed_binary(
+    size: usize,
+    null_density: f32,
+    uniq: usize,
+    width: usize,
+) -> FixedSizeBinaryArray {
+    let mut rng = StdRng::seed_from_u64(42);
+    let mut values = Vec::with_capacity(size * width);
+    let mut validity = MutableBitmap::with_capacity(size);
+    for _ in 0..size {
+        let value = rng.gen_range::<u32, _>(0u32..uniq as u32);
+        values.extend_from_slice(&value.to_le_bytes());
+        values.resize(values.len() + width - 4, 0);
+        validity.push(rng.gen::<f32>() > null_density);
+    }
+    FixedSizeBinaryArray::new(
+        DataType::FixedSizeBinary(width),
+        values.into(),
+        validity.into(),
+    )
+}
+
 fn create_random_offsets(size: usize, null_density: f32) -> (Vec<i32>, Option<Bitmap>) {
     let mut offsets = Vec::with_capacity(size + 1);
     offsets.push(0i32);
@@ -419,7 +1450,7 @@ fn test_write_read(chunk: Chunk<Box<dyn Array>>) {
 
     let compressions = vec![
         CommonCompression::Lz4,
-        CommonCompression::Zstd,
+        CommonCompression::Zstd(0),
         CommonCompression::Snappy,
         CommonCompression::None,
     ];
@@ -430,16 +1461,26 @@ fn test_write_read(chunk: Chunk<Box<dyn Array>>) {
             WriteOptions {
                 default_compression: compression,
                 max_page_size: Some(WRITE_PAGE),
+                max_page_bytes: None,
                 default_compress_ratio: Some(2.0f64),
                 forbidden_compressions: vec![],
+                forced_compression: None,
+                forced_compressions: vec![],
+                page_checksum: false,
+                column_dictionary: false,
+                column_options: vec![],
             },
         );
     }
 }
 
 fn test_write_read_with_options(chunk: Chunk<Box<dyn Array>>, options: WriteOptions) {
+    test_write_read_chunks_with_options(vec![chunk], options)
+}
+
+fn test_write_read_chunks_with_options(chunks: Vec<Chunk<Box<dyn Array>>>, options: WriteOptions) {
     let mut bytes = Vec::new();
-    let fields: Vec<Field> = chunk
+    let fields: Vec<Field> = chunks[0]
         .iter()
         .map(|array| {
             Field::new(
@@ -454,12 +1495,23 @@ fn test_write_read_with_options(chunk: Chunk<Box<dyn Array>>, options: WriteOpti
     let mut writer = NativeWriter::new(&mut bytes, schema.clone(), options);
 
     writer.start().unwrap();
-    writer.write(&chunk).unwrap();
+    for chunk in chunks.iter() {
+        writer.write(chunk).unwrap();
+    }
     writer.finish().unwrap();
 
     log::info!("write finished, start to read");
 
+    let columns = (0..schema.fields.len())
+        .map(|i| {
+            let arrays: Vec<&dyn Array> = chunks.iter().map(|c| c.arrays()[i].as_ref()).collect();
+            compute::concatenate::concatenate(&arrays).unwrap()
+        })
+        .collect();
+    let chunk = Chunk::new(columns);
+
     let mut batch_metas = writer.metas.clone();
+    let mut async_metas = writer.metas.clone();
     let mut metas = writer.metas.clone();
     let schema_descriptor = to_parquet_schema(&schema).unwrap();
     let mut leaves = schema_descriptor.columns().to_vec();
@@ -525,4 +1577,36 @@ fn test_write_read_with_options(chunk: Chunk<Box<dyn Array>>, options: WriteOpti
     let batch_result_chunk = Chunk::new(batch_results);
 
     assert_eq!(chunk, batch_result_chunk);
+
+    // test async read
+    async_std::task::block_on(async {
+        let schema_descriptor = to_parquet_schema(&schema).unwrap();
+        let mut leaves = schema_descriptor.columns().to_vec();
+        let mut async_results = Vec::with_capacity(schema.fields.len());
+        for field in schema.fields.iter() {
+            let n = n_columns(&field.data_type);
+
+            let curr_metas: Vec<ColumnMeta> = async_metas.drain(..n).collect();
+            let curr_leaves: Vec<ColumnDescriptor> = leaves.drain(..n).collect();
+
+            let mut pages: Vec<Vec<PageMeta>> = Vec::with_capacity(n);
+            let mut readers = Vec::with_capacity(n);
+            for curr_meta in curr_metas.iter() {
+                pages.push(curr_meta.pages.clone());
+                let mut reader = async_std::io::Cursor::new(bytes.clone());
+                reader
+                    .seek(std::io::SeekFrom::Start(curr_meta.offset))
+                    .await
+                    .unwrap();
+                readers.push(reader);
+            }
+            let is_nested = !is_primitive(field.data_type());
+            let async_result =
+                batch_read_array_async(readers, curr_leaves, field.clone(), is_nested, pages)
+                    .await
+                    .unwrap();
+            async_results.push(async_result);
+        }
+        assert_eq!(chunk, Chunk::new(async_results));
+    });
 }
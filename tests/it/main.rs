@@ -18,5 +18,7 @@
 // this landed on 1.60. Let's not force everyone to bump just yet
 #![allow(clippy::unnecessary_lazy_evaluations)]
 
+mod file_reader;
 mod io;
+mod pruning;
 mod read_meta;
@@ -0,0 +1,191 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use arrow::{
+    array::{Array, Float64Array, Int64Array, Utf8Array},
+    chunk::Chunk,
+    datatypes::{DataType, Field, Schema},
+    error::Result,
+    scalar::{PrimitiveScalar, Utf8Scalar},
+};
+use strawboat::{
+    read::{
+        pruning::{prune_pages, CompareOp, Predicate},
+        reader::{infer_schema, read_meta},
+    },
+    write::{NativeWriter, WriteOptions},
+};
+
+const PAGE_SIZE: usize = 100;
+
+fn write_data() -> Vec<u8> {
+    // a: 0..1000 sorted, b: "x" in rows [200, 300), "y" for others
+    let size = PAGE_SIZE * 10;
+    let chunk = Chunk::new(vec![
+        Box::new(Int64Array::from_vec((0..size as i64).collect())) as Box<dyn Array>,
+        Box::new(Utf8Array::<i32>::from_iter_values((0..size).map(|i| {
+            if (200..300).contains(&i) {
+                "x"
+            } else {
+                "y"
+            }
+        }))) as _,
+    ]);
+    let schema = Schema::from(vec![
+        Field::new("a", DataType::Int64, false),
+        Field::new("b", DataType::Utf8, false),
+    ]);
+
+    let mut buf = vec![];
+    let mut writer = NativeWriter::new(
+        &mut buf,
+        schema,
+        WriteOptions {
+            max_page_size: Some(PAGE_SIZE),
+            ..Default::default()
+        },
+    );
+    writer.start().unwrap();
+    writer.write(&chunk).unwrap();
+    writer.finish().unwrap();
+    buf
+}
+
+fn read_pages(predicate: &Predicate) -> Result<Vec<Vec<usize>>> {
+    prune(write_data(), predicate)
+}
+
+// the indices of the pages which are not skipped
+fn prune(buf: Vec<u8>, predicate: &Predicate) -> Result<Vec<Vec<usize>>> {
+    let mut reader = std::io::Cursor::new(buf);
+    let schema = infer_schema(&mut reader)?;
+    let metas = read_meta(&mut reader)?;
+
+    let skips = prune_pages(&schema, &metas, predicate)?;
+    Ok(skips
+        .iter()
+        .map(|skip| (0..skip.len()).filter(|i| !skip[*i]).collect())
+        .collect())
+}
+
+fn int64(v: i64) -> Box<PrimitiveScalar<i64>> {
+    Box::new(PrimitiveScalar::new(DataType::Int64, Some(v)))
+}
+
+#[test]
+fn test_prune_compare() -> Result<()> {
+    let pages = read_pages(&Predicate::compare("a", CompareOp::Gt, int64(850)))?;
+    assert_eq!(pages, vec![vec![8, 9], vec![8, 9]]);
+
+    let pages = read_pages(&Predicate::compare("a", CompareOp::LtEq, int64(100)))?;
+    assert_eq!(pages, vec![vec![0, 1], vec![0, 1]]);
+
+    let pages = read_pages(&Predicate::compare("a", CompareOp::Eq, int64(2000)))?;
+    assert_eq!(pages, vec![Vec::<usize>::new(), vec![]]);
+
+    let pages = read_pages(&Predicate::compare(
+        "b",
+        CompareOp::Eq,
+        Box::new(Utf8Scalar::<i32>::new(Some("x"))),
+    ))?;
+    assert_eq!(pages, vec![vec![2], vec![2]]);
+
+    let pages = read_pages(&Predicate::compare(
+        "b",
+        CompareOp::NotEq,
+        Box::new(Utf8Scalar::<i32>::new(Some("y"))),
+    ))?;
+    assert_eq!(pages, vec![vec![2], vec![2]]);
+
+    let pages = read_pages(&Predicate::IsNull("a".to_string()))?;
+    assert_eq!(pages, vec![Vec::<usize>::new(), vec![]]);
+    Ok(())
+}
+
+#[test]
+fn test_prune_and_or() -> Result<()> {
+    let x = || {
+        Predicate::compare(
+            "b",
+            CompareOp::Eq,
+            Box::new(Utf8Scalar::<i32>::new(Some("x"))),
+        )
+    };
+    let pages = read_pages(&Predicate::compare("a", CompareOp::Gt, int64(250)).and(x()))?;
+    assert_eq!(pages, vec![vec![2], vec![2]]);
+
+    let pages = read_pages(&Predicate::compare("a", CompareOp::Gt, int64(850)).or(x()))?;
+    assert_eq!(pages, vec![vec![2, 8, 9], vec![2, 8, 9]]);
+
+    let pages = read_pages(&Predicate::compare("a", CompareOp::Lt, int64(100)).and(x()))?;
+    assert_eq!(pages, vec![Vec::<usize>::new(), vec![]]);
+    Ok(())
+}
+
+#[test]
+fn test_prune_nan() -> Result<()> {
+    let chunk = Chunk::new(vec![Float64Array::from_vec(vec![
+        3.0,
+        f64::NAN,
+        3.0,
+        3.0,
+        f64::NAN,
+        f64::NAN,
+        1.0,
+        7.0,
+    ])
+    .boxed()]);
+    let schema = Schema::from(vec![Field::new("f", DataType::Float64, false)]);
+    let mut buf = vec![];
+    let mut writer = NativeWriter::new(
+        &mut buf,
+        schema,
+        WriteOptions {
+            max_page_size: Some(2),
+            ..Default::default()
+        },
+    );
+    writer.start()?;
+    writer.write(&chunk)?;
+    writer.finish()?;
+
+    let float64 = |v: f64| Box::new(PrimitiveScalar::new(DataType::Float64, Some(v)));
+    let cases = [
+        // NaN matches these predicates of any value
+        (CompareOp::NotEq, 3.0, vec![0, 2, 3]),
+        (CompareOp::Gt, 5.0, vec![0, 2, 3]),
+        (CompareOp::GtEq, 5.0, vec![0, 2, 3]),
+        (CompareOp::Eq, 3.0, vec![0, 1, 3]),
+        (CompareOp::Lt, 2.0, vec![3]),
+    ];
+    for (op, value, expected) in cases {
+        let pages = prune(buf.clone(), &Predicate::compare("f", op, float64(value)))?;
+        assert_eq!(pages, vec![expected], "{op:?} {value}");
+    }
+    Ok(())
+}
+
+#[test]
+fn test_prune_invalid() {
+    assert!(read_pages(&Predicate::compare("c", CompareOp::Eq, int64(1))).is_err());
+    assert!(read_pages(&Predicate::compare(
+        "a",
+        CompareOp::Eq,
+        Box::new(Utf8Scalar::<i32>::new(Some("x")))
+    ))
+    .is_err());
+}
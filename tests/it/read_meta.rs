@@ -16,13 +16,27 @@
 // under the License.
 
 use arrow::{
-    datatypes::{Field, Schema},
+    array::{
+        Array, BinaryArray, BooleanArray, FixedSizeBinaryArray, Float16Array, Float64Array,
+        Int128Array, Int32Array, Utf8Array,
+    },
+    bitmap::Bitmap,
+    chunk::Chunk,
+    datatypes::{DataType, Field, Schema},
     error::Result,
+    types::f16,
 };
 use strawboat::{
-    read::reader::{read_meta, read_meta_async},
+    read::{
+        reader::{
+            infer_schema, infer_schema_async, read_file_meta, read_file_meta_async,
+            read_format_version, read_meta, read_meta_async,
+        },
+        NativeFileReader,
+    },
+    statistics::{decode_value, STATISTICS_TRUNCATE_LENGTH},
     write::{NativeWriter, WriteOptions},
-    ColumnMeta, CommonCompression,
+    ColumnMeta, CommonCompression, FORMAT_VERSION,
 };
 
 use crate::io::{new_test_chunk, WRITE_PAGE};
@@ -95,3 +109,288 @@ async fn test_read_meta_async_impl() -> Result<()> {
     }
     Ok(())
 }
+
+#[test]
+fn test_read_meta_statistics() -> Result<()> {
+    let chunk = Chunk::new(vec![
+        Box::new(Int32Array::from([
+            Some(3),
+            None,
+            Some(-1),
+            Some(5),
+            None,
+            Some(2),
+        ])) as _,
+        Box::new(Float64Array::from([
+            None,
+            Some(1.5),
+            Some(f64::NAN),
+            Some(-2.5),
+            None,
+            None,
+        ])) as _,
+        Box::new(Utf8Array::<i32>::from([
+            Some("b"),
+            Some("a"),
+            None,
+            Some("d"),
+            Some("c"),
+            None,
+        ])) as Box<dyn Array>,
+    ]);
+    let fields: Vec<Field> = chunk
+        .iter()
+        .map(|array| Field::new("name", array.data_type().clone(), true))
+        .collect();
+
+    let mut buf = vec![];
+    let mut writer = NativeWriter::new(
+        &mut buf,
+        Schema::from(fields),
+        WriteOptions {
+            max_page_size: Some(3),
+            ..Default::default()
+        },
+    );
+    writer.start()?;
+    writer.write(&chunk)?;
+    writer.finish()?;
+
+    let mut reader = std::io::Cursor::new(buf);
+    let metas = read_meta(&mut reader)?;
+    assert_eq!(metas.len(), 3);
+
+    let int_meta = &metas[0];
+    assert_eq!(int_meta.statistics.null_count, 2);
+    assert_eq!(
+        decode_value::<i32>(int_meta.statistics.min_value.as_ref().unwrap()),
+        -1
+    );
+    assert_eq!(
+        decode_value::<i32>(int_meta.statistics.max_value.as_ref().unwrap()),
+        5
+    );
+    assert_eq!(int_meta.pages.len(), 2);
+    let page = &int_meta.pages[1].statistics;
+    assert_eq!(page.null_count, 1);
+    assert_eq!(decode_value::<i32>(page.min_value.as_ref().unwrap()), 2);
+    assert_eq!(decode_value::<i32>(page.max_value.as_ref().unwrap()), 5);
+
+    let float_meta = &metas[1];
+    assert_eq!(float_meta.statistics.null_count, 3);
+    assert_eq!(
+        decode_value::<f64>(float_meta.statistics.min_value.as_ref().unwrap()),
+        -2.5
+    );
+    // NaN is the largest value
+    assert!(decode_value::<f64>(float_meta.statistics.max_value.as_ref().unwrap()).is_nan());
+    let page = &float_meta.pages[0].statistics;
+    assert_eq!(page.null_count, 1);
+    assert_eq!(decode_value::<f64>(page.min_value.as_ref().unwrap()), 1.5);
+    assert!(decode_value::<f64>(page.max_value.as_ref().unwrap()).is_nan());
+    let page = &float_meta.pages[1].statistics;
+    assert_eq!(decode_value::<f64>(page.min_value.as_ref().unwrap()), -2.5);
+    assert_eq!(decode_value::<f64>(page.max_value.as_ref().unwrap()), -2.5);
+
+    let utf8_meta = &metas[2];
+    assert_eq!(utf8_meta.statistics.null_count, 2);
+    assert_eq!(
+        utf8_meta.statistics.min_value.as_deref(),
+        Some(b"a".as_ref())
+    );
+    assert_eq!(
+        utf8_meta.statistics.max_value.as_deref(),
+        Some(b"d".as_ref())
+    );
+    assert_eq!(
+        utf8_meta.pages[1].statistics.min_value.as_deref(),
+        Some(b"c".as_ref())
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_read_meta_statistics_types() -> Result<()> {
+    let long_binary = [vec![1], vec![u8::MAX; 99]].concat();
+    let chunk = Chunk::new(vec![
+        Box::new(Utf8Array::<i32>::from([
+            Some("a".repeat(100)),
+            None,
+            Some("b".repeat(70)),
+        ])) as Box<dyn Array>,
+        Box::new(BinaryArray::<i64>::from([
+            Some(long_binary.clone()),
+            None,
+            Some(long_binary.clone()),
+        ])) as _,
+        Box::new(BinaryArray::<i32>::from([
+            Some(long_binary.clone()),
+            Some(vec![u8::MAX; 100]),
+            None,
+        ])) as _,
+        Box::new(Float16Array::from([
+            Some(f16::from_f32(1.5)),
+            Some(f16::from_f32(f32::NAN)),
+            Some(f16::from_f32(-0.5)),
+        ])) as _,
+        // the null slot holds the smallest value
+        Box::new(FixedSizeBinaryArray::new(
+            DataType::FixedSizeBinary(2),
+            vec![3, 4, 0, 0, 1, 2].into(),
+            Some(Bitmap::from([true, false, true])),
+        )) as _,
+        Box::new(Int128Array::from([Some(5), None, Some(-7)]).to(DataType::Decimal(10, 2))) as _,
+        Box::new(BooleanArray::from([Some(true), None, Some(true)])) as _,
+    ]);
+    let fields: Vec<Field> = chunk
+        .iter()
+        .map(|array| Field::new("name", array.data_type().clone(), true))
+        .collect();
+
+    let mut buf = vec![];
+    let mut writer = NativeWriter::new(&mut buf, Schema::from(fields), WriteOptions::default());
+    writer.start()?;
+    writer.write(&chunk)?;
+    writer.finish()?;
+
+    let metas = read_meta(&mut std::io::Cursor::new(buf))?;
+    let min_max = |i: usize| {
+        let statistics = &metas[i].statistics;
+        (
+            statistics.min_value.clone().unwrap(),
+            statistics.max_value.clone().unwrap(),
+        )
+    };
+
+    // the truncated max is rounded up
+    let (min, max) = min_max(0);
+    assert_eq!(min, vec![b'a'; STATISTICS_TRUNCATE_LENGTH]);
+    assert_eq!(
+        max,
+        [vec![b'b'; STATISTICS_TRUNCATE_LENGTH - 1], vec![b'c']].concat()
+    );
+    let (min, max) = min_max(1);
+    assert_eq!(min, long_binary[..STATISTICS_TRUNCATE_LENGTH].to_vec());
+    assert_eq!(max, vec![2]);
+    // the max is kept if it can't be rounded up
+    let (min, max) = min_max(2);
+    assert_eq!(min, long_binary[..STATISTICS_TRUNCATE_LENGTH].to_vec());
+    assert_eq!(max, vec![u8::MAX; 100]);
+
+    let (min, max) = min_max(3);
+    assert_eq!(decode_value::<f16>(&min), f16::from_f32(-0.5));
+    assert!(decode_value::<f16>(&max).to_f32().is_nan());
+
+    assert_eq!(min_max(4), (vec![1, 2], vec![3, 4]));
+
+    let (min, max) = min_max(5);
+    assert_eq!(decode_value::<i128>(&min), -7);
+    assert_eq!(decode_value::<i128>(&max), 5);
+
+    assert_eq!(min_max(6), (vec![1], vec![1]));
+
+    Ok(())
+}
+
+#[test]
+fn test_format_version() -> Result<()> {
+    let mut buf = Vec::new();
+    write_data(&mut buf);
+    let len = buf.len();
+    assert_eq!(
+        read_format_version(&mut std::io::Cursor::new(&buf))?,
+        FORMAT_VERSION
+    );
+
+    let error = |buf: Vec<u8>| {
+        let mut reader = std::io::Cursor::new(buf);
+        assert!(read_meta(&mut reader).is_err());
+        assert!(infer_schema(&mut reader).is_err());
+        read_format_version(&mut reader).unwrap_err().to_string()
+    };
+
+    let mut corrupted = buf.clone();
+    corrupted[len - 1] = b'X';
+    assert!(error(corrupted).contains("Invalid magic"));
+
+    let mut newer = buf.clone();
+    newer[len - 8..len - 6].copy_from_slice(&(FORMAT_VERSION + 1).to_le_bytes());
+    assert!(error(newer).contains("Unsupported format version"));
+
+    // the layout of the files written without a format version
+    let mut legacy = buf.clone();
+    legacy[..8].copy_from_slice(b"ARROW2\0\0");
+    legacy[len - 8..].copy_from_slice(&[0xff, 0xff, 0xff, 0xff, 0, 0, 0, 0]);
+    assert!(error(legacy).contains("older version of strawboat"));
+
+    // the footer is still valid, the header is checked when the file is opened
+    let mut header = buf;
+    header[0] = b'X';
+    let mut reader = std::io::Cursor::new(header.clone());
+    let err = read_format_version(&mut reader).unwrap_err().to_string();
+    assert!(err.contains("in the header"));
+    let err = read_file_meta(&mut reader).unwrap_err().to_string();
+    assert!(err.contains("in the header"));
+    assert!(NativeFileReader::try_new(reader.clone()).is_err());
+    let err = async_std::task::block_on(async {
+        let mut reader = async_std::io::Cursor::new(header.clone());
+        read_file_meta_async(&mut reader).await
+    })
+    .unwrap_err()
+    .to_string();
+    assert!(err.contains("in the header"));
+
+    // the other readers of the footer don't read the header
+    assert!(read_meta(&mut reader).is_ok());
+    assert!(infer_schema(&mut reader).is_ok());
+    async_std::task::block_on(async {
+        let mut reader = async_std::io::Cursor::new(header.clone());
+        infer_schema_async(&mut reader).await?;
+        read_meta_async(&mut reader, None).await?;
+        read_meta_async(&mut reader, Some(header.len())).await
+    })?;
+    Ok(())
+}
+
+#[test]
+fn test_read_file_meta() -> Result<()> {
+    let chunk = new_test_chunk();
+    let schema = Schema::from(
+        chunk
+            .iter()
+            .map(|array| Field::new("name", array.data_type().clone(), true))
+            .collect::<Vec<_>>(),
+    );
+
+    let mut buf = vec![];
+    let mut writer = NativeWriter::new(&mut buf, schema.clone(), WriteOptions::default());
+    writer.set_key_value_metadata("snapshot_id", "42");
+    writer.start()?;
+    writer.write(&chunk)?;
+    // the metadata can be set after the data is written
+    writer.set_key_value_metadata("row_id_range", "0..100");
+    writer.set_key_value_metadata("snapshot_id", "43");
+    writer.finish()?;
+    let expected_metas = writer.metas;
+
+    let meta = read_file_meta(&mut std::io::Cursor::new(&buf))?;
+    assert_eq!(meta.schema, schema);
+    assert_eq!(meta.metas, expected_metas);
+    assert_eq!(
+        meta.key_value_metadata.into_iter().collect::<Vec<_>>(),
+        vec![
+            ("row_id_range".to_string(), "0..100".to_string()),
+            ("snapshot_id".to_string(), "43".to_string()),
+        ]
+    );
+
+    let async_meta =
+        async_std::task::block_on(read_file_meta_async(&mut async_std::io::Cursor::new(&buf)))?;
+    assert_eq!(async_meta.schema, schema);
+    assert_eq!(async_meta.metas, expected_metas);
+
+    let reader = NativeFileReader::try_new(std::io::Cursor::new(buf))?;
+    assert_eq!(reader.key_value_metadata()["row_id_range"], "0..100");
+    Ok(())
+}